@@ -0,0 +1,37 @@
+//! The errors of broot-core, which carry the messages of broot's
+//! internal errors without exposing their types
+
+use {
+    broot::errors::{ConfError, PatternError, ProgramError, TreeBuildError},
+    custom_error::custom_error,
+};
+
+custom_error! {pub Error
+    Conf {details: String} = "Bad configuration: {details}",
+    Pattern {details: String} = "Invalid pattern: {details}",
+    Search {details: String} = "Search failed: {details}",
+}
+
+impl From<ConfError> for Error {
+    fn from(e: ConfError) -> Self {
+        Self::Conf { details: e.to_string() }
+    }
+}
+
+impl From<PatternError> for Error {
+    fn from(e: PatternError) -> Self {
+        Self::Pattern { details: e.to_string() }
+    }
+}
+
+impl From<TreeBuildError> for Error {
+    fn from(e: TreeBuildError) -> Self {
+        Self::Search { details: e.to_string() }
+    }
+}
+
+impl From<ProgramError> for Error {
+    fn from(e: ProgramError) -> Self {
+        Self::Search { details: e.to_string() }
+    }
+}
@@ -0,0 +1,51 @@
+//! The tree building and searches of [broot](https://dystroy.org/broot),
+//! without its terminal user interface.
+//!
+//! A [`Searcher`] builds trees the way broot displays them, or searches a
+//! whole hierarchy for the matches of a pattern, which may be any pattern
+//! you could type in broot (fuzzy, regular expressions, file contents,
+//! composite patterns, etc.).
+//!
+//! Searches can be cancelled from another thread with a [`CancellationToken`].
+//!
+//! ```no_run
+//! use {
+//!     broot_core::*,
+//!     std::path::Path,
+//! };
+//!
+//! # fn main() -> Result<(), Error> {
+//! let searcher = Searcher::new()?;
+//! let cancellation = CancellationToken::never();
+//! let matches = searcher.search(
+//!     Path::new("."),
+//!     "c/TODO",
+//!     &SearchOptions::default(),
+//!     cancellation.clone(), // call cancellation.cancel() to stop the search
+//! )?;
+//! for m in matches.unwrap_or_default() {
+//!     println!("{} ({})", m.subpath, m.score);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! This crate is a thin facade: it depends on the whole broot crate, whose
+//! internals do the work. Its types, errors included, are its own, so that
+//! its API doesn't change with those internals. The only exception is
+//! [`CancellationToken`], which is broot's.
+
+mod error;
+mod search_match;
+mod search_options;
+mod searcher;
+mod tree_entry;
+
+pub use {
+    broot::task_sync::CancellationToken,
+    error::Error,
+    search_match::SearchMatch,
+    search_options::SearchOptions,
+    searcher::Searcher,
+    tree_entry::TreeEntry,
+};
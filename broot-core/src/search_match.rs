@@ -0,0 +1,24 @@
+use {
+    broot::tree::TreeLine,
+    std::path::PathBuf,
+};
+
+/// a path matching the pattern of a search
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchMatch {
+    pub path: PathBuf,
+    pub subpath: String, // the path relative to the root of the search
+    pub score: i32,      // the bigger the better, 0 for patterns without real scores
+    pub is_dir: bool,
+}
+
+impl From<TreeLine> for SearchMatch {
+    fn from(line: TreeLine) -> Self {
+        Self {
+            is_dir: line.is_dir(),
+            path: line.path,
+            subpath: line.subpath,
+            score: line.score,
+        }
+    }
+}
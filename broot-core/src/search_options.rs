@@ -0,0 +1,38 @@
+use broot::tree::TreeOptions;
+
+/// the options of the trees and of the searches
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchOptions {
+    /// whether files whose name starts with a dot are included
+    pub show_hidden: bool,
+    /// whether the files ignored by git are excluded
+    pub respect_git_ignore: bool,
+    /// whether only directories are included
+    pub only_folders: bool,
+    /// the maximal number of lines of the tree, which is also the one
+    /// of the tree of matches (and parents) kept by a search
+    pub max_lines: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        let options = TreeOptions::default();
+        Self {
+            show_hidden: options.show_hidden,
+            respect_git_ignore: options.respect_git_ignore,
+            only_folders: options.only_folders,
+            max_lines: 100,
+        }
+    }
+}
+
+impl SearchOptions {
+    pub(crate) fn to_tree_options(&self) -> TreeOptions {
+        TreeOptions {
+            show_hidden: self.show_hidden,
+            respect_git_ignore: self.respect_git_ignore,
+            only_folders: self.only_folders,
+            ..TreeOptions::default()
+        }
+    }
+}
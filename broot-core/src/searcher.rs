@@ -0,0 +1,146 @@
+use {
+    crate::{CancellationToken, Error, SearchMatch, SearchOptions, TreeEntry},
+    std::path::Path,
+};
+
+/// builds trees and runs searches the way broot does.
+///
+/// A searcher holds broot's default configuration (search modes,
+/// special paths, etc.) and can be shared between threads.
+pub struct Searcher {
+    searcher: broot::search::Searcher,
+}
+
+impl Searcher {
+    pub fn new() -> Result<Self, Error> {
+        Ok(Self {
+            searcher: broot::search::Searcher::new()?,
+        })
+    }
+
+    /// build the tree of the root as broot would display it, with
+    /// at most `options.max_lines` lines. When the pattern isn't
+    /// empty, the tree shows its best matches.
+    ///
+    /// Return `Ok(None)` if the cancellation token was cancelled
+    pub fn tree(
+        &self,
+        root: &Path,
+        pattern: &str,
+        options: &SearchOptions,
+        cancellation: CancellationToken,
+    ) -> Result<Option<Vec<TreeEntry>>, Error> {
+        let mut tree_options = options.to_tree_options();
+        tree_options.pattern = self.searcher.pattern(pattern)?;
+        let tree = self.searcher.tree(root, tree_options, options.max_lines, cancellation)?;
+        Ok(tree.map(|tree| {
+            tree.lines
+                .iter()
+                .filter(|line| !line.is_pruning())
+                .map(TreeEntry::from)
+                .collect()
+        }))
+    }
+
+    /// search the whole hierarchy under the root and return the
+    /// best matches of the pattern, best first. The pattern may be
+    /// any pattern you'd type in broot, for example `mn` (fuzzy on
+    /// names), `/\.rs$/` (regular expression) or `c/TODO` (file contents).
+    ///
+    /// As they're kept in a tree, the lines of the parent directories
+    /// of the matches count in `options.max_lines`, so that there may
+    /// be fewer matches.
+    ///
+    /// Return `Ok(None)` if the cancellation token was cancelled
+    pub fn search(
+        &self,
+        root: &Path,
+        pattern: &str,
+        options: &SearchOptions,
+        cancellation: CancellationToken,
+    ) -> Result<Option<Vec<SearchMatch>>, Error> {
+        let lines = self.searcher.search(
+            root,
+            pattern,
+            options.to_tree_options(),
+            options.max_lines,
+            cancellation,
+        )?;
+        Ok(lines.map(|lines| lines.into_iter().map(SearchMatch::from).collect()))
+    }
+
+    /// compute the size of a directory, in bytes (on unix, the
+    /// space its files take on disk)
+    pub fn dir_size(&self, path: &Path) -> Option<u64> {
+        self.searcher.dir_sum(path).map(|sum| sum.to_size())
+    }
+}
+
+#[cfg(test)]
+mod searcher_test {
+
+    use {
+        super::*,
+        std::fs,
+    };
+
+    #[test]
+    fn check_search_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for subpath in &["src/main.rs", "src/.main.bak", "doc/main.md"] {
+            let path = root.join(subpath);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "some content").unwrap();
+        }
+        let searcher = Searcher::new().unwrap();
+        let mut options = SearchOptions::default();
+        let matches = searcher
+            .search(root, "main", &options, CancellationToken::never())
+            .unwrap()
+            .unwrap();
+        assert_eq!(matches.len(), 2);
+        assert!(matches.iter().all(|m| !m.is_dir && m.score > 0));
+        options.show_hidden = true;
+        let matches = searcher
+            .search(root, "main", &options, CancellationToken::never())
+            .unwrap()
+            .unwrap();
+        assert!(matches.iter().any(|m| m.subpath == "src/.main.bak"));
+        // without pattern, the tree lists the directories and their content
+        let entries = searcher
+            .tree(root, "", &options, CancellationToken::never())
+            .unwrap()
+            .unwrap();
+        assert_eq!(entries[0].path, root);
+        assert_eq!(entries[0].depth, 0);
+        assert_eq!(entries.len(), 6);
+        options.only_folders = true;
+        let entries = searcher
+            .tree(root, "doc", &options, CancellationToken::never())
+            .unwrap()
+            .unwrap();
+        assert!(entries.iter().any(|e| e.is_match && e.path == root.join("doc")));
+        assert!(searcher.dir_size(root).unwrap() > 0);
+    }
+
+    #[test]
+    fn check_cancelled_search() {
+        let dir = tempfile::tempdir().unwrap();
+        let searcher = Searcher::new().unwrap();
+        let cancellation = CancellationToken::never();
+        cancellation.cancel();
+        let matches = searcher
+            .search(dir.path(), "main", &SearchOptions::default(), cancellation)
+            .unwrap();
+        assert!(matches.is_none());
+    }
+
+    #[test]
+    fn check_invalid_pattern() {
+        let dir = tempfile::tempdir().unwrap();
+        let searcher = Searcher::new().unwrap();
+        let res = searcher.tree(dir.path(), "/[/", &SearchOptions::default(), CancellationToken::never());
+        assert!(matches!(res, Err(Error::Pattern { .. })));
+    }
+}
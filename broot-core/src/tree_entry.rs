@@ -0,0 +1,26 @@
+use {
+    broot::tree::TreeLine,
+    std::path::PathBuf,
+};
+
+/// a line of a tree, as broot would display it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeEntry {
+    pub path: PathBuf,
+    pub depth: u16,      // 0 for the root
+    pub is_dir: bool,
+    pub is_match: bool,  // whether the path matches the pattern (true when there's none)
+    pub unlisted: usize, // number of children of a directory which didn't fit
+}
+
+impl From<&TreeLine> for TreeEntry {
+    fn from(line: &TreeLine) -> Self {
+        Self {
+            path: line.path.clone(),
+            depth: line.depth,
+            is_dir: line.is_dir(),
+            is_match: line.direct_match,
+            unlisted: line.unlisted,
+        }
+    }
+}
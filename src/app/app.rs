@@ -3,27 +3,27 @@ use {
     crate::{
         browser::BrowserState,
         command::{Command, Sequence},
-        conf::Conf,
-        display::{Areas, Screen, W},
+        conf::{self, Conf},
+        display::{Areas, Layout, Screen, W},
         errors::ProgramError,
+        event::{Event, EventSource},
         file_sum, git,
         launchable::Launchable,
-        skin::*,
+        skin::{self, *},
         task_sync::{Dam, Either},
-        verb::Internal,
+        verb::{Internal, PrefixSearchResult, VerbExecution, VerbStore},
     },
     crossbeam::channel::{
         Receiver,
         Sender,
         unbounded,
     },
-    crossterm::event::KeyModifiers,
+    crossterm::event::KeyEvent,
     std::{
         io::Write,
         path::PathBuf,
     },
     strict::NonEmptyVec,
-    termimad::{Event, EventSource},
 };
 
 const ESCAPE_TO_QUIT: bool = false;
@@ -56,15 +56,42 @@ pub struct App {
 
     stage_panel: Option<PanelId>,
 
+    /// the panel listing what the verbs would have done, in dry-run mode
+    dry_run_panel: Option<PanelId>,
+
     /// the root of the active panel
     #[cfg(feature = "client-server")]
     root: Arc<Mutex<PathBuf>>,
 
-    /// sender to the sequence channel
-    tx_seqs: Sender<Sequence>,
+    /// sender to the channel of the messages (sequences, etc.)
+    tx_msgs: Sender<AppMessage>,
+
+    /// receiver to listen to the channel of the messages
+    rx_msgs: Receiver<AppMessage>,
+
+    /// the last command which executed a verb modifying files,
+    /// to be executed again on `:repeat`
+    last_mutating_command: Option<Command>,
+
+    /// the key bindings changed with `:rebind`, which will be
+    /// applied to the verb store after the command
+    key_binding_changes: Vec<(usize, Vec<KeyEvent>)>,
+
+    /// whether the configuration must be read again after the command
+    must_reload_config: bool,
+
+    /// whether the skin must be switched to the one of the other
+    /// background after the command
+    must_toggle_skin: bool,
+
+    /// the relative widths of the panels, when changed by the user
+    layout: Layout,
+
+    /// the panel temporarily taking the whole width, if any
+    zoomed_panel: Option<PanelId>,
 
-    /// receiver to listen to the sequence channel
-    rx_seqs: Receiver<Sequence>,
+    /// the command waiting for the user to confirm its execution
+    pending_confirmation: Option<Command>,
 }
 
 impl App {
@@ -85,10 +112,10 @@ impl App {
                 )?
                 .expect("Failed to create BrowserState"),
             ),
-            Areas::create(&mut Vec::new(), 0, screen, false)?,
+            Areas::create(&mut Vec::new(), 0, screen, false, &Layout::default())?,
             con,
         );
-        let (tx_seqs, rx_seqs) = unbounded::<Sequence>();
+        let (tx_msgs, rx_msgs) = unbounded::<AppMessage>();
         Ok(App {
             screen,
             active_panel_idx: 0,
@@ -98,11 +125,19 @@ impl App {
             created_panels_count: 1,
             preview_panel: None,
             stage_panel: None,
+            dry_run_panel: None,
 
             #[cfg(feature = "client-server")]
             root: Arc::new(Mutex::new(con.launch_args.root.clone())),
-            tx_seqs,
-            rx_seqs,
+            tx_msgs,
+            rx_msgs,
+            last_mutating_command: None,
+            key_binding_changes: Vec::new(),
+            must_reload_config: false,
+            must_toggle_skin: false,
+            layout: Layout::default(),
+            zoomed_panel: None,
+            pending_confirmation: None,
         })
     }
 
@@ -138,6 +173,42 @@ impl App {
         }
     }
 
+    /// recompute the areas of the panels, after a change of the
+    /// screen or of the layout, and refresh their states
+    fn resize_panels(&mut self, con: &AppContext) -> Result<(), ProgramError> {
+        match self.zoomed_panel.and_then(|id| self.panel_id_to_idx(id)) {
+            Some(idx) => {
+                // the other panels keep their areas but aren't displayed
+                Areas::resize_all(
+                    &mut self.panels.as_mut_slice()[idx..=idx],
+                    self.screen,
+                    false,
+                    &Layout::default(),
+                )?;
+            }
+            None => {
+                Areas::resize_all(
+                    self.panels.as_mut_slice(),
+                    self.screen,
+                    self.preview_panel.is_some(),
+                    &self.layout,
+                )?;
+            }
+        }
+        for panel in &mut self.panels {
+            panel.mut_state().on_resize(self.screen, con);
+        }
+        Ok(())
+    }
+
+    /// restore the normal layout if a panel was zoomed
+    fn unzoom(&mut self, con: &AppContext) -> Result<(), ProgramError> {
+        if self.zoomed_panel.take().is_some() {
+            self.resize_panels(con)?;
+        }
+        Ok(())
+    }
+
     /// close the panel if it's not the last one
     ///
     /// Return true when the panel has been removed (ie it wasn't the last one)
@@ -155,6 +226,12 @@ impl App {
                 return false;
             }
         }
+        if let Some(dry_run_id) = self.dry_run_panel {
+            if self.panels.has_len(2) && self.panels[panel_idx].id != dry_run_id {
+                // nor with just the list of the dry-run actions
+                return false;
+            }
+        }
         if let Ok(removed_panel) = self.panels.remove(panel_idx) {
             if self.preview_panel == Some(removed_panel.id) {
                 self.preview_panel = None;
@@ -162,10 +239,15 @@ impl App {
             if self.stage_panel == Some(removed_panel.id) {
                 self.stage_panel = None;
             }
+            if self.dry_run_panel == Some(removed_panel.id) {
+                self.dry_run_panel = None;
+            }
+            self.zoomed_panel = None;
             Areas::resize_all(
                 self.panels.as_mut_slice(),
                 self.screen,
                 self.preview_panel.is_some(),
+                &self.layout,
             )
             .expect("removing a panel should be easy");
             self.active_panel_idx = self
@@ -208,9 +290,14 @@ impl App {
                 let mut renderer = renderer.lock().unwrap();
                 renderer.take_current_images()
             });
+        let zoomed_panel = self.zoomed_panel;
         for (idx, panel) in self.panels.as_mut_slice().iter_mut().enumerate() {
+            if matches!(zoomed_panel, Some(id) if id != panel.id) {
+                continue;
+            }
             let active = idx == self.active_panel_idx;
-            let panel_skin = if active { &skin.focused } else { &skin.unfocused };
+            let is_preview = self.preview_panel == Some(panel.id);
+            let panel_skin = skin.panel_skin(active, is_preview);
             let disc = DisplayContext {
                 active,
                 screen: self.screen,
@@ -263,19 +350,37 @@ impl App {
     fn apply_command(
         &mut self,
         w: &mut W,
-        cmd: Command,
+        mut cmd: Command,
         panel_skin: &PanelSkin,
         app_state: &mut AppState,
         con: &AppContext,
     ) -> Result<(), ProgramError> {
         use CmdResult::*;
         let mut error: Option<String> = None;
+        let mut message: Option<String> = None;
+        // a command waiting for a confirmation is executed on enter,
+        // and cancelled by any other command
+        let mut confirmed = false;
+        if let Some(pending_cmd) = self.pending_confirmation.take() {
+            if matches!(cmd, Command::None) {
+                self.pending_confirmation = Some(pending_cmd);
+            } else if is_internal_command(&cmd, Internal::open_stay, con) {
+                cmd = pending_cmd;
+                confirmed = true;
+            } else if is_internal_command(&cmd, Internal::back, con) {
+                self.mut_panel().set_message("Command cancelled".to_string());
+                return Ok(());
+            }
+        }
         let is_input_invocation = cmd.is_verb_invocated_from_input();
+        let is_mutating = is_mutating_command(&cmd, con);
         let app_cmd_context = AppCmdContext {
             other_path: self.get_other_panel_path(),
             panel_skin,
             preview_panel: self.preview_panel,
             stage_panel: self.stage_panel,
+            dry_run_panel: self.dry_run_panel,
+            confirmed,
             screen: self.screen, // it can't change in this function
             con,
         };
@@ -330,6 +435,8 @@ impl App {
                             panel_skin,
                             preview_panel: self.preview_panel,
                             stage_panel: self.stage_panel,
+                            dry_run_panel: self.dry_run_panel,
+                            confirmed: false,
                             screen,
                             con,
                         };
@@ -339,11 +446,30 @@ impl App {
                     self.quitting = true;
                 }
             }
+            AskConfirmation(question) => {
+                if is_input_invocation {
+                    self.mut_panel().clear_input_invocation(con);
+                }
+                self.pending_confirmation = Some(cmd.clone());
+                message = Some(question);
+            }
+            ChangeKeyBinding { verb_index, keys } => {
+                if is_input_invocation {
+                    self.mut_panel().clear_input_invocation(con);
+                }
+                self.key_binding_changes.push((verb_index, keys));
+            }
             DisplayError(txt) => {
                 error = Some(txt);
             }
+            DisplayMessage(txt) => {
+                if is_input_invocation {
+                    self.mut_panel().clear_input_invocation(con);
+                }
+                message = Some(txt);
+            }
             ExecuteSequence { sequence } => {
-                self.tx_seqs.send(sequence).unwrap();
+                self.tx_msgs.send(AppMessage::Sequence(sequence)).unwrap();
             }
             HandleInApp(internal) => {
                 let new_active_panel_idx = match internal {
@@ -368,6 +494,104 @@ impl App {
                             Some(self.active_panel_idx + 1)
                         }
                     }
+                    Internal::register_paste => {
+                        match cmd.invocation_args() {
+                            Some(register) => match app_state.registers.get(register) {
+                                Some(content) => {
+                                    let content = content.clone();
+                                    if is_input_invocation {
+                                        self.mut_panel().clear_input_invocation(con);
+                                    }
+                                    let mut input = self.panel().get_input_content();
+                                    input.push_str(&content);
+                                    self.mut_panel().set_input_content(&input);
+                                    let cmd = Command::from_raw(input, false);
+                                    self.apply_command(w, cmd, panel_skin, app_state, con)?;
+                                }
+                                None => {
+                                    error = Some(format!("register {:?} is empty", register));
+                                }
+                            },
+                            None => {
+                                error = Some("a register is needed, eg `:register_paste a`".to_string());
+                            }
+                        }
+                        None
+                    }
+                    Internal::reload_config => {
+                        if is_input_invocation {
+                            self.mut_panel().clear_input_invocation(con);
+                        }
+                        self.must_reload_config = true;
+                        None
+                    }
+                    Internal::toggle_skin => {
+                        if is_input_invocation {
+                            self.mut_panel().clear_input_invocation(con);
+                        }
+                        self.must_toggle_skin = true;
+                        None
+                    }
+                    Internal::layout => {
+                        self.unzoom(con)?;
+                        match internal_arg(&cmd, con) {
+                            Some(preset) => {
+                                if is_input_invocation {
+                                    self.mut_panel().clear_input_invocation(con);
+                                }
+                                match self.layout.apply_preset(&preset, self.panels.len().get()) {
+                                    Ok(()) => self.resize_panels(con)?,
+                                    Err(e) => error = Some(e),
+                                }
+                            }
+                            None => {
+                                error = Some("a layout is needed, eg `:layout 70/30`".to_string());
+                            }
+                        }
+                        None
+                    }
+                    Internal::panel_grow | Internal::panel_shrink => {
+                        if is_input_invocation {
+                            self.mut_panel().clear_input_invocation(con);
+                        }
+                        self.unzoom(con)?;
+                        let widths = self.panels.iter().map(|p| p.areas.state.width).collect();
+                        let step = (self.screen.width / 20).max(2) as i32;
+                        let delta = if internal == Internal::panel_grow { step } else { -step };
+                        match self.layout.resize_panel(widths, self.active_panel_idx, delta) {
+                            Ok(()) => self.resize_panels(con)?,
+                            Err(e) => error = Some(e.to_string()),
+                        }
+                        None
+                    }
+                    Internal::panel_zoom => {
+                        if is_input_invocation {
+                            self.mut_panel().clear_input_invocation(con);
+                        }
+                        if self.zoomed_panel.is_some() {
+                            self.unzoom(con)?;
+                        } else if self.panels.len().get() > 1 {
+                            self.zoomed_panel = Some(self.panel().id);
+                            self.resize_panels(con)?;
+                        } else {
+                            error = Some("there's no other panel to hide".to_string());
+                        }
+                        None
+                    }
+                    Internal::repeat => {
+                        match self.last_mutating_command.clone() {
+                            Some(last_cmd) => {
+                                if is_input_invocation {
+                                    self.mut_panel().clear_input_invocation(con);
+                                }
+                                self.apply_command(w, last_cmd, panel_skin, app_state, con)?;
+                            }
+                            None => {
+                                error = Some("no verb to repeat".to_string());
+                            }
+                        }
+                        None
+                    }
                     _ => {
                         debug!("unhandled propagated internal. cmd={:?}", &cmd);
                         None
@@ -377,12 +601,15 @@ impl App {
                     if is_input_invocation {
                         self.mut_panel().clear_input();
                     }
+                    self.unzoom(con)?;
                     self.active_panel_idx = idx;
                     let app_cmd_context = AppCmdContext {
                         other_path: self.get_other_panel_path(),
                         panel_skin,
                         preview_panel: self.preview_panel,
                         stage_panel: self.stage_panel,
+                        dry_run_panel: self.dry_run_panel,
+                        confirmed: false,
                         screen: self.screen,
                         con,
                     };
@@ -423,6 +650,16 @@ impl App {
                     self.quitting = true;
                 }
             }
+            PopStateAndApply(new_cmd) => {
+                if is_input_invocation {
+                    self.mut_panel().clear_input();
+                }
+                if self.remove_state() {
+                    self.apply_command(w, *new_cmd, panel_skin, app_state, con)?;
+                } else if ESCAPE_TO_QUIT {
+                    self.quitting = true;
+                }
+            }
             PopStateAndReapply => {
                 if is_input_invocation {
                     self.mut_panel().clear_input();
@@ -433,6 +670,8 @@ impl App {
                         panel_skin,
                         preview_panel: self.preview_panel,
                         stage_panel: self.stage_panel,
+                        dry_run_panel: self.dry_run_panel,
+                        confirmed: false,
                         screen: self.screen,
                         con,
                     };
@@ -455,10 +694,19 @@ impl App {
                 for i in 0..self.panels.len().get() {
                     self.panels[i].mut_state().refresh(self.screen, con);
                 }
+                // the selection may have changed with the refresh
+                self.mut_panel().refresh_input_status(app_state, &app_cmd_context);
             }
         }
+        if let (None, Command::VerbInvocate(invocation)) = (&error, &cmd) {
+            app_state.remember_invocation(invocation.clone());
+        }
         if let Some(text) = error {
             self.mut_panel().set_error(text);
+        } else if let Some(text) = message {
+            self.mut_panel().set_message(text);
+        } else if is_mutating {
+            self.last_mutating_command = Some(cmd);
         }
         self.update_preview(con);
 
@@ -476,7 +724,7 @@ impl App {
         if let Some(preview_idx) = preview_idx {
             if let Some(path) = self.state().selected_path() {
                 let old_path = self.panels[preview_idx].state().selected_path();
-                if Some(path) != old_path && path.is_file() {
+                if Some(path) != old_path && (path.is_file() || path.is_dir()) {
                     let path = path.to_path_buf();
                     self.panels[preview_idx].mut_state().set_selected_path(path, con);
                 }
@@ -486,6 +734,9 @@ impl App {
 
     /// get the index of the panel at x
     fn clicked_panel_index(&self, x: u16, _y: u16) -> usize {
+        if self.zoomed_panel.is_some() {
+            return self.active_panel_idx;
+        }
         let len = self.panels.len().get();
         (len * x as usize) / (self.screen.width as usize + 1)
     }
@@ -508,6 +759,9 @@ impl App {
                 return Err("There can be only one stage panel".to_owned());
                 // todo replace instead ?
             }
+            PanelStateType::DryRun if self.dry_run_panel.is_some() => {
+                return Err("There can be only one dry-run panel".to_owned());
+            }
             _ => {}
         }
         if is_input_invocation {
@@ -526,8 +780,11 @@ impl App {
             insertion_idx,
             self.screen,
             with_preview,
+            &self.layout,
         ) {
             Ok(areas) => {
+                // the areas of all panels were computed again
+                self.zoomed_panel = None;
                 let panel_id = self.created_panels_count.into();
                 match state.get_type() {
                     PanelStateType::Preview => {
@@ -536,6 +793,9 @@ impl App {
                     PanelStateType::Stage => {
                         self.stage_panel = Some(panel_id);
                     }
+                    PanelStateType::DryRun => {
+                        self.dry_run_panel = Some(panel_id);
+                    }
                     _ => {
                         self.active_panel_idx = insertion_idx;
                     }
@@ -567,6 +827,8 @@ impl App {
                     panel_skin: &skin.focused,
                     preview_panel: self.preview_panel,
                     stage_panel: self.stage_panel,
+                    dry_run_panel: self.dry_run_panel,
+                    confirmed: false,
                     screen: self.screen,
                     con,
                 };
@@ -609,51 +871,139 @@ impl App {
         self.panels.iter().any(|p| p.has_pending_task())
     }
 
+    /// apply the changes of the context (and skin) required by the
+    /// last command, ie rebinding keys, reloading the configuration,
+    /// or switching skins
+    fn apply_context_changes(
+        &mut self,
+        con: &mut AppContext,
+        skin: &mut AppSkin,
+    ) {
+        for (verb_index, keys) in self.key_binding_changes.drain(..) {
+            con.rebind(verb_index, keys);
+        }
+        if self.must_reload_config {
+            self.must_reload_config = false;
+            if let Err(e) = self.reload_config(con, skin) {
+                warn!("failed to reload configuration: {}", e);
+                self.mut_panel().set_error(e.to_string());
+            }
+        }
+        if self.must_toggle_skin {
+            self.must_toggle_skin = false;
+            if skin.toggle() {
+                for panel in &mut self.panels {
+                    panel.mut_state().refresh(self.screen, con);
+                }
+            } else {
+                self.mut_panel().set_error(
+                    "there's no light_skin or dark_skin in the configuration".to_string()
+                );
+            }
+        }
+    }
+
+    /// read again the configuration files, then rebuild the context,
+    /// the skin, and the states of the panels with the new tree options.
+    /// The old ones are kept if the configuration is invalid.
+    fn reload_config(
+        &mut self,
+        con: &mut AppContext,
+        skin: &mut AppSkin,
+    ) -> Result<(), ProgramError> {
+        let mut config = Conf::from_files(&con.config_paths)?;
+        let mut verb_store = VerbStore::default();
+        verb_store.init(&mut config)?;
+        let mut launch_args = con.launch_args.clone();
+        launch_args.tree_options = launch_args.make_tree_options(&config)?;
+        *con = AppContext::from(launch_args, verb_store, &config)?;
+        *skin = AppSkin::new(&config, con.launch_args.no_style, skin.background);
+        // the verbs may have changed, so the last command can't be repeated
+        self.last_mutating_command = None;
+        let initial = &con.launch_args.tree_options;
+        for panel in &mut self.panels {
+            let cmd_result = panel.mut_state().with_new_options(
+                self.screen,
+                &|o| o.apply_initial(initial),
+                false,
+                con,
+            );
+            match cmd_result {
+                CmdResult::NewState(state) => panel.replace_state(state),
+                _ => {
+                    panel.mut_state().refresh(self.screen, con);
+                }
+            }
+        }
+        info!("configuration reloaded");
+        Ok(())
+    }
+
     /// This is the main loop of the application
     pub fn run(
         mut self,
         w: &mut W,
-        con: &AppContext,
+        mut con: AppContext,
         conf: &Conf,
     ) -> Result<Option<Launchable>, ProgramError> {
         // we listen for events in a separate thread so that we can go on listening
         // when a long search is running, and interrupt it if needed
+        // the terminal must be queried for its background before we listen to events
+        let background = if conf.light_skin.is_some()
+            || conf.dark_skin.is_some()
+            || conf.unfocused_dimming.is_some()
+        {
+            skin::detect_background()
+        } else {
+            None
+        };
+        info!("background: {:?}", background);
         let event_source = EventSource::new()?;
         let rx_events = event_source.receiver();
         let mut dam = Dam::from(rx_events);
-        let skin = AppSkin::new(conf, con.launch_args.no_style);
+        let mut skin = AppSkin::new(conf, con.launch_args.no_style, background);
         let mut app_state = AppState::default();
 
         self.screen.clear_bottom_right_char(w, &skin.focused)?;
 
         if let Some(raw_sequence) = &con.launch_args.commands {
-            self.tx_seqs
-                .send(Sequence::new_local(raw_sequence.to_string()))
+            self.tx_msgs
+                .send(AppMessage::Sequence(Sequence::new_local(raw_sequence.to_string())))
                 .unwrap();
         }
 
+        // the configuration is reloaded when its files are modified
+        let tx_msgs = self.tx_msgs.clone();
+        conf::watch(con.config_paths.clone(), move || {
+            tx_msgs.send(AppMessage::ConfChange).is_ok()
+        });
+
         #[cfg(feature="client-server")]
         let _server = con.launch_args.listen.as_ref()
             .map(|server_name| crate::net::Server::new(
                 &server_name,
-                self.tx_seqs.clone(),
+                self.tx_msgs.clone(),
                 Arc::clone(&self.root),
             ))
             .transpose()?;
 
         loop {
             if !self.quitting {
-                self.display_panels(w, &skin, &app_state, con)?;
+                self.display_panels(w, &skin, &app_state, &con)?;
                 time!(
                     "pending_tasks",
-                    self.do_pending_tasks(w, &skin, &mut dam, &mut app_state, con)?,
+                    self.do_pending_tasks(w, &skin, &mut dam, &mut app_state, &con)?,
                 );
             }
-            match dam.next(&self.rx_seqs) {
+            match dam.next(&self.rx_msgs) {
                 Either::First(Some(event)) => {
                     info!("event: {:?}", &event);
+                    if let Some((x, y)) = event.drag_start() {
+                        // a drag is handled by the panel where it started
+                        self.active_panel_idx = self.clicked_panel_index(x, y);
+                    }
                     match event {
-                        Event::Click(x, y, KeyModifiers::NONE)
+                        Event::Click(x, y, _)
                             if self.clicked_panel_index(x, y) != self.active_panel_idx =>
                         {
                             // panel activation click
@@ -662,21 +1012,15 @@ impl App {
                             self.active_panel_idx = self.clicked_panel_index(x, y);
                         }
                         Event::Resize(w, h) => {
-                            self.screen.set_terminal_size(w, h, con);
-                            Areas::resize_all(
-                                self.panels.as_mut_slice(),
-                                self.screen,
-                                self.preview_panel.is_some(),
-                            )?;
-                            for panel in &mut self.panels {
-                                panel.mut_state().refresh(self.screen, con);
-                            }
+                            self.screen.set_terminal_size(w, h, &con);
+                            self.resize_panels(&con)?;
                         }
                         _ => {
                             // event handled by the panel
-                            let cmd = self.mut_panel().add_event(w, event, &app_state, con)?;
+                            let cmd = self.mut_panel().add_event(w, event, &app_state, &con)?;
                             debug!("command after add_event: {:?}", &cmd);
-                            self.apply_command(w, cmd, &skin.focused, &mut app_state, con)?;
+                            self.apply_command(w, cmd, &skin.focused, &mut app_state, &con)?;
+                            self.apply_context_changes(&mut con, &mut skin);
                         }
                     }
                     event_source.unblock(self.quitting);
@@ -686,19 +1030,24 @@ impl App {
                     // when the input thread is properly closed
                     break;
                 }
-                Either::Second(Some(raw_sequence)) => {
+                Either::Second(Some(AppMessage::ConfChange)) => {
+                    self.must_reload_config = true;
+                    self.apply_context_changes(&mut con, &mut skin);
+                }
+                Either::Second(Some(AppMessage::Sequence(raw_sequence))) => {
                     debug!("got command sequence: {:?}", &raw_sequence);
-                    for (input, arg_cmd) in raw_sequence.parse(con)? {
+                    for (input, arg_cmd) in raw_sequence.parse(&con)? {
                         self.mut_panel().set_input_content(&input);
-                        self.apply_command(w, arg_cmd, &skin.focused, &mut app_state, con)?;
+                        self.apply_command(w, arg_cmd, &skin.focused, &mut app_state, &con)?;
+                        self.apply_context_changes(&mut con, &mut skin);
                         if self.quitting {
                             // is that a 100% safe way of quitting ?
                             return Ok(self.launch_at_end.take());
                         } else {
-                            self.display_panels(w, &skin, &app_state, con)?;
+                            self.display_panels(w, &skin, &app_state, &con)?;
                             time!(
                                 "sequence pending tasks",
-                                self.do_pending_tasks(w, &skin, &mut dam, &mut app_state, con)?,
+                                self.do_pending_tasks(w, &skin, &mut dam, &mut app_state, &con)?,
                             );
                         }
                     }
@@ -713,6 +1062,49 @@ impl App {
     }
 }
 
+/// tell whether the command executes a verb which may modify
+/// files, and which can thus be repeated with `:repeat`
+fn is_mutating_command(cmd: &Command, con: &AppContext) -> bool {
+    let verb = match cmd {
+        Command::VerbTrigger { index, .. } => con.verb_store.verbs.get(*index),
+        Command::VerbInvocate(invocation) => match con.verb_store.search_prefix(&invocation.name) {
+            PrefixSearchResult::Match(_, verb) => Some(verb),
+            _ => None,
+        },
+        _ => None,
+    };
+    matches!(verb, Some(verb) if verb.modifies_files())
+}
+
+/// tell whether the command executes the given internal, either
+/// directly or with a verb (eg when the user hits enter)
+fn is_internal_command(cmd: &Command, internal: Internal, con: &AppContext) -> bool {
+    match cmd {
+        Command::Internal { internal: cmd_internal, .. } => *cmd_internal == internal,
+        Command::VerbTrigger { index, .. } => matches!(
+            con.verb_store.verbs.get(*index),
+            Some(verb) if verb.is_internal(internal),
+        ),
+        _ => false,
+    }
+}
+
+/// return the argument of the internal executed by the command,
+/// either typed in the input or given in the verb definition
+/// (eg `:layout 70/30` bound to a key)
+fn internal_arg(cmd: &Command, con: &AppContext) -> Option<String> {
+    if let Some(args) = cmd.invocation_args() {
+        return Some(args.to_string());
+    }
+    match cmd {
+        Command::VerbTrigger { index, .. } => match &con.verb_store.verbs.get(*index)?.execution {
+            VerbExecution::Internal(internal_exec) => internal_exec.arg.clone(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// clear the file sizes and git stats cache.
 /// This should be done on Refresh actions and after any external
 /// command.
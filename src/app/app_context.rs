@@ -1,15 +1,22 @@
 use {
     super::*,
     crate::{
+        cleanable,
         cli::AppLaunchArgs,
-        conf::Conf,
+        command::WheelBehavior,
+        conf::{self, Conf},
         errors::ConfError,
         icon::*,
         pattern::SearchModeMap,
-        path::SpecialPath,
+        path::{ScoreAdjustment, SpecialPath},
+        preview::PreviewMode,
         skin::ExtColorMap,
-        verb::VerbStore,
+        tree::parse_flags,
+        verb::{Opener, VerbStore},
     },
+    ahash::AHashMap,
+    clap::ArgMatches,
+    crossterm::event::KeyEvent,
     std::{
         convert::{TryFrom, TryInto},
         path::PathBuf,
@@ -34,9 +41,26 @@ pub struct AppContext {
     /// the paths for which there's a special behavior to follow (comes from conf)
     pub special_paths: Vec<SpecialPath>,
 
+    /// changes of the scores of the matches, by glob on their subpath
+    pub score_adjustments: Vec<ScoreAdjustment>,
+
+    /// the programs to use to open files, before
+    /// falling back to the system's default
+    pub openers: Vec<Opener>,
+
     /// the map between search prefixes and the search mode to apply
     pub search_modes: SearchModeMap,
 
+    /// the named sets of tree options which can be applied
+    /// with `:profile`, already parsed
+    pub profiles: AHashMap<String, ArgMatches<'static>>,
+
+    /// the default preview modes, per lowercase file extension
+    pub preview_modes: AHashMap<String, PreviewMode>,
+
+    /// names of the directories listed by `:cleanable`
+    pub cleanable_dirs: Vec<String>,
+
     /// whether to show a triangle left to selected lines
     pub show_selection_mark: bool,
 
@@ -62,9 +86,24 @@ pub struct AppContext {
 
     pub mouse_capture_disabled: bool,
 
+    /// what a turn of the mouse wheel does
+    pub mouse_wheel: WheelBehavior,
+
     /// max number of panels (including preview) that can be
     /// open. Guaranteed to be at least 2.
     pub max_panels_count: usize,
+
+    /// the directory holding the templates used by `:new_file`,
+    /// one file per extension (eg `rs` for new `.rs` files)
+    pub templates_dir: Option<PathBuf>,
+
+    /// max number of children read in a directory, the other
+    /// ones being only counted (unless the user asks for them)
+    pub max_loaded_children: usize,
+
+    /// whether searches explore first the directories where previous
+    /// searches found matches, then the recently modified ones
+    pub search_priority_heuristic: bool,
 }
 
 impl AppContext {
@@ -86,22 +125,47 @@ impl AppContext {
             .iter()
             .map(|(k, v)| SpecialPath::new(k.clone(), *v))
             .collect();
+        let openers = config.openers
+            .iter()
+            .map(Opener::try_from)
+            .collect::<Result<Vec<Opener>, ConfError>>()?;
         let search_modes = config
             .search_modes
             .as_ref()
             .map(|map| map.try_into())
             .transpose()?
             .unwrap_or_default();
+        let profiles = config.profiles
+            .iter()
+            .map(|(name, flags)| parse_flags(flags).map(|m| (name.clone(), m)))
+            .collect::<Result<AHashMap<String, ArgMatches<'static>>, ConfError>>()?;
+        let preview_modes = config.preview_modes
+            .iter()
+            .map(|(ext, mode)| mode.parse().map(|mode| (ext.to_lowercase(), mode)))
+            .collect::<Result<AHashMap<String, PreviewMode>, ConfError>>()?;
         let ext_colors = ExtColorMap::try_from(&config.ext_colors)?;
         let max_panels_count = config.max_panels_count
             .unwrap_or(2)
             .clamp(2, 100);
+        // a relative templates dir is relative to the configuration directory
+        let templates_dir = config.templates_dir
+            .as_ref()
+            .map(|dir| conf::dir().join(dir));
         Ok(Self {
             config_paths,
             launch_args,
             verb_store,
             special_paths,
+            score_adjustments: ScoreAdjustment::list(&config.score_adjustments),
+            openers,
             search_modes,
+            profiles,
+            preview_modes,
+            cleanable_dirs: config.cleanable_dirs
+                .clone()
+                .unwrap_or_else(|| {
+                    cleanable::DEFAULT_CLEANABLE_DIRS.iter().map(|s| s.to_string()).collect()
+                }),
             show_selection_mark: config.show_selection_mark.unwrap_or(false),
             ext_colors,
             syntax_theme: config.syntax_theme.clone(),
@@ -110,11 +174,23 @@ impl AppContext {
             icons,
             modal: config.modal.unwrap_or(false),
             mouse_capture_disabled: config.disable_mouse_capture.unwrap_or(false),
+            mouse_wheel: config.mouse_wheel.unwrap_or(WheelBehavior::MoveSelection),
             max_panels_count,
+            templates_dir,
+            max_loaded_children: config.max_loaded_children.unwrap_or(10_000).max(1),
+            search_priority_heuristic: config.search_priority_heuristic.unwrap_or(true),
         })
     }
 }
 
+impl AppContext {
+    /// make the key (or chord) trigger the verb for the rest of the session
+    pub fn rebind(&mut self, verb_index: usize, keys: Vec<KeyEvent>) {
+        self.verb_store.rebind(verb_index, keys);
+        self.standard_status = StandardStatus::new(&self.verb_store);
+    }
+}
+
 /// try to determine whether the terminal supports true
 /// colors. This doesn't work well, hence the use of an
 /// optional config setting.
@@ -0,0 +1,14 @@
+use {
+    crate::command::Sequence,
+};
+
+/// what the application receives on its channel, besides
+/// the events of the terminal
+#[derive(Debug)]
+pub enum AppMessage {
+    /// a sequence of commands to execute, eg from `--cmd`,
+    /// from a verb, or from another broot
+    Sequence(Sequence),
+    /// a change of the configuration files
+    ConfChange,
+}
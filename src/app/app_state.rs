@@ -1,15 +1,33 @@
 use {
     crate::{
         stage::Stage,
+        verb::VerbInvocation,
     },
+    ahash::AHashMap,
 };
 
+/// how many typed commands are remembered for the palette
+const MAX_RECENT_INVOCATIONS: usize = 20;
+
 
 /// global mutable state
 #[derive(Debug, Default)]
 pub struct AppState {
     pub stage: Stage,
+    /// the paths yanked with `:register_yank`, by register name
+    pub registers: AHashMap<String, String>,
+    /// the verb invocations last typed in the input, most recent first
+    pub recent_invocations: Vec<VerbInvocation>,
+    /// what the verbs modifying files would have done, in dry-run mode
+    pub dry_run_actions: Vec<String>,
 }
 
 impl AppState {
+    /// remember a verb invocation typed in the input, to
+    /// propose it again in the palette
+    pub fn remember_invocation(&mut self, invocation: VerbInvocation) {
+        self.recent_invocations.retain(|inv| *inv != invocation);
+        self.recent_invocations.insert(0, invocation);
+        self.recent_invocations.truncate(MAX_RECENT_INVOCATIONS);
+    }
 }
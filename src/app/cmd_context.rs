@@ -22,6 +22,8 @@ pub struct AppCmdContext<'c> {
     pub panel_skin: &'c PanelSkin,
     pub preview_panel: Option<PanelId>, // id of the app's preview panel
     pub stage_panel: Option<PanelId>, // id of the app's preview panel
+    pub dry_run_panel: Option<PanelId>, // id of the app's dry-run panel
+    pub confirmed: bool, // whether the user confirmed the execution of the command
     pub screen: Screen,
     pub con: &'c AppContext,
 }
@@ -2,11 +2,12 @@ use {
     super::*,
     crate::{
         browser::BrowserState,
-        command::Sequence,
+        command::{Command, Sequence},
         errors::TreeBuildError,
         launchable::Launchable,
         verb::Internal,
     },
+    crossterm::event::KeyEvent,
     std::fmt,
 };
 
@@ -32,11 +33,17 @@ pub enum CmdResult {
     ApplyOnPanel {
         id: PanelId,
     },
+    AskConfirmation(String), // the command will be executed if the user hits enter
+    ChangeKeyBinding {
+        verb_index: usize,
+        keys: Vec<KeyEvent>, // a key or a chord
+    },
     ClosePanel {
         validate_purpose: bool,
         panel_ref: PanelReference,
     },
     DisplayError(String),
+    DisplayMessage(String), // an information for the status line, not an error
     ExecuteSequence {
         sequence: Sequence,
     },
@@ -49,6 +56,7 @@ pub enum CmdResult {
         direction: HDir,
     },
     NewState(Box<dyn PanelState>),
+    PopStateAndApply(Box<Command>), // the state asks another command be executed on a previous state
     PopStateAndReapply, // the state asks the command be executed on a previous state
     PopState,
     Quit,
@@ -99,18 +107,22 @@ impl fmt::Debug for CmdResult {
             "{}",
             match self {
                 CmdResult::ApplyOnPanel { .. } => "ApplyOnPanel",
+                CmdResult::AskConfirmation(_) => "AskConfirmation",
+                CmdResult::ChangeKeyBinding { .. } => "ChangeKeyBinding",
                 CmdResult::ClosePanel {
                     validate_purpose: false, ..
                 } => "CancelPanel",
                 CmdResult::ClosePanel {
                     validate_purpose: true, ..
                 } => "OkPanel",
+                CmdResult::DisplayMessage(_) => "DisplayMessage",
                 CmdResult::DisplayError(_) => "DisplayError",
                 CmdResult::ExecuteSequence{ .. } => "ExecuteSequence",
                 CmdResult::Keep => "Keep",
                 CmdResult::Launch(_) => "Launch",
                 CmdResult::NewState { .. } => "NewState",
                 CmdResult::NewPanel { .. } => "NewPanel",
+                CmdResult::PopStateAndApply(_) => "PopStateAndApply",
                 CmdResult::PopStateAndReapply => "PopStateAndReapply",
                 CmdResult::PopState => "PopState",
                 CmdResult::HandleInApp(_) => "HandleInApp",
@@ -0,0 +1,72 @@
+use {
+    super::*,
+    crate::{
+        conf,
+        keys,
+    },
+};
+
+/// handle `:rebind`, whose argument is either a verb name followed by
+/// a key (or a chord, eg `g t`), or only a key when a default verb is
+/// given (it's the case in help when the table is filtered down to one verb).
+///
+/// The binding is written in the first configuration file then a
+/// `ChangeKeyBinding` is returned for the app to apply it immediately.
+pub fn rebind(
+    arg: &str,
+    default_verb: Option<usize>,
+    con: &AppContext,
+) -> CmdResult {
+    let verbs = &con.verb_store.verbs;
+    let first_token = arg.split_whitespace().next().unwrap_or("");
+    let named_verb = verbs.iter()
+        .position(|verb| verb.names.iter().any(|name| name == first_token));
+    let rest = arg.trim_start()[first_token.len()..].trim();
+    let (verb_index, raw_keys) = match (named_verb, default_verb) {
+        (Some(idx), _) if !rest.is_empty() => (idx, rest),
+        (_, Some(idx)) => (idx, arg),
+        _ => {
+            return CmdResult::error(
+                "a verb and a key are needed, eg `:rebind back ctrl-b`"
+            );
+        }
+    };
+    let keys = match keys::parse_key_sequence(raw_keys) {
+        Ok(keys) => keys,
+        Err(e) => {
+            return CmdResult::error(e.to_string());
+        }
+    };
+    if let Some(&key) = keys.iter().find(|&&key| keys::is_reserved(key)) {
+        return CmdResult::error(format!("reserved key: {}", keys::key_event_desc(key)));
+    }
+    if let Some(conflict) = con.verb_store.chord_conflict(&keys) {
+        return CmdResult::error(conflict);
+    }
+    let verb = &verbs[verb_index];
+    let name = match verb.names.first() {
+        Some(name) => name,
+        None => {
+            return CmdResult::error("this verb has no name and can't be rebound");
+        }
+    };
+    // an internal is bound directly, other verbs are called by name
+    let execution_field = match verb.get_internal() {
+        Some(internal) if internal.name() == name => "internal",
+        _ => "cmd",
+    };
+    let conf_path = match con.config_paths.first() {
+        Some(path) => path,
+        None => {
+            return CmdResult::error("no configuration file to write to");
+        }
+    };
+    // we write the keys as typed, as they're known to be parsable
+    let key_desc = raw_keys.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let execution = format!(":{}", name);
+    if let Err(e) = conf::add_key_binding(conf_path, &key_desc, execution_field, &execution) {
+        return CmdResult::error(format!("failed to write {:?}: {}", conf_path, e));
+    }
+    info!("{} bound to {} in {:?}", key_desc, execution, conf_path);
+    CmdResult::ChangeKeyBinding { verb_index, keys }
+}
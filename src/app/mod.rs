@@ -1,9 +1,11 @@
 mod app;
 mod app_context;
+mod app_message;
 mod app_state;
 mod cmd_context;
 mod cmd_result;
 mod display_context;
+mod key_binding;
 mod mode;
 mod panel;
 mod panel_id;
@@ -17,10 +19,12 @@ mod status;
 pub use {
     app::App,
     app_context::AppContext,
+    app_message::AppMessage,
     app_state::*,
     cmd_context::*,
     cmd_result::*,
     display_context::*,
+    key_binding::rebind,
     mode::*,
     panel::Panel,
     panel_id::PanelId,
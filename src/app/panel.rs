@@ -11,13 +11,13 @@ use {
             flags_display,
         },
         errors::ProgramError,
+        event::Event,
         keys,
         skin::PanelSkin,
         task_sync::Dam,
         verb::*,
     },
     minimad::{Alignment, Composite},
-    termimad::Event,
 };
 
 /// A colon on screen containing a stack of states, the top
@@ -56,6 +56,10 @@ impl Panel {
         self.status = Status::from_error(text);
     }
 
+    pub fn set_message(&mut self, text: String) {
+        self.status = Status::from_message(text);
+    }
+
     /// apply a command on the current state, with no
     /// effect on screen
     #[allow(clippy::too_many_arguments)] // a refactory could still be useful
@@ -134,6 +138,10 @@ impl Panel {
         self.input.set_content(&new_state.get_starting_input());
         self.states.push(new_state);
     }
+    /// replace the current state, without changing the input
+    pub fn replace_state(&mut self, new_state: Box<dyn PanelState>) {
+        *self.states.last_mut().unwrap() = new_state;
+    }
     pub fn mut_state(&mut self) -> &mut dyn PanelState {
         self.states.last_mut().unwrap().as_mut()
     }
@@ -218,10 +226,16 @@ impl Panel {
         screen: Screen,
     ) -> Result<(), ProgramError> {
         let task = self.state().get_pending_task();
+        // while a chord is being typed, its keys replace the status
+        let chord_status = self.input.pending_keys_desc()
+            .map(|desc| Status::from_message(format!(
+                "Pending keys: *{}* (hit *esc* to cancel)",
+                desc,
+            )));
         status_line::write(
             w,
             task,
-            &self.status,
+            chord_status.as_ref().unwrap_or(&self.status),
             &self.areas.status,
             panel_skin,
             screen,
@@ -1,18 +1,24 @@
 use {
     super::*,
     crate::{
+        cleanable::CleanableState,
         command::*,
         display::{Screen, W},
+        dry_run::DryRunState,
         errors::ProgramError,
         flag::Flag,
+        git,
         help::HelpState,
+        palette::PaletteState,
         pattern::*,
         preview::{PreviewMode, PreviewState},
         print,
         stage::StageState,
+        tag,
         task_sync::Dam,
         tree::*,
         verb::*,
+        worktrees::WorktreesState,
     },
     std::{
         path::{Path, PathBuf},
@@ -44,10 +50,43 @@ pub trait PanelState {
 
     fn on_double_click(
         &mut self,
+        _w: &mut W,
         _x: u16,
         _y: u16,
-        _screen: Screen,
-        _con: &AppContext,
+        _app_state: &mut AppState,
+        _cc: &CmdContext,
+    ) -> Result<CmdResult, ProgramError> {
+        Ok(CmdResult::Keep)
+    }
+
+    /// handle a click ending a range of lines starting at the
+    /// selection. By default, it's just a click
+    fn on_range_click(
+        &mut self,
+        x: u16,
+        y: u16,
+        _app_state: &mut AppState,
+        cc: &CmdContext,
+    ) -> Result<CmdResult, ProgramError> {
+        self.on_click(x, y, cc.app.screen, cc.app.con)
+    }
+
+    /// handle a move of the mouse with its left button pressed
+    fn on_drag(
+        &mut self,
+        _start: (u16, u16),
+        _pos: (u16, u16),
+    ) -> Result<CmdResult, ProgramError> {
+        Ok(CmdResult::Keep)
+    }
+
+    /// handle the release of the left button ending a drag
+    fn on_drag_end(
+        &mut self,
+        _start: (u16, u16),
+        _pos: (u16, u16),
+        _app_state: &mut AppState,
+        _cc: &CmdContext,
     ) -> Result<CmdResult, ProgramError> {
         Ok(CmdResult::Keep)
     }
@@ -94,7 +133,7 @@ pub trait PanelState {
     /// behavior to execute
     fn on_internal_generic(
         &mut self,
-        _w: &mut W,
+        w: &mut W,
         internal_exec: &InternalExecution,
         input_invocation: Option<&VerbInvocation>,
         _trigger_type: TriggerType,
@@ -108,21 +147,25 @@ pub trait PanelState {
             .unwrap_or(internal_exec.bang);
         Ok(match internal_exec.internal {
             Internal::back => CmdResult::PopState,
-            Internal::copy_line | Internal::copy_path => {
+            Internal::copy_line
+            | Internal::copy_path
+            | Internal::copy_relative_path
+            | Internal::copy_name
+            | Internal::copy_file_content => {
                 #[cfg(not(feature = "clipboard"))]
                 {
                     CmdResult::error("Clipboard feature not enabled at compilation")
                 }
                 #[cfg(feature = "clipboard")]
                 {
-                    if let Some(path) = self.selected_path() {
-                        let path = path.to_string_lossy().to_string();
-                        match terminal_clipboard::set_string(path) {
+                    let paths = self.sel_paths(app_state);
+                    let root = self.tree_root().unwrap_or(&con.launch_args.root);
+                    match clipboard_content(internal_exec.internal, &paths, root) {
+                        Ok(content) => match terminal_clipboard::set_string(content) {
                             Ok(()) => CmdResult::Keep,
-                            Err(_) => CmdResult::error("Clipboard error while copying path"),
-                        }
-                    } else {
-                        CmdResult::error("Nothing to copy")
+                            Err(_) => CmdResult::error("Clipboard error while copying"),
+                        },
+                        Err(e) => CmdResult::error(e),
                     }
                 }
             }
@@ -159,24 +202,66 @@ pub trait PanelState {
                     Err(e) => CmdResult::DisplayError(format!("{}", e)),
                 }
             }
-            Internal::help => {
+            Internal::clean => CmdResult::error("`:clean` applies to the list of `:cleanable`"),
+            Internal::cleanable => match self.selected_path() {
+                Some(path) if path.is_dir() => {
+                    let state = CleanableState::new(path, self.tree_options(), con);
+                    CmdResult::NewState(Box::new(state))
+                }
+                _ => CmdResult::error("no selected directory"),
+            },
+            Internal::palette => CmdResult::NewState(Box::new(PaletteState::new(
+                self.selection(),
+                self.tree_options(),
+                app_state,
+                con,
+            ))),
+            Internal::worktrees => {
+                let state = match self.selected_path() {
+                    Some(path) => WorktreesState::new(path, self.tree_options(), con),
+                    None => Err(ProgramError::Git { details: "no selection".to_string() }),
+                };
+                match state {
+                    Ok(state) => {
+                        let bang = input_invocation
+                            .map(|inv| inv.bang)
+                            .unwrap_or(internal_exec.bang);
+                        if bang && cc.app.preview_panel.is_none() {
+                            CmdResult::NewPanel {
+                                state: Box::new(state),
+                                purpose: PanelPurpose::None,
+                                direction: HDir::Right,
+                            }
+                        } else {
+                            CmdResult::NewState(Box::new(state))
+                        }
+                    }
+                    Err(e) => CmdResult::DisplayError(format!("{}", e)),
+                }
+            }
+            Internal::help | Internal::keys => {
                 let bang = input_invocation
                     .map(|inv| inv.bang)
                     .unwrap_or(internal_exec.bang);
+                let mut help_state = HelpState::new(self.tree_options(), screen, con);
+                if internal_exec.internal == Internal::keys {
+                    help_state = help_state.scrolled_to_verbs();
+                }
                 if bang && cc.app.preview_panel.is_none() {
                     CmdResult::NewPanel {
-                        state: Box::new(HelpState::new(self.tree_options(), screen, con)),
+                        state: Box::new(help_state),
                         purpose: PanelPurpose::None,
                         direction: HDir::Right,
                     }
                 } else {
-                    CmdResult::NewState(Box::new(
-                            HelpState::new(self.tree_options(), screen, con)
-                    ))
+                    CmdResult::NewState(Box::new(help_state))
                 }
             }
             Internal::mode_input => self.on_mode_verb(Mode::Input, con),
             Internal::mode_command => self.on_mode_verb(Mode::Command, con),
+            Internal::open_leave if con.launch_args.pick.is_some() => {
+                self.pick_paths(app_state, con)?
+            }
             Internal::open_leave => {
                 if let Some(selection) = self.selection() {
                     selection.to_opener(con)?
@@ -188,6 +273,17 @@ pub trait PanelState {
             Internal::preview_image => self.open_preview(Some(PreviewMode::Image), false, cc),
             Internal::preview_text => self.open_preview(Some(PreviewMode::Text), false, cc),
             Internal::preview_binary => self.open_preview(Some(PreviewMode::Hex), false, cc),
+            Internal::preview_mode => match get_str_arg(input_invocation, internal_exec) {
+                Some(arg) => match arg.parse::<PreviewMode>() {
+                    Ok(mode) => self.open_preview(Some(mode), false, cc),
+                    Err(e) => CmdResult::error(e.to_string()),
+                },
+                None => match cc.app.preview_panel {
+                    // the preview goes back to the default mode of the file
+                    Some(id) => CmdResult::ApplyOnPanel { id },
+                    None => self.open_preview(None, false, cc),
+                },
+            },
             Internal::toggle_preview => self.open_preview(None, true, cc),
             Internal::sort_by_count => self.with_new_options(
                 screen,
@@ -203,6 +299,26 @@ pub trait PanelState {
                 bang,
                 con,
             ),
+            Internal::date_kind => {
+                let kind = match get_str_arg(input_invocation, internal_exec) {
+                    Some(arg) => arg.parse::<DateKind>(),
+                    None => Ok(self.tree_options().date_kind.next()),
+                };
+                match kind {
+                    Ok(kind) => self.with_new_options(
+                        screen,
+                        &|o| {
+                            o.date_kind = kind;
+                            if !o.needs_dates() {
+                                o.show_dates = true;
+                            }
+                        },
+                        bang,
+                        con,
+                    ),
+                    Err(e) => CmdResult::error(e.to_string()),
+                }
+            }
             Internal::sort_by_date => self.with_new_options(
                 screen,
                 &|o| {
@@ -245,6 +361,17 @@ pub trait PanelState {
             Internal::toggle_hidden => {
                 self.with_new_options(screen, &|o| o.show_hidden ^= true, bang, con)
             }
+            Internal::toggle_ignored_sizes => self.with_new_options(
+                screen,
+                &|o| {
+                    o.show_ignored_sizes ^= true;
+                    if o.show_ignored_sizes {
+                        o.show_sizes = true;
+                    }
+                },
+                bang,
+                con,
+            ),
             Internal::toggle_root_fs => {
                 self.with_new_options(screen, &|o| o.show_root_fs ^= true, bang, con)
             }
@@ -314,6 +441,25 @@ pub trait PanelState {
                 }
             }
             Internal::stage => self.stage(app_state, cc, con),
+            Internal::stage_all => {
+                let paths = self.results_paths();
+                if paths.is_empty() {
+                    CmdResult::error("no listed path to stage")
+                } else {
+                    for path in paths {
+                        app_state.stage.add(path);
+                    }
+                    if cc.app.stage_panel.is_none() {
+                        CmdResult::NewPanel {
+                            state: Box::new(StageState::new(app_state, self.tree_options(), con)),
+                            purpose: PanelPurpose::None,
+                            direction: HDir::Right,
+                        }
+                    } else {
+                        CmdResult::Keep
+                    }
+                }
+            }
             Internal::unstage => self.unstage(app_state, cc, con),
             Internal::toggle_stage => self.toggle_stage(app_state, cc, con),
             Internal::close_staging_area => {
@@ -351,6 +497,43 @@ pub trait PanelState {
                     }
                 }
             }
+            Internal::cd_now => match self.selection() {
+                Some(sel) => {
+                    // the pending search, if any, was already given up in
+                    // clear_pending, so the selection is the displayed one
+                    let builder = ExecutionStringBuilder::from_sel_info(SelInfo::One(sel));
+                    ExternalExecution::new(
+                        ExecPattern::from_string("cd {directory}"),
+                        ExternalExecutionMode::FromParentShell,
+                    )
+                    .to_cmd_result(w, builder, con)?
+                }
+                None => CmdResult::error("no selection"),
+            },
+            Internal::why_ignored => match self.selected_path() {
+                Some(path) => match git::why_ignored(path) {
+                    Some(reason) => {
+                        let file = reason.file.to_string_lossy();
+                        let rule = if reason.excluded_path == path {
+                            format!(
+                                "ignored by line {} of {}: `{}`",
+                                reason.line_number, file, reason.line,
+                            )
+                        } else {
+                            format!(
+                                "{} is ignored by line {} of {}: `{}`",
+                                reason.excluded_path.to_string_lossy(),
+                                reason.line_number, file, reason.line,
+                            )
+                        };
+                        CmdResult::DisplayMessage(rule)
+                    }
+                    None => CmdResult::DisplayMessage(
+                        "this path isn't excluded by a gitignore rule".to_string()
+                    ),
+                },
+                None => CmdResult::error("no selection"),
+            },
             Internal::print_path => {
                 if let Some(path) = self.selected_path() {
                     print::print_path(path, con)?
@@ -365,7 +548,88 @@ pub trait PanelState {
                     CmdResult::error("no selection to print")
                 }
             }
+            Internal::profile => {
+                let name = get_str_arg(input_invocation, internal_exec);
+                match name.and_then(|name| con.profiles.get(name)) {
+                    Some(profile) => {
+                        let initial = &con.launch_args.tree_options;
+                        self.with_new_options(
+                            screen,
+                            &|o| o.apply_profile(initial, profile),
+                            bang,
+                            con,
+                        )
+                    }
+                    None => {
+                        let mut names: Vec<&str> = con.profiles.keys()
+                            .map(|n| n.as_str())
+                            .collect();
+                        names.sort_unstable();
+                        let names = if names.is_empty() {
+                            "none defined".to_string()
+                        } else {
+                            names.join(", ")
+                        };
+                        match name {
+                            Some(name) => CmdResult::error(format!(
+                                "Unknown profile: {:?} (available: {})", name, names,
+                            )),
+                            None => CmdResult::error(format!(
+                                "a profile name is needed (available: {})", names,
+                            )),
+                        }
+                    }
+                }
+            }
             Internal::refresh => CmdResult::RefreshState { clear_cache: true },
+            Internal::tag => match get_str_arg(input_invocation, internal_exec) {
+                Some(t) if tag::is_valid_tag(t) => {
+                    let paths = self.sel_paths(app_state);
+                    match tag::add_tag(&paths, t) {
+                        Ok(()) => CmdResult::Keep,
+                        Err(e) => CmdResult::error(format!("Failed to save tags: {}", e)),
+                    }
+                }
+                Some(t) => CmdResult::error(format!("Invalid tag: {:?}", t)),
+                None => CmdResult::error("a tag is needed, eg `:tag todo`"),
+            },
+            Internal::untag => {
+                let paths = self.sel_paths(app_state);
+                let t = get_str_arg(input_invocation, internal_exec);
+                match tag::remove_tag(&paths, t) {
+                    Ok(()) => CmdResult::Keep,
+                    Err(e) => CmdResult::error(format!("Failed to save tags: {}", e)),
+                }
+            }
+            Internal::register_yank => match get_str_arg(input_invocation, internal_exec) {
+                Some(register) => {
+                    // the paths are quoted, so that they can be told apart
+                    // and used as verb arguments once pasted
+                    let paths = self.sel_paths(app_state);
+                    if paths.is_empty() {
+                        CmdResult::error("nothing to yank")
+                    } else {
+                        app_state.registers.insert(register.to_string(), join_quoted(&paths));
+                        CmdResult::Keep
+                    }
+                }
+                None => CmdResult::error("a register is needed, eg `:register_yank a`"),
+            },
+            Internal::rebind => match get_str_arg(input_invocation, internal_exec) {
+                Some(arg) => rebind(arg, None, con),
+                None => CmdResult::error("a verb and a key are needed, eg `:rebind back ctrl-b`"),
+            },
+            Internal::layout
+            | Internal::panel_grow
+            | Internal::panel_shrink
+            | Internal::panel_zoom
+            | Internal::register_paste
+            | Internal::repeat
+            | Internal::reload_config
+            | Internal::toggle_skin => {
+                // those ones act on the input, on the layout or on the app's memory
+                CmdResult::HandleInApp(internal_exec.internal)
+            }
             Internal::quit => CmdResult::Quit,
             _ => CmdResult::Keep,
         })
@@ -445,6 +709,35 @@ pub trait PanelState {
         if verb.needs_another_panel && cc.app.other_path.is_none() {
             return Ok(CmdResult::error("This verb needs another panel"));
         }
+        if cc.app.con.launch_args.pick.is_some() {
+            if let VerbExecution::External(external) = &verb.execution {
+                if external.exec_mode.is_from_shell() {
+                    // the verbs meant to leave broot in the shell, like `cd`,
+                    // pick the selection instead
+                    return self.pick_paths(app_state, cc.app.con);
+                }
+            }
+            if verb.modifies_files() {
+                return Ok(CmdResult::error("This verb is disabled in pick mode"));
+            }
+        }
+        if verb.modifies_files() && cc.app.con.launch_args.dry_run {
+            return Ok(self.dry_run_verb(verb, invocation, app_state, cc));
+        }
+        if !cc.app.confirmed {
+            let paths = self.verb_paths(verb, app_state);
+            if verb.confirmation.is_required(paths.len()) {
+                let target = match paths.as_slice() {
+                    [path] => path.to_string_lossy().to_string(),
+                    _ => format!("{} paths", paths.len()),
+                };
+                return Ok(CmdResult::AskConfirmation(format!(
+                    "Execute *{}* on {}? Hit *enter* to confirm, *esc* to cancel",
+                    verb.names.first().map_or("this verb", |name| name.as_str()),
+                    target,
+                )));
+            }
+        }
         match &verb.execution {
             VerbExecution::Internal(internal_exec) => {
                 self.on_internal(w, internal_exec, invocation, trigger_type, app_state, cc)
@@ -458,16 +751,38 @@ pub trait PanelState {
         }
     }
 
-    fn execute_external(
-        &mut self,
-        w: &mut W,
-        verb: &Verb,
-        external_execution: &ExternalExecution,
+    /// the staged paths, when there are some, else the selected ones
+    fn staged_or_sel_paths(&self, app_state: &AppState) -> Vec<PathBuf> {
+        if app_state.stage.is_empty() {
+            self.sel_paths(app_state)
+        } else {
+            app_state.stage.paths().to_vec()
+        }
+    }
+
+    /// the paths a verb applies to: the staged ones for `:clean` and
+    /// the verbs with a `{files}` argument, else the selected ones
+    fn verb_paths(&self, verb: &Verb, app_state: &AppState) -> Vec<PathBuf> {
+        let uses_stage = match &verb.execution {
+            VerbExecution::Internal(internal_exec) => internal_exec.internal == Internal::clean,
+            VerbExecution::External(external) => external.exec_pattern.has_multi_path_group(),
+            VerbExecution::Sequence(_) => false,
+        };
+        if uses_stage {
+            self.staged_or_sel_paths(app_state)
+        } else {
+            self.sel_paths(app_state)
+        }
+    }
+
+    fn execution_builder<'c>(
+        &'c self,
+        verb: &'c Verb,
         invocation: Option<&VerbInvocation>,
-        app_state: &mut AppState,
-        cc: &CmdContext,
-    ) -> Result<CmdResult, ProgramError> {
-        let exec_builder = ExecutionStringBuilder::from_invocation(
+        app_state: &'c AppState,
+        cc: &'c CmdContext,
+    ) -> ExecutionStringBuilder<'c> {
+        ExecutionStringBuilder::from_invocation(
             &verb.invocation_parser,
             self.sel_info(app_state),
             &cc.app.other_path,
@@ -476,10 +791,66 @@ pub trait PanelState {
             } else {
                 &None
             },
-        );
+        )
+        .with_files(self.staged_or_sel_paths(app_state))
+        .with_results(self.results_paths())
+        .with_content_pattern(self.content_pattern())
+    }
+
+    fn execute_external(
+        &mut self,
+        w: &mut W,
+        verb: &Verb,
+        external_execution: &ExternalExecution,
+        invocation: Option<&VerbInvocation>,
+        app_state: &mut AppState,
+        cc: &CmdContext,
+    ) -> Result<CmdResult, ProgramError> {
+        let exec_builder = self.execution_builder(verb, invocation, app_state, cc);
         external_execution.to_cmd_result(w, exec_builder, &cc.app.con)
     }
 
+    /// list what the verb would do, in the dry-run panel, instead
+    /// of executing it
+    fn dry_run_verb(
+        &self,
+        verb: &Verb,
+        invocation: Option<&VerbInvocation>,
+        app_state: &mut AppState,
+        cc: &CmdContext,
+    ) -> CmdResult {
+        let actions = match &verb.execution {
+            VerbExecution::External(external_execution) => {
+                let exec_builder = self.execution_builder(verb, invocation, app_state, cc);
+                external_execution.dry_run_commands(&exec_builder)
+            }
+            VerbExecution::Internal(internal_exec) => {
+                let mut action = format!(":{}", internal_exec.internal.name());
+                let arg = invocation
+                    .and_then(|inv| inv.args.as_ref())
+                    .or(internal_exec.arg.as_ref());
+                if let Some(arg) = arg {
+                    action.push(' ');
+                    action.push_str(arg);
+                }
+                self.verb_paths(verb, app_state).iter()
+                    .map(|path| format!("{} on {}", action, path.to_string_lossy()))
+                    .collect()
+            }
+            VerbExecution::Sequence(_) => Vec::new(),
+        };
+        app_state.dry_run_actions.extend(actions);
+        if cc.app.dry_run_panel.is_some() {
+            CmdResult::DisplayMessage("Dry run: the verb wasn't executed".to_string())
+        } else {
+            CmdResult::NewPanel {
+                state: Box::new(DryRunState::new(self.tree_options(), cc.app.con)),
+                purpose: PanelPurpose::None,
+                direction: HDir::Right,
+            }
+        }
+    }
+
     fn execute_sequence(
         &mut self,
         _w: &mut W,
@@ -505,7 +876,8 @@ pub trait PanelState {
             } else {
                 &None
             },
-        );
+        )
+        .with_content_pattern(self.content_pattern());
         // TODO what follows is dangerous: if an inserted group value contains the separator,
         // the parsing will cut on this separator
         let sequence = Sequence {
@@ -527,7 +899,10 @@ pub trait PanelState {
         let screen = cc.app.screen;
         match &cc.cmd {
             Command::Click(x, y) => self.on_click(*x, *y, screen, con),
-            Command::DoubleClick(x, y) => self.on_double_click(*x, *y, screen, con),
+            Command::DoubleClick(x, y) => self.on_double_click(w, *x, *y, app_state, cc),
+            Command::RangeClick(x, y) => self.on_range_click(*x, *y, app_state, cc),
+            Command::Drag { start, pos } => self.on_drag(*start, *pos),
+            Command::DragEnd { start, pos } => self.on_drag_end(*start, *pos, app_state, cc),
             Command::PatternEdit { raw, expr } => {
                 match InputPattern::new(raw.clone(), expr, con) {
                     Ok(pattern) => self.on_pattern(pattern, app_state, con),
@@ -606,7 +981,7 @@ pub trait PanelState {
             }
         } else {
             if let Some(path) = self.selected_path() {
-                if path.is_file() {
+                if path.is_file() || path.is_dir() {
                     CmdResult::NewPanel {
                         state: Box::new(PreviewState::new(
                             path.to_path_buf(),
@@ -619,7 +994,7 @@ pub trait PanelState {
                         direction: HDir::Right,
                     }
                 } else {
-                    CmdResult::error("only regular files can be previewed")
+                    CmdResult::error("only regular files and directories can be previewed")
                 }
             } else {
                 CmdResult::error("no selected file")
@@ -631,6 +1006,11 @@ pub trait PanelState {
 
     fn selection(&self) -> Option<Selection<'_>>;
 
+    /// the root of the displayed tree, if the state displays one
+    fn tree_root(&self) -> Option<&Path> {
+        None
+    }
+
     fn sel_info<'c>(&'c self, _app_state: &'c AppState) -> SelInfo<'c> {
         // overloaded in stage_state
         match self.selection() {
@@ -639,12 +1019,55 @@ pub trait PanelState {
         }
     }
 
+    /// return the selected paths: either the selected one or,
+    /// in the staging area, all the staged ones
+    fn sel_paths(&self, app_state: &AppState) -> Vec<PathBuf> {
+        match self.sel_info(app_state) {
+            SelInfo::None => Vec::new(),
+            SelInfo::One(sel) => vec![sel.path.to_path_buf()],
+            SelInfo::More(stage) => stage.paths().to_vec(),
+        }
+    }
+
+    /// in pick mode, print the staged paths or the selection, and quit
+    fn pick_paths(
+        &self,
+        app_state: &AppState,
+        con: &AppContext,
+    ) -> Result<CmdResult, ProgramError> {
+        let paths = if app_state.stage.is_empty() {
+            self.sel_paths(app_state)
+        } else {
+            app_state.stage.paths().to_vec()
+        };
+        Ok(match &con.launch_args.pick {
+            Some(pick) => pick.pick(&paths, con)?,
+            None => CmdResult::error("not in pick mode"),
+        })
+    }
+
+    /// return the paths of the current search results, if any
+    fn results_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// return the pattern of the current search if it's on file contents
+    fn content_pattern(&self) -> Option<&Pattern> {
+        None
+    }
+
     fn has_at_least_one_selection(&self, _app_state: &AppState) -> bool {
         true // overloaded in stage_state
     }
 
     fn refresh(&mut self, screen: Screen, con: &AppContext) -> Command;
 
+    /// called when the screen or the layout changed, after the areas
+    /// of the panels have been recomputed
+    fn on_resize(&mut self, screen: Screen, con: &AppContext) {
+        self.refresh(screen, con);
+    }
+
     fn tree_options(&self) -> TreeOptions;
 
     /// build a cmdResult in response to a command being a change of
@@ -791,6 +1214,68 @@ pub fn get_arg<T: Copy + FromStr>(
         .unwrap_or(default)
 }
 
+/// biggest file whose content can be copied with `:copy_file_content`
+#[cfg(feature = "clipboard")]
+const MAX_COPIED_FILE_SIZE: u64 = 1_000_000;
+
+/// build the text put in the clipboard by the copy internals, the
+/// values of the various paths being separated with newlines.
+///
+/// Relative paths are relative to the given root (usually the
+/// one of the displayed tree)
+#[cfg(feature = "clipboard")]
+fn clipboard_content(
+    internal: Internal,
+    paths: &[PathBuf],
+    root: &Path,
+) -> Result<String, String> {
+    if paths.is_empty() {
+        return Err("Nothing to copy".to_string());
+    }
+    let mut values = Vec::new();
+    for path in paths {
+        values.push(match internal {
+            Internal::copy_relative_path => {
+                match pathdiff::diff_paths(path, root) {
+                    Some(p) if p.components().next().is_some() => p.to_string_lossy().to_string(),
+                    Some(_) => ".".to_string(),
+                    None => path.to_string_lossy().to_string(),
+                }
+            }
+            Internal::copy_name => match path.file_name() {
+                Some(name) => name.to_string_lossy().to_string(),
+                None => path.to_string_lossy().to_string(),
+            },
+            Internal::copy_file_content => {
+                let too_big = std::fs::metadata(path)
+                    .map_err(|e| format!("Can't read {:?}: {}", path, e))?
+                    .len() > MAX_COPIED_FILE_SIZE;
+                if too_big || path.is_dir() {
+                    return Err(format!("Not a small text file: {:?}", path));
+                }
+                std::fs::read_to_string(path)
+                    .map_err(|_| format!("Not a small text file: {:?}", path))?
+            }
+            _ => path.to_string_lossy().to_string(),
+        });
+    }
+    Ok(values.join("\n"))
+}
+
+/// return the trimmed argument given either in the input or in the
+/// internal execution (for configured verbs like `:new_file notes.md`),
+/// None if there's none or it's empty
+pub fn get_str_arg<'a>(
+    verb_invocation: Option<&'a VerbInvocation>,
+    internal_exec: &'a InternalExecution,
+) -> Option<&'a str> {
+    verb_invocation
+        .and_then(|vi| vi.args.as_deref())
+        .or(internal_exec.arg.as_deref())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
 pub fn initial_mode(con: &AppContext) -> Mode {
     if con.modal {
         Mode::Command
@@ -12,11 +12,14 @@ use {
 pub struct StandardStatus {
     tree_top_focus: String, // go up (if not at root)
     tree_dir_focus: String,
+    tree_pruning: String, // list the unlisted children
     tree_dir_cd: Option<String>, // TODO check outcmd
     tree_file_open_stay: Option<String>,
     tree_file_open_leave: Option<String>,
     tree_unfiltered: String,
     tree_filtered: String,
+    tree_budget_exhausted: Option<String>,
+    tree_result_capped: Option<String>,
     preview_unfiltered: String, // ctrl-left to close, or a pattern to filter
     preview_filtered: Option<String>,
     preview_restorable_filter: Option<String>,
@@ -29,6 +32,7 @@ impl StandardStatus {
     pub fn new(verb_store: &VerbStore) -> Self {
         let tree_top_focus = "*enter* to go up".to_string(); // enter is hardcoded on focus
         let tree_dir_focus = "*enter* to focus".to_string();
+        let tree_pruning = "*enter* to list them".to_string();
         let tree_dir_cd = verb_store
             .key_desc_of_internal_stype(Internal::open_leave, SelectionType::Directory)
             .map(|k| format!("*{}* to cd", k));
@@ -41,6 +45,12 @@ impl StandardStatus {
         //let tree_file_enter = None; // TODO (for when enter is customized)
         let tree_unfiltered = "a few letters to search".to_string();
         let tree_filtered = "*esc* to clear the filter".to_string();
+        let tree_budget_exhausted = verb_store
+            .key_desc_of_internal(Internal::total_search)
+            .map(|k| format!("*{}* to search past the time budget", k));
+        let tree_result_capped = verb_store
+            .key_desc_of_internal(Internal::total_search)
+            .map(|k| format!("*{}* to search past the result cap", k));
         let preview_unfiltered = "a pattern to filter".to_string();
         let preview_filtered = verb_store
             .key_desc_of_internal(Internal::panel_right)
@@ -54,12 +64,15 @@ impl StandardStatus {
         Self {
             tree_top_focus,
             tree_dir_focus,
+            tree_pruning,
             tree_dir_cd,
             tree_file_open_stay,
             tree_file_open_leave,
             //tree_file_enter,
             tree_unfiltered,
             tree_filtered,
+            tree_budget_exhausted,
+            tree_result_capped,
             preview_unfiltered,
             preview_filtered,
             preview_restorable_filter,
@@ -115,8 +128,11 @@ pub struct StandardStatusBuilder<'s> {
     selection: Selection<'s>,
     pub has_previous_state: bool,
     pub is_filtered: bool,
+    pub budget_exhausted: bool, // whether the search was stopped by its time budget
+    pub result_capped: bool, // whether the search was stopped by the result cap
     pub has_removed_pattern: bool,
     pub on_tree_root: bool, // should this be part of the Selection struct ?
+    pub on_pruning: bool, // whether the selected line is a "xxx unlisted" one
 }
 impl<'s> StandardStatusBuilder<'s> {
     fn new(
@@ -130,8 +146,11 @@ impl<'s> StandardStatusBuilder<'s> {
             selection,
             has_previous_state: true,
             is_filtered: false,
+            budget_exhausted: false,
+            result_capped: false,
             has_removed_pattern: false,
             on_tree_root: false,
+            on_pruning: false,
         }
     }
     pub fn status(self) -> Status {
@@ -146,6 +165,8 @@ impl<'s> StandardStatusBuilder<'s> {
                     if self.selection.path.file_name().is_some() { // it's not '/'
                         parts.add(&ss.tree_top_focus);
                     }
+                } else if self.on_pruning {
+                    parts.add(&ss.tree_pruning);
                 } else if self.selection.stype == SelectionType::Directory {
                     parts.add(&ss.tree_dir_focus);
                     parts.addo(&ss.tree_dir_cd);
@@ -158,6 +179,11 @@ impl<'s> StandardStatusBuilder<'s> {
                 if self.is_filtered {
                     parts.add(&ss.tree_filtered);
                 }
+                if self.budget_exhausted {
+                    parts.addo(&ss.tree_budget_exhausted);
+                } else if self.result_capped {
+                    parts.addo(&ss.tree_result_capped);
+                }
                 if parts.len() < 3 {
                     parts.add(&ss.help);
                 }
@@ -185,12 +211,24 @@ impl<'s> StandardStatusBuilder<'s> {
                     parts.add(&ss.no_verb);
                 }
             }
+            PanelStateType::Palette => {
+                // not used, palette_state has its own status
+            }
+            PanelStateType::Cleanable => {
+                // not used, cleanable_state has its own status
+            }
+            PanelStateType::DryRun => {
+                parts.add(&ss.not_first_state);
+            }
             PanelStateType::Fs => {
                 warn!("TODO fs status");
             }
             PanelStateType::Stage => {
                 warn!("TODO stage status");
             }
+            PanelStateType::Worktrees => {
+                // not used, worktrees_state has its own status
+            }
         }
         parts.to_status()
     }
@@ -7,15 +7,27 @@ pub enum PanelStateType {
     /// The standard browsing tree
     Tree,
 
+    /// the build artifacts found under a directory
+    Cleanable,
+
+    /// what the verbs would have done, in dry-run mode
+    DryRun,
+
     /// the filesystem
     Fs,
 
     /// The help "screen"
     Help,
 
+    /// the list of commands to search and execute
+    Palette,
+
     /// The preview panel, never alone on screen
     Preview,
 
     /// The stage panel, never alone on screen
     Stage,
+
+    /// the git worktrees of a repository
+    Worktrees,
 }
@@ -1,23 +1,66 @@
 use {
     crate::{
         app::*,
-        command::{Command, TriggerType},
+        cleanable::CleanableState,
+        command::{Command, CommandParts, TriggerType},
+        conf::LocalConf,
         display::{DisplayableTree, Screen, W},
         errors::{ProgramError, TreeBuildError},
         flag::Flag,
         git,
         pattern::*,
         path::{self, PathAnchor},
+        path_index,
         print,
+        stage::StageState,
         task_sync::Dam,
         tree::*,
         tree_build::TreeBuilder,
         verb::*,
     },
     open,
-    std::path::{Path, PathBuf},
+    std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
 };
 
+/// number of chars the names are scrolled by, when no count is given
+/// to `:scroll_left` or `:scroll_right`
+const H_SCROLL_STEP: i32 = 10;
+
+/// apply the local configuration (the `.broot.toml` files) of the
+/// root, if it's not the one which already applied to the options.
+///
+/// This way toggles done by the user are kept while navigating
+/// inside the same project, and the options the project changed
+/// are restored when leaving it.
+fn apply_local_conf(root: &Path, options: &mut TreeOptions, con: &AppContext) {
+    let local_conf = LocalConf::find(root);
+    let files = |lc: Option<&LocalConf>| lc.map(|lc| lc.files.clone()).unwrap_or_default();
+    if files(local_conf.as_ref()) == files(options.local_conf.as_deref()) {
+        return;
+    }
+    if let Some(base) = options.local_conf_base.take() {
+        base.restore(options);
+    }
+    if let Some(local_conf) = &local_conf {
+        options.local_conf_base = Some(local_conf.apply_to(options));
+        if let Some(raw) = local_conf.default_pattern.as_ref().filter(|_| options.pattern.is_none()) {
+            let parts = CommandParts::from(raw.to_string());
+            match InputPattern::new(raw.to_string(), &parts.pattern, con) {
+                Ok(pattern) => {
+                    options.pattern = pattern;
+                }
+                Err(e) => {
+                    warn!("invalid default pattern {:?} : {}", raw, e);
+                }
+            }
+        }
+    }
+    options.local_conf = local_conf.map(Arc::new);
+}
+
 /// An application state dedicated to displaying a tree.
 /// It's the first and main screen of broot.
 pub struct BrowserState {
@@ -25,6 +68,8 @@ pub struct BrowserState {
     pub filtered_tree: Option<Tree>,
     pub pending_pattern: InputPattern, // a pattern (or not) which has not yet be applied
     pub total_search_required: bool,   // whether the pending pattern should be in total search mode
+    pending_revealed_match: Option<PathBuf>, // a match which didn't fit, to bring into view
+    page_height: i32, // the height the trees were last sized for
     mode: Mode,
 }
 
@@ -41,6 +86,7 @@ impl BrowserState {
         con: &AppContext,
         dam: &Dam,
     ) -> Result<Option<BrowserState>, TreeBuildError> {
+        apply_local_conf(&path, &mut options, con);
         let pending_pattern = options.pattern.take();
         let builder = TreeBuilder::from(
             path,
@@ -53,6 +99,8 @@ impl BrowserState {
             filtered_tree: None,
             pending_pattern,
             total_search_required: false,
+            pending_revealed_match: None,
+            page_height: BrowserState::page_height(screen),
             mode: initial_mode(con),
         }))
     }
@@ -65,6 +113,41 @@ impl BrowserState {
         i32::from(screen.height) - 2
     }
 
+    /// return the selected directory, or the parent of the selection,
+    /// if it has children which weren't loaded (see max_loaded_children)
+    fn dir_with_unloaded_children(&self) -> Option<PathBuf> {
+        let tree = self.displayed_tree();
+        let selected = tree.selected_line();
+        let mut candidates = vec![selected.path.as_path()];
+        candidates.extend(selected.path.parent());
+        candidates
+            .into_iter()
+            .find(|path| {
+                tree.lines
+                    .iter()
+                    .any(|line| line.path == *path && line.is_dir() && line.not_loaded > 0)
+            })
+            .map(Path::to_path_buf)
+    }
+
+    /// return the selected directory if some of its children aren't
+    /// listed, or else the parent of the selection if it's in this case
+    /// (which is the directory of a selected pruning line)
+    fn dir_with_unlisted_children(&self) -> Option<PathBuf> {
+        let tree = self.displayed_tree();
+        let selected = tree.selected_line();
+        if selected.is_dir() && selected.unlisted > 0 {
+            return Some(selected.path.clone());
+        }
+        let parent = selected.path.parent()?;
+        if selected.is_pruning() {
+            return Some(parent.to_path_buf());
+        }
+        tree.lines.iter()
+            .find(|line| line.path == parent && line.unlisted > 0)
+            .map(|line| line.path.clone())
+    }
+
     /// return a reference to the currently displayed tree, which
     /// is the filtered tree if there's one, the base tree if not.
     pub fn displayed_tree(&self) -> &Tree {
@@ -77,8 +160,49 @@ impl BrowserState {
         self.filtered_tree.as_mut().unwrap_or(&mut self.tree)
     }
 
+    /// list all the children of the selected directory (or of the
+    /// directory of the selected pruning line), in place, even if the
+    /// tree becomes higher than the screen
+    fn expand_unlisted(&mut self) -> CmdResult {
+        match self.dir_with_unlisted_children() {
+            Some(dir) => {
+                self.tree.options.expand_dir(&dir);
+                if let Some(tree) = &mut self.filtered_tree {
+                    tree.options.expand_dir(&dir);
+                }
+                CmdResult::RefreshState { clear_cache: false }
+            }
+            None => CmdResult::error("no unlisted children here"),
+        }
+    }
+
+    /// collapse the selected directory if its children are listed,
+    /// list all of them if they aren't
+    fn toggle_expand(&mut self) -> CmdResult {
+        if self.filtered_tree.is_some() {
+            return CmdResult::error("directories can't be collapsed while searching");
+        }
+        let tree = &self.tree;
+        let line = tree.selected_line();
+        if tree.selection == 0 || !line.is_dir() {
+            return CmdResult::error("select a directory below the root");
+        }
+        let dir = line.path.clone();
+        let children_listed = matches!(
+            tree.lines.get(tree.selection + 1),
+            Some(next) if next.path.parent() == Some(&dir),
+        );
+        if children_listed {
+            self.tree.options.collapse_dir(&dir);
+        } else {
+            self.tree.options.expand_dir(&dir);
+        }
+        CmdResult::RefreshState { clear_cache: false }
+    }
+
     pub fn open_selection_stay_in_broot(
         &mut self,
+        w: &mut W,
         screen: Screen,
         con: &AppContext,
         in_new_panel: bool,
@@ -86,6 +210,9 @@ impl BrowserState {
     ) -> Result<CmdResult, ProgramError> {
         let tree = self.displayed_tree();
         let line = tree.selected_line();
+        if line.is_pruning() {
+            return Ok(self.expand_unlisted());
+        }
         let mut target = line.target().to_path_buf();
         if line.is_dir() {
             if tree.selection == 0 {
@@ -110,6 +237,8 @@ impl BrowserState {
                 ),
                 in_new_panel,
             ))
+        } else if let Some(opener) = Opener::find(&con.openers, &target) {
+            opener.open(w, &target, con)
         } else {
             match open::that(&target) {
                 Ok(exit_status) => {
@@ -142,6 +271,66 @@ impl BrowserState {
         }
     }
 
+    /// create a new file or directory, relative to the selection,
+    /// then refresh the tree and select the created path
+    fn create_and_select(
+        &mut self,
+        internal: Internal,
+        name: &str,
+        screen: Screen,
+        con: &AppContext,
+    ) -> CmdResult {
+        let selected_path = self.displayed_tree().selected_line().path.clone();
+        let created = if internal == Internal::new_dir {
+            internal_new::create_dir(&selected_path, name)
+        } else {
+            internal_new::create_file(&selected_path, name, con)
+        };
+        let path = match created {
+            Ok(path) => path,
+            Err(e) => {
+                return CmdResult::error(format!("Can't create {:?}: {}", name, e));
+            }
+        };
+        // the new path wouldn't necessarily match the current filter
+        self.filtered_tree = None;
+        let page_height = BrowserState::page_height(screen);
+        if let Err(e) = self.tree.refresh(page_height as usize, con) {
+            warn!("refreshing tree failed : {:?}", e);
+        }
+        if self.tree.try_select_path(&path) {
+            self.tree.make_selection_visible(page_height);
+        }
+        CmdResult::Keep
+    }
+
+    /// stage the lines from start to end (in any order), then
+    /// open the staging area if it's not already
+    fn stage_lines(
+        &mut self,
+        start: usize,
+        end: usize,
+        app_state: &mut AppState,
+        cc: &CmdContext,
+    ) -> CmdResult {
+        let tree = self.displayed_tree();
+        let range = start.min(end)..=start.max(end);
+        for line in &tree.lines[range] {
+            // neither the root nor the pruning lines can be staged
+            if line.depth > 0 && !line.is_pruning() {
+                app_state.stage.add(line.path.clone());
+            }
+        }
+        if app_state.stage.is_empty() || cc.app.stage_panel.is_some() {
+            return CmdResult::Keep;
+        }
+        CmdResult::NewPanel {
+            state: Box::new(StageState::new(app_state, self.tree_options(), cc.app.con)),
+            purpose: PanelPurpose::None,
+            direction: HDir::Right,
+        }
+    }
+
 }
 
 impl PanelState for BrowserState {
@@ -161,8 +350,12 @@ impl PanelState for BrowserState {
     fn get_pending_task(&self) -> Option<&'static str> {
         if self.pending_pattern.is_some() {
             Some("searching")
+        } else if self.pending_revealed_match.is_some() {
+            Some("searching the next match")
         } else if self.displayed_tree().has_dir_missing_sum() {
             Some("computing stats")
+        } else if self.displayed_tree().has_dir_missing_ignored_size() {
+            Some("computing ignored sizes")
         } else if self.displayed_tree().is_missing_git_status_computation() {
             Some("computing git status")
         } else {
@@ -171,17 +364,48 @@ impl PanelState for BrowserState {
     }
 
     fn selected_path(&self) -> Option<&Path> {
-        Some(&self.displayed_tree().selected_line().path)
+        let line = self.displayed_tree().selected_line();
+        if line.is_pruning() {
+            // it doesn't stand for a path, verbs mustn't act on it
+            None
+        } else {
+            Some(&line.path)
+        }
     }
 
     fn selection(&self) -> Option<Selection<'_>> {
-        Some(self.displayed_tree().selected_line().as_selection())
+        let tree = self.displayed_tree();
+        let line = tree.selected_line();
+        if line.is_pruning() {
+            return None;
+        }
+        Some(line.as_selection())
+    }
+
+    fn tree_root(&self) -> Option<&Path> {
+        Some(self.displayed_tree().root())
     }
 
     fn tree_options(&self) -> TreeOptions {
         self.displayed_tree().options.clone()
     }
 
+    fn results_paths(&self) -> Vec<PathBuf> {
+        match &self.filtered_tree {
+            Some(tree) => tree.matching_paths(),
+            None => Vec::new(),
+        }
+    }
+
+    fn content_pattern(&self) -> Option<&Pattern> {
+        let pattern = &self.displayed_tree().options.pattern.pattern;
+        if pattern.object().content {
+            Some(pattern)
+        } else {
+            None
+        }
+    }
+
     /// build a cmdResult asking for the addition of a new state
     /// being a browser state similar to the current one but with
     /// different options
@@ -194,6 +418,7 @@ impl PanelState for BrowserState {
     ) -> CmdResult {
         let tree = self.displayed_tree();
         let mut options = tree.options.clone();
+        options.revealed_matches.clear();
         change_options(&mut options);
         CmdResult::from_optional_state(
             BrowserState::new(tree.root().clone(), options, screen, con, &Dam::unlimited()),
@@ -203,6 +428,7 @@ impl PanelState for BrowserState {
 
     fn clear_pending(&mut self) {
         self.pending_pattern = InputPattern::none();
+        self.pending_revealed_match = None;
     }
 
     fn on_click(
@@ -216,20 +442,87 @@ impl PanelState for BrowserState {
         Ok(CmdResult::Keep)
     }
 
+    /// stage the lines between the selection and the clicked
+    /// one, which becomes the selection
+    fn on_range_click(
+        &mut self,
+        _x: u16,
+        y: u16,
+        app_state: &mut AppState,
+        cc: &CmdContext,
+    ) -> Result<CmdResult, ProgramError> {
+        let tree = self.displayed_tree_mut();
+        let end = match tree.line_index_at_y(y as i32) {
+            Some(idx) => idx,
+            None => return Ok(CmdResult::Keep),
+        };
+        let start = tree.selection;
+        tree.selection = end;
+        Ok(self.stage_lines(start, end, app_state, cc))
+    }
+
+    /// select the line under the mouse, showing the range of
+    /// lines from the one where the drag started
+    fn on_drag(
+        &mut self,
+        start: (u16, u16),
+        pos: (u16, u16),
+    ) -> Result<CmdResult, ProgramError> {
+        let tree = self.displayed_tree_mut();
+        if tree.drag_start.is_none() {
+            tree.drag_start = tree.line_index_at_y(start.1 as i32);
+        }
+        if tree.drag_start.is_some() {
+            if let Some(idx) = tree.line_index_at_y(pos.1 as i32) {
+                tree.selection = idx;
+            }
+        }
+        Ok(CmdResult::Keep)
+    }
+
+    /// stage the range of lines from the one where the drag
+    /// started to the one where it ended
+    fn on_drag_end(
+        &mut self,
+        start: (u16, u16),
+        pos: (u16, u16),
+        app_state: &mut AppState,
+        cc: &CmdContext,
+    ) -> Result<CmdResult, ProgramError> {
+        self.on_drag(start, pos)?;
+        let tree = self.displayed_tree_mut();
+        match tree.drag_start.take() {
+            Some(start) => {
+                let end = tree.selection;
+                Ok(self.stage_lines(start, end, app_state, cc))
+            }
+            None => Ok(CmdResult::Keep),
+        }
+    }
+
+    /// execute the verb configured for double-clicks on the type
+    /// of the selection or, by default, open the selection
     fn on_double_click(
         &mut self,
+        w: &mut W,
         _x: u16,
         y: u16,
-        screen: Screen,
-        con: &AppContext,
+        app_state: &mut AppState,
+        cc: &CmdContext,
     ) -> Result<CmdResult, ProgramError> {
-        if self.displayed_tree().selection == y as usize {
-            self.open_selection_stay_in_broot(screen, con, false, false)
-        } else {
+        let tree = self.displayed_tree();
+        if tree.line_index_at_y(y as i32) != Some(tree.selection) {
             // A double click always come after a simple click at
             // same position. If it's not the selected line, it means
             // the click wasn't on a selectable/openable tree line
-            Ok(CmdResult::Keep)
+            return Ok(CmdResult::Keep);
+        }
+        let con = cc.app.con;
+        let stype = tree.selected_line().selection_type();
+        if let Some(verb) = con.verb_store.double_click_verb(stype) {
+            self.execute_verb(w, verb, None, TriggerType::Other, app_state, cc)
+        } else {
+            self.open_selection_stay_in_broot(w, cc.app.screen, con, false, false)
         }
     }
 
@@ -242,7 +535,9 @@ impl PanelState for BrowserState {
         if pat.is_none() {
             self.filtered_tree = None;
         }
+        self.total_search_required = pat.is_unbounded();
         self.pending_pattern = pat;
+        self.pending_revealed_match = None;
         Ok(CmdResult::Keep)
     }
 
@@ -286,6 +581,28 @@ impl PanelState for BrowserState {
                 con,
                 self.displayed_tree().options.clone(),
             ),
+            Internal::load_children => match self.dir_with_unloaded_children() {
+                Some(dir) => {
+                    self.tree.options.uncapped_dirs.push(dir.clone());
+                    if let Some(tree) = &mut self.filtered_tree {
+                        tree.options.uncapped_dirs.push(dir);
+                    }
+                    CmdResult::RefreshState { clear_cache: false }
+                }
+                None => CmdResult::error("no directory with children left to load here"),
+            },
+            Internal::cleanable => {
+                // the whole tree is scanned, not just the selected directory
+                let root = self.displayed_tree().root().to_path_buf();
+                let state = CleanableState::new(&root, self.tree_options(), con);
+                CmdResult::NewState(Box::new(state))
+            }
+            Internal::expand_unlisted => self.expand_unlisted(),
+            Internal::toggle_expand => self.toggle_expand(),
+            Internal::build_index => {
+                path_index::update_in_background(self.displayed_tree().root().clone());
+                CmdResult::Keep
+            }
             Internal::up_tree => match self.displayed_tree().root().parent() {
                 Some(path) => internal_focus::on_path(
                     path.to_path_buf(),
@@ -296,8 +613,15 @@ impl PanelState for BrowserState {
                 ),
                 None => CmdResult::error("no parent found"),
             },
-            Internal::open_stay => self.open_selection_stay_in_broot(screen, con, bang, false)?,
-            Internal::open_stay_filter => self.open_selection_stay_in_broot(screen, con, bang, true)?,
+            Internal::open_stay if con.launch_args.pick.is_some()
+                && !self.displayed_tree().selected_line().is_dir()
+                && !self.displayed_tree().selected_line().is_pruning() =>
+            {
+                // in pick mode, files are picked instead of being opened
+                self.pick_paths(app_state, con)?
+            }
+            Internal::open_stay => self.open_selection_stay_in_broot(w, screen, con, bang, false)?,
+            Internal::open_stay_filter => self.open_selection_stay_in_broot(w, screen, con, bang, true)?,
             Internal::line_down => {
                 let count = get_arg(input_invocation, internal_exec, 1);
                 self.displayed_tree_mut().move_selection(count, page_height, true);
@@ -308,6 +632,26 @@ impl PanelState for BrowserState {
                 self.displayed_tree_mut().move_selection(-count, page_height, true);
                 CmdResult::Keep
             }
+            Internal::scroll_down => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.displayed_tree_mut().scroll_view(count, page_height);
+                CmdResult::Keep
+            }
+            Internal::scroll_up => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.displayed_tree_mut().scroll_view(-count, page_height);
+                CmdResult::Keep
+            }
+            Internal::scroll_left => {
+                let count: i32 = get_arg(input_invocation, internal_exec, H_SCROLL_STEP);
+                self.displayed_tree_mut().h_scroll_by(-count);
+                CmdResult::Keep
+            }
+            Internal::scroll_right => {
+                let count: i32 = get_arg(input_invocation, internal_exec, H_SCROLL_STEP);
+                self.displayed_tree_mut().h_scroll_by(count);
+                CmdResult::Keep
+            }
             Internal::line_down_no_cycle => {
                 let count = get_arg(input_invocation, internal_exec, 1);
                 self.displayed_tree_mut().move_selection(count, page_height, false);
@@ -318,12 +662,38 @@ impl PanelState for BrowserState {
                 self.displayed_tree_mut().move_selection(-count, page_height, false);
                 CmdResult::Keep
             }
+            Internal::new_dir | Internal::new_file => {
+                match get_str_arg(input_invocation, internal_exec) {
+                    Some(name) => self.create_and_select(internal_exec.internal, name, screen, con),
+                    None => CmdResult::error("a name is needed, eg `:new_file notes.md`"),
+                }
+            }
             Internal::previous_match => {
-                self.displayed_tree_mut().try_select_previous_match();
+                let tree = self.displayed_tree_mut();
+                if tree.has_match_before_selection() {
+                    tree.try_select_previous_match();
+                } else if let Some(path) = tree.take_unlisted_match(true) {
+                    // going backward from the first match, the matches which
+                    // didn't fit are brought into view, worst first
+                    self.pending_revealed_match = Some(path);
+                } else {
+                    tree.try_select_previous_match();
+                }
+                self.displayed_tree_mut().make_selection_visible(page_height);
                 CmdResult::Keep
             }
             Internal::next_match => {
-                self.displayed_tree_mut().try_select_next_match();
+                let tree = self.displayed_tree_mut();
+                if tree.has_match_after_selection() && tree.options.revealed_matches.is_empty() {
+                    tree.try_select_next_match();
+                } else if let Some(path) = tree.take_unlisted_match(false) {
+                    // once the displayed matches are visited, the ones which
+                    // didn't fit are brought into view, best first, then we cycle
+                    self.pending_revealed_match = Some(path);
+                } else {
+                    tree.try_select_next_match();
+                }
+                self.displayed_tree_mut().make_selection_visible(page_height);
                 CmdResult::Keep
             }
             Internal::previous_same_depth => {
@@ -399,7 +769,13 @@ impl PanelState for BrowserState {
                 print::print_relative_path(path, con)?
             }
             Internal::print_tree => {
-                print::print_tree(&self.displayed_tree(), cc.app.screen, &cc.app.panel_skin, con)?
+                print::print_tree(
+                    self.displayed_tree(),
+                    cc.app.screen,
+                    cc.app.panel_skin,
+                    &con.launch_args.export_config,
+                    con,
+                )?
             }
             Internal::select_first => {
                 self.displayed_tree_mut().try_select_first();
@@ -482,8 +858,11 @@ impl PanelState for BrowserState {
         );
         ssb.has_previous_state = has_previous_state;
         ssb.is_filtered = self.filtered_tree.is_some();
+        ssb.budget_exhausted = matches!(&self.filtered_tree, Some(tree) if tree.budget_exhausted);
+        ssb.result_capped = matches!(&self.filtered_tree, Some(tree) if tree.result_capped);
         ssb.has_removed_pattern = false;
         ssb.on_tree_root = self.displayed_tree().selection == 0;
+        ssb.on_pruning = self.displayed_tree().selected_line().is_pruning();
         ssb.status()
     }
 
@@ -498,7 +877,7 @@ impl PanelState for BrowserState {
         if self.pending_pattern.is_some() {
             let pattern_str = self.pending_pattern.raw.clone();
             let mut options = self.tree.options.clone();
-            options.pattern = self.pending_pattern.take();
+            options.pattern = self.pending_pattern.clone();
             let root = self.tree.root().clone();
             let page_height = BrowserState::page_height(screen) as usize;
             let builder = match TreeBuilder::from(root, options, page_height, con) {
@@ -514,18 +893,40 @@ impl PanelState for BrowserState {
                 &pattern_str,
                 builder.build(self.total_search_required, dam),
             ); // can be None if a cancellation was required
+            if filtered_tree.is_none() {
+                // the pattern is kept pending so that the search restarts when
+                // the interruption wasn't a command (eg a resize of the terminal)
+                return;
+            }
+            self.pending_pattern = InputPattern::none();
             self.total_search_required = false;
             if let Some(ref mut ft) = filtered_tree {
                 ft.try_select_best_match();
                 ft.make_selection_visible(BrowserState::page_height(screen));
                 self.filtered_tree = filtered_tree;
             }
+        } else if let Some(path) = self.pending_revealed_match.clone() {
+            let page_height = BrowserState::page_height(screen) as usize;
+            match self.displayed_tree_mut().reveal_match(&path, page_height, con, dam) {
+                Ok(true) => {
+                    self.pending_revealed_match = None;
+                }
+                Ok(false) => {
+                    // interrupted, the match is searched again on next call
+                }
+                Err(e) => {
+                    warn!("Error while revealing a match: {:?}", e);
+                    self.pending_revealed_match = None;
+                }
+            }
         } else if self.displayed_tree().is_missing_git_status_computation() {
             let root_path = self.displayed_tree().root();
             let git_status = git::get_tree_status(root_path, dam);
             self.displayed_tree_mut().git_status = git_status;
-        } else {
+        } else if self.displayed_tree().has_dir_missing_sum() {
             self.displayed_tree_mut().fetch_some_missing_dir_sum(dam, con);
+        } else {
+            self.displayed_tree_mut().fetch_some_missing_ignored_size(dam, con);
         }
     }
 
@@ -541,6 +942,8 @@ impl PanelState for BrowserState {
             ext_colors: &disc.con.ext_colors,
             area: disc.state_area.clone(),
             in_app: true,
+            show_selection: true,
+            ascii: false,
         };
         dp.write_on(w)
     }
@@ -563,9 +966,27 @@ impl PanelState for BrowserState {
         })
     }
 
+    fn on_resize(&mut self, screen: Screen, con: &AppContext) {
+        let page_height = BrowserState::page_height(screen);
+        let height_changed = page_height != self.page_height;
+        self.page_height = page_height;
+        if let Err(e) = self.tree.refresh(page_height as usize, con) {
+            warn!("refreshing base tree failed : {:?}", e);
+        }
+        // the filtered tree isn't rebuilt here, which could take a long
+        // time, but the search is restarted in background for the new height
+        if let Some(tree) = &mut self.filtered_tree {
+            tree.make_selection_visible(page_height);
+            if height_changed && self.pending_pattern.is_none() {
+                self.pending_pattern = tree.options.pattern.clone();
+                self.total_search_required = tree.total_search;
+            }
+        }
+    }
+
     fn get_flags(&self) -> Vec<Flag> {
         let options = &self.displayed_tree().options;
-        vec![
+        let mut flags = vec![
             Flag {
                 name: "h",
                 value: if options.show_hidden { "y" } else { "n" },
@@ -574,7 +995,20 @@ impl PanelState for BrowserState {
                 name: "gi",
                 value: if options.respect_git_ignore { "y" } else { "n" },
             },
-        ]
+        ];
+        if options.needs_dates() {
+            flags.push(Flag {
+                name: "date",
+                value: options.date_kind.name(),
+            });
+        }
+        if options.local_conf.is_some() {
+            flags.push(Flag {
+                name: "local",
+                value: "y",
+            });
+        }
+        flags
     }
 
     fn get_starting_input(&self) -> String {
@@ -586,3 +1020,161 @@ impl PanelState for BrowserState {
     }
 }
 
+
+#[cfg(test)]
+mod browser_state_test {
+
+    use {
+        super::*,
+        crate::{
+            conf::LOCAL_CONF_FILENAME,
+            display::{Areas, Layout},
+            search::Searcher,
+            skin::{PanelSkin, StyleMap},
+        },
+        std::fs,
+    };
+
+    #[test]
+    fn check_local_conf_application() {
+        let searcher = Searcher::new().unwrap();
+        let con = searcher.context();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let outside = temp_dir.path();
+        let project = outside.join("project");
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::write(project.join(LOCAL_CONF_FILENAME), "hidden = true\n").unwrap();
+        let initial = TreeOptions::default();
+        let mut options = initial.clone();
+        apply_local_conf(&project, &mut options, con);
+        assert!(options.show_hidden);
+        // the toggles of the user are kept while inside the project
+        options.respect_git_ignore = !initial.respect_git_ignore;
+        apply_local_conf(&project.join("src"), &mut options, con);
+        assert!(options.show_hidden);
+        assert_eq!(options.respect_git_ignore, !initial.respect_git_ignore);
+        // leaving the project restores the options as they were before
+        apply_local_conf(outside, &mut options, con);
+        assert!(options.local_conf.is_none());
+        assert_eq!(options.show_hidden, initial.show_hidden);
+        assert_eq!(options.respect_git_ignore, initial.respect_git_ignore);
+    }
+
+    #[test]
+    fn check_pruning_line_expansion() {
+        let searcher = Searcher::new().unwrap();
+        let con = searcher.context();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("dir");
+        fs::create_dir_all(&dir).unwrap();
+        for i in 0..20 {
+            fs::write(dir.join(format!("f{:02}", i)), "").unwrap();
+        }
+        let screen = Screen { width: 80, height: 10 };
+        let mut state = BrowserState::new(
+            temp_dir.path().to_path_buf(),
+            TreeOptions::default(),
+            screen,
+            con,
+            &Dam::unlimited(),
+        ).unwrap().unwrap();
+        let page_height = BrowserState::page_height(screen);
+        assert!(state.tree.try_select_last(page_height));
+        assert!(state.tree.selected_line().is_pruning());
+        // verbs can't act on the pruning line
+        assert!(state.selected_path().is_none());
+        assert!(state.selection().is_none());
+        // enter lists the unlisted children in place
+        let mut w = std::io::BufWriter::new(std::io::stderr());
+        let res = state.open_selection_stay_in_broot(&mut w, screen, con, false, false).unwrap();
+        assert!(matches!(res, CmdResult::RefreshState { .. }));
+        state.refresh(screen, con);
+        assert!(!state.tree.lines.iter().any(TreeLine::is_pruning));
+        assert_eq!(state.tree.lines.len(), 22);
+    }
+
+    #[test]
+    fn check_search_restart_on_resize() {
+        let searcher = Searcher::new().unwrap();
+        let con = searcher.context();
+        let temp_dir = tempfile::tempdir().unwrap();
+        for i in 0..5 {
+            fs::write(temp_dir.path().join(format!("f{}", i)), "").unwrap();
+        }
+        let screen = Screen { width: 80, height: 20 };
+        let mut state = BrowserState::new(
+            temp_dir.path().to_path_buf(),
+            TreeOptions::default(),
+            screen,
+            con,
+            &Dam::unlimited(),
+        ).unwrap().unwrap();
+        state.pending_pattern = searcher.pattern("f").unwrap();
+        state.do_pending_task(screen, con, &mut Dam::unlimited());
+        assert!(state.filtered_tree.is_some());
+        assert!(state.pending_pattern.is_none());
+        // a change of width, or no change at all, doesn't restart the search
+        state.on_resize(screen, con);
+        state.on_resize(Screen { width: 40, height: 20 }, con);
+        assert!(state.pending_pattern.is_none());
+        // a change of height does
+        state.on_resize(Screen { width: 40, height: 30 }, con);
+        assert_eq!(state.pending_pattern.raw, "f");
+    }
+
+    #[test]
+    fn check_drag_staging() {
+        let searcher = Searcher::new().unwrap();
+        let con = searcher.context();
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in &["a", "b", "c", "d", "e"] {
+            fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+        let screen = Screen { width: 80, height: 20 };
+        let mut state = BrowserState::new(
+            temp_dir.path().to_path_buf(),
+            TreeOptions::default(),
+            screen,
+            con,
+            &Dam::unlimited(),
+        ).unwrap().unwrap();
+        // while dragging, the range is shown but nothing is staged
+        state.on_drag((0, 1), (0, 2)).unwrap();
+        state.on_drag((0, 1), (0, 3)).unwrap();
+        let tree = &state.tree;
+        assert_eq!(tree.drag_start, Some(1));
+        assert_eq!(tree.selection, 3);
+        assert!(!tree.is_in_drag_range(0));
+        assert!((1..=3).all(|idx| tree.is_in_drag_range(idx)));
+        assert!(!tree.is_in_drag_range(4));
+        // the release stages the range
+        let mut app_state = AppState::default();
+        let panel_skin = PanelSkin::new(StyleMap::no_term());
+        let areas = Areas::create(&mut Vec::new(), 0, screen, false, &Layout::default()).unwrap();
+        let app_cmd_context = AppCmdContext {
+            other_path: None,
+            panel_skin: &panel_skin,
+            preview_panel: None,
+            stage_panel: None,
+            dry_run_panel: None,
+            confirmed: false,
+            screen,
+            con,
+        };
+        let cc = CmdContext {
+            cmd: &Command::None,
+            app: &app_cmd_context,
+            panel: PanelCmdContext {
+                areas: &areas,
+                purpose: PanelPurpose::None,
+            },
+        };
+        let res = state.on_drag_end((0, 1), (0, 3), &mut app_state, &cc).unwrap();
+        assert!(matches!(res, CmdResult::NewPanel { .. }));
+        assert!(state.tree.drag_start.is_none());
+        let expected: Vec<PathBuf> = ["a", "b", "c"].iter()
+            .map(|name| temp_dir.path().join(name))
+            .collect();
+        assert_eq!(app_state.stage.paths(), &expected[..]);
+    }
+}
@@ -0,0 +1,531 @@
+use {
+    super::CleanableScan,
+    crate::{
+        app::*,
+        browser::BrowserState,
+        command::*,
+        display::*,
+        errors::ProgramError,
+        file_sum::{self, FileSum},
+        pattern::*,
+        task_sync::Dam,
+        tree::TreeOptions,
+        verb::*,
+    },
+    crossterm::{
+        cursor,
+        QueueableCommand,
+    },
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+/// a directory which may be deleted to reclaim space
+struct CleanableDir {
+    path: PathBuf,
+    /// path relative to the root of the scan, for display and filtering
+    subpath: String,
+    /// None until computed
+    size: Option<u64>,
+}
+
+/// an application state listing the build artifacts and caches
+/// found under a directory, biggest first, so that they can be
+/// staged then deleted together
+pub struct CleanableState {
+    root: PathBuf,
+    /// the search of the cleanable directories, until it's done
+    scan: Option<CleanableScan>,
+    dirs: Vec<CleanableDir>,
+    /// indexes in dirs of the ones matching the pattern
+    displayed: Vec<usize>,
+    /// index in displayed
+    selection_idx: usize,
+    scroll: usize,
+    page_height: usize,
+    pattern: Pattern,
+    /// whether the deletion of the staged directories was asked
+    /// and waits for a confirmation
+    tree_options: TreeOptions,
+    mode: Mode,
+}
+
+impl CleanableState {
+    pub fn new(
+        root: &Path,
+        tree_options: TreeOptions,
+        con: &AppContext,
+    ) -> CleanableState {
+        CleanableState {
+            root: root.to_path_buf(),
+            scan: Some(CleanableScan::new(root)),
+            displayed: Vec::new(),
+            dirs: Vec::new(),
+            selection_idx: 0,
+            scroll: 0,
+            page_height: 0,
+            pattern: Pattern::None,
+            tree_options,
+            mode: initial_mode(con),
+        }
+    }
+
+    pub fn try_scroll(
+        &mut self,
+        cmd: ScrollCommand,
+    ) -> bool {
+        let old_scroll = self.scroll;
+        self.scroll = cmd.apply(self.scroll, self.displayed.len(), self.page_height);
+        self.scroll != old_scroll
+    }
+
+    fn move_line(
+        &mut self,
+        internal_exec: &InternalExecution,
+        input_invocation: Option<&VerbInvocation>,
+        dir: i32, // -1 for up, 1 for down
+        cycle: bool,
+    ) -> CmdResult {
+        let count: i32 = get_arg(input_invocation, internal_exec, 1);
+        let dir = dir * count;
+        self.selection_idx = move_sel(self.selection_idx, self.displayed.len(), dir, cycle);
+        self.make_selection_visible();
+        CmdResult::Keep
+    }
+
+    /// change the scroll, if needed, so that the selection is visible
+    fn make_selection_visible(&mut self) {
+        if self.selection_idx < self.scroll {
+            self.scroll = self.selection_idx;
+        } else if self.page_height > 0 && self.selection_idx >= self.scroll + self.page_height {
+            self.scroll = self.selection_idx + 1 - self.page_height;
+        }
+    }
+
+    fn selected_dir(&self) -> Option<&CleanableDir> {
+        self.displayed
+            .get(self.selection_idx)
+            .map(|&idx| &self.dirs[idx])
+    }
+
+    /// filter and sort the displayed directories, biggest first,
+    /// keeping the selection on the same directory
+    fn update_displayed(&mut self) {
+        let selected = self.displayed.get(self.selection_idx).copied();
+        let mut displayed: Vec<usize> = (0..self.dirs.len())
+            .filter(|&idx| self.pattern.score_of_string(&self.dirs[idx].subpath).is_some())
+            .collect();
+        displayed.sort_by_key(|&idx| std::cmp::Reverse(self.dirs[idx].size));
+        self.displayed = displayed;
+        self.selection_idx = selected
+            .and_then(|selected| self.displayed.iter().position(|&idx| idx == selected))
+            .unwrap_or(0);
+    }
+
+    /// go on with the search of the cleanable directories, until
+    /// it's done or an event comes
+    fn advance_scan(&mut self, dam: &Dam, con: &AppContext) {
+        if let Some(scan) = &mut self.scan {
+            for path in scan.advance(dam, con) {
+                let subpath = path.strip_prefix(&self.root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .to_string();
+                self.dirs.push(CleanableDir { path, subpath, size: None });
+            }
+            if scan.is_done() {
+                self.scan = None;
+            }
+            self.update_displayed();
+        }
+    }
+
+    /// the listed directories which are staged
+    fn staged_dirs<'s>(&'s self, app_state: &'s AppState) -> impl Iterator<Item = &'s CleanableDir> {
+        self.dirs.iter().filter(move |dir| app_state.stage.contains(&dir.path))
+    }
+
+    /// delete the staged directories, remove them from the list
+    /// and from the stage (the verb asks for a confirmation before)
+    fn clean(&mut self, app_state: &mut AppState) -> CmdResult {
+        if self.staged_dirs(app_state).next().is_none() {
+            return CmdResult::error(
+                "Stage some directories first, for example all of them with `:stage_all`"
+            );
+        }
+        let staged: Vec<PathBuf> = self.staged_dirs(app_state)
+            .map(|dir| dir.path.clone())
+            .collect();
+        let mut freed = 0;
+        let mut errors = Vec::new();
+        for path in &staged {
+            let size = self.dirs.iter()
+                .find(|dir| &dir.path == path)
+                .and_then(|dir| dir.size)
+                .unwrap_or(0);
+            match fs::remove_dir_all(path) {
+                Ok(()) => {
+                    freed += size;
+                    app_state.stage.remove(path);
+                    self.dirs.retain(|dir| &dir.path != path);
+                }
+                Err(e) => {
+                    errors.push(format!("{}: {}", path.to_string_lossy(), e));
+                }
+            }
+        }
+        // the sizes of the parents of the deleted directories changed
+        file_sum::clear_cache();
+        self.selection_idx = 0;
+        self.scroll = 0;
+        self.update_displayed();
+        if errors.is_empty() {
+            CmdResult::DisplayMessage(format!(
+                "{} directories deleted, {} freed",
+                staged.len(),
+                file_size::fit_4(freed).trim(),
+            ))
+        } else {
+            CmdResult::DisplayError(format!("Deletion failed for {}", errors.join(", ")))
+        }
+    }
+}
+
+impl PanelState for CleanableState {
+
+    fn get_type(&self) -> PanelStateType {
+        PanelStateType::Cleanable
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    fn get_mode(&self) -> Mode {
+        self.mode
+    }
+
+    fn selected_path(&self) -> Option<&Path> {
+        self.selected_dir().map(|dir| dir.path.as_path())
+    }
+
+    fn results_paths(&self) -> Vec<PathBuf> {
+        self.displayed
+            .iter()
+            .map(|&idx| self.dirs[idx].path.clone())
+            .collect()
+    }
+
+    fn tree_options(&self) -> TreeOptions {
+        self.tree_options.clone()
+    }
+
+    fn with_new_options(
+        &mut self,
+        _screen: Screen,
+        change_options: &dyn Fn(&mut TreeOptions),
+        _in_new_panel: bool,
+        _con: &AppContext,
+    ) -> CmdResult {
+        change_options(&mut self.tree_options);
+        CmdResult::Keep
+    }
+
+    fn selection(&self) -> Option<Selection<'_>> {
+        self.selected_path().map(|path| Selection {
+            path,
+            stype: SelectionType::Directory,
+            is_exe: false,
+            line: 0,
+        })
+    }
+
+    fn refresh(&mut self, _screen: Screen, con: &AppContext) -> Command {
+        *self = CleanableState::new(&self.root, self.tree_options.clone(), con);
+        Command::empty()
+    }
+
+    fn on_resize(&mut self, _screen: Screen, _con: &AppContext) {
+        // nothing to recompute: the page height is updated on display
+    }
+
+    fn on_pattern(
+        &mut self,
+        pattern: InputPattern,
+        _app_state: &AppState,
+        _con: &AppContext,
+    ) -> Result<CmdResult, ProgramError> {
+        self.pattern = pattern.pattern;
+        self.update_displayed();
+        self.scroll = 0;
+        Ok(CmdResult::Keep)
+    }
+
+    fn get_pending_task(&self) -> Option<&'static str> {
+        if self.scan.is_some() {
+            Some("searching cleanable directories")
+        } else if self.dirs.iter().any(|dir| dir.size.is_none()) {
+            Some("computing sizes")
+        } else {
+            None
+        }
+    }
+
+    fn do_pending_task(
+        &mut self,
+        _screen: Screen,
+        con: &AppContext,
+        dam: &mut Dam,
+    ) {
+        if self.scan.is_some() {
+            self.advance_scan(dam, con);
+        } else if let Some(dir) = self.dirs.iter_mut().find(|dir| dir.size.is_none()) {
+            dir.size = FileSum::from_dir(&dir.path, dam, con).map(FileSum::to_size);
+            self.update_displayed();
+        }
+    }
+
+    fn no_verb_status(
+        &self,
+        _has_previous_state: bool,
+        _con: &AppContext,
+    ) -> Status {
+        if self.dirs.is_empty() && self.scan.is_none() {
+            Status::from_message("No cleanable directory found - hit *esc* to get back")
+        } else {
+            Status::from_message(
+                "Stage directories then type *:clean* to delete them, or hit *esc* to get back"
+            )
+        }
+    }
+
+    fn display(
+        &mut self,
+        w: &mut W,
+        disc: &DisplayContext,
+    ) -> Result<(), ProgramError> {
+        let area = &disc.state_area;
+        let con = &disc.con;
+        let stage = &disc.app_state.stage;
+        let page_height = (area.height as usize).max(2) - 2;
+        if page_height != self.page_height {
+            // the panel was resized
+            self.page_height = page_height;
+            self.make_selection_visible();
+        }
+        let scrollbar = area.scrollbar(self.scroll as i32, self.displayed.len() as i32);
+        let styles = &disc.panel_skin.styles;
+        let border_style = &styles.help_table_border;
+        let width = area.width as usize;
+        let mut wc_size = 5;
+        if con.show_selection_mark {
+            wc_size += 1;
+        }
+        let total: u64 = self.dirs.iter().filter_map(|dir| dir.size).sum();
+        let staged: u64 = self.dirs.iter()
+            .filter(|dir| stage.contains(&dir.path))
+            .filter_map(|dir| dir.size)
+            .sum();
+        //- titles
+        w.queue(cursor::MoveTo(area.left, area.top))?;
+        let mut cw = CropWriter::new(w, width);
+        cw.queue_g_string(&styles.default, format!("{:^width$}", "size", width = wc_size))?;
+        cw.queue_char(border_style, '│')?;
+        cw.queue_g_string(
+            &styles.default,
+            format!(
+                "{} directories under {} - {} total, {} staged",
+                self.dirs.len(),
+                self.root.to_string_lossy(),
+                file_size::fit_4(total).trim(),
+                file_size::fit_4(staged).trim(),
+            ),
+        )?;
+        cw.fill(border_style, &SPACE_FILLING)?;
+        //- horizontal line
+        w.queue(cursor::MoveTo(area.left, 1 + area.top))?;
+        let mut cw = CropWriter::new(w, width);
+        cw.queue_g_string(border_style, format!("{:─>width$}", '┼', width = wc_size + 1))?;
+        cw.fill(border_style, &BRANCH_FILLING)?;
+        //- content
+        let mut idx = self.scroll;
+        for y in 2..area.height {
+            w.queue(cursor::MoveTo(area.left, y + area.top))?;
+            let selected = self.selection_idx == idx;
+            let mut cw = CropWriter::new(w, width - 1); // -1 for scrollbar
+            let txt_style = if selected { &styles.selected_line } else { &styles.default };
+            if let Some(dir) = self.displayed.get(idx).map(|&i| &self.dirs[i]) {
+                let mut border_style = border_style.clone();
+                if let Some(bg) = txt_style.get_bg() {
+                    border_style.set_bg(bg);
+                }
+                if con.show_selection_mark {
+                    cw.queue_char(txt_style, if selected { '▶' } else { ' ' })?;
+                }
+                cw.queue_char(txt_style, if stage.contains(&dir.path) { '✓' } else { ' ' })?;
+                let size = dir.size.map_or_else(
+                    || "    ".to_string(),
+                    file_size::fit_4,
+                );
+                cw.queue_g_string(txt_style, format!("{:>4}", size))?;
+                cw.queue_char(&border_style, '│')?;
+                let mut name_style = styles.directory.clone();
+                if let Some(bg) = txt_style.get_bg() {
+                    name_style.set_bg(bg);
+                }
+                cw.queue_str(&name_style, &dir.subpath)?;
+                idx += 1;
+            }
+            cw.fill(txt_style, &SPACE_FILLING)?;
+            let scrollbar_style = if ScrollCommand::is_thumb(y, scrollbar) {
+                &styles.scrollbar_thumb
+            } else {
+                &styles.scrollbar_track
+            };
+            scrollbar_style.queue_str(w, "▐")?;
+        }
+        Ok(())
+    }
+
+    fn on_internal(
+        &mut self,
+        w: &mut W,
+        internal_exec: &InternalExecution,
+        input_invocation: Option<&VerbInvocation>,
+        trigger_type: TriggerType,
+        app_state: &mut AppState,
+        cc: &CmdContext,
+    ) -> Result<CmdResult, ProgramError> {
+        let screen = cc.app.screen;
+        let con = &cc.app.con;
+        Ok(match internal_exec.internal {
+            Internal::back => {
+                if self.pattern.is_some() {
+                    self.on_pattern(InputPattern::none(), app_state, con)?
+                } else {
+                    CmdResult::PopState
+                }
+            }
+            Internal::clean => self.clean(app_state),
+            Internal::line_down => {
+                self.move_line(internal_exec, input_invocation, 1, true)
+            }
+            Internal::line_up => {
+                self.move_line(internal_exec, input_invocation, -1, true)
+            }
+            Internal::line_down_no_cycle => {
+                self.move_line(internal_exec, input_invocation, 1, false)
+            }
+            Internal::line_up_no_cycle => {
+                self.move_line(internal_exec, input_invocation, -1, false)
+            }
+            Internal::open_stay | Internal::focus => {
+                match self.selected_path() {
+                    Some(path) => {
+                        let in_new_panel = input_invocation
+                            .map(|inv| inv.bang)
+                            .unwrap_or(internal_exec.bang);
+                        CmdResult::from_optional_state(
+                            BrowserState::new(
+                                path.to_path_buf(),
+                                self.tree_options(),
+                                screen,
+                                con,
+                                &Dam::unlimited(),
+                            ),
+                            in_new_panel,
+                        )
+                    }
+                    None => CmdResult::error("no selected directory"),
+                }
+            }
+            Internal::scroll_down => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.try_scroll(ScrollCommand::Lines(count));
+                CmdResult::Keep
+            }
+            Internal::scroll_up => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.try_scroll(ScrollCommand::Lines(-count));
+                CmdResult::Keep
+            }
+            Internal::page_down => {
+                self.try_scroll(ScrollCommand::Pages(1));
+                CmdResult::Keep
+            }
+            Internal::page_up => {
+                self.try_scroll(ScrollCommand::Pages(-1));
+                CmdResult::Keep
+            }
+            _ => self.on_internal_generic(
+                w,
+                internal_exec,
+                input_invocation,
+                trigger_type,
+                app_state,
+                cc,
+            )?,
+        })
+    }
+
+    fn on_click(
+        &mut self,
+        _x: u16,
+        y: u16,
+        _screen: Screen,
+        _con: &AppContext,
+    ) -> Result<CmdResult, ProgramError> {
+        if y >= 2 {
+            let y = y as usize - 2 + self.scroll;
+            if y < self.displayed.len() {
+                self.selection_idx = y;
+            }
+        }
+        Ok(CmdResult::Keep)
+    }
+}
+
+#[cfg(test)]
+mod cleanable_state_test {
+
+    use {
+        super::*,
+        crate::search::Searcher,
+    };
+
+    #[test]
+    fn check_clean() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        for dir in &["a/target/debug", "b/target", "b/node_modules/lib"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join("a/target/debug/app"), "binary").unwrap();
+        let searcher = Searcher::new().unwrap();
+        let con = searcher.context();
+        let mut state = CleanableState::new(root, TreeOptions::default(), con);
+        let dam = Dam::unlimited();
+        state.advance_scan(&dam, con);
+        assert!(state.scan.is_none());
+        assert_eq!(state.dirs.len(), 3);
+        assert_eq!(state.displayed.len(), 3);
+        let mut app_state = AppState::default();
+        // nothing is deleted without staged directories
+        assert!(matches!(state.clean(&mut app_state), CmdResult::DisplayError(_)));
+        assert_eq!(state.dirs.len(), 3);
+        app_state.stage.add(root.join("a/target"));
+        app_state.stage.add(root.join("b/node_modules"));
+        // the staged paths which aren't listed aren't deleted
+        app_state.stage.add(root.join("b"));
+        state.clean(&mut app_state);
+        assert!(!root.join("a/target").exists());
+        assert!(!root.join("b/node_modules").exists());
+        assert!(root.join("b/target").exists());
+        assert!(root.join("a").exists());
+        assert_eq!(state.dirs.len(), 1);
+        assert_eq!(state.dirs[0].path, root.join("b/target"));
+        assert_eq!(app_state.stage.paths(), &[root.join("b")]);
+    }
+}
@@ -0,0 +1,126 @@
+mod cleanable_state;
+
+pub use cleanable_state::CleanableState;
+
+use {
+    crate::{
+        app::AppContext,
+        path::{SpecialHandling, SpecialPathList},
+        task_sync::Dam,
+    },
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+/// names of the directories which are usually build artifacts or
+/// caches, used when the configuration doesn't define `cleanable_dirs`.
+///
+/// Names like `build` or `dist`, which are also given to directories
+/// of sources or of committed files, aren't there
+pub static DEFAULT_CLEANABLE_DIRS: &[&str] = &[
+    "target",
+    "node_modules",
+    ".venv",
+    "venv",
+    "__pycache__",
+    ".pytest_cache",
+    ".mypy_cache",
+    ".tox",
+    ".gradle",
+    ".next",
+];
+
+/// an interruptible search of the cleanable directories under
+/// a root, which enters neither them nor the `.git` directories
+pub struct CleanableScan {
+    /// the directories still to read
+    dirs: Vec<PathBuf>,
+}
+
+impl CleanableScan {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            dirs: vec![root.to_path_buf()],
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.dirs.is_empty()
+    }
+
+    /// go on with the scan until it's done or an event comes, and
+    /// return the cleanable directories found meanwhile
+    pub fn advance(&mut self, dam: &Dam, con: &AppContext) -> Vec<PathBuf> {
+        let mut found = Vec::new();
+        while !dam.has_event() {
+            let dir = match self.dirs.pop() {
+                Some(dir) => dir,
+                None => break,
+            };
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for e in entries.flatten() {
+                if !matches!(e.file_type(), Ok(ft) if ft.is_dir()) {
+                    continue;
+                }
+                let name = e.file_name();
+                let name = name.to_string_lossy();
+                if name == ".git" {
+                    continue;
+                }
+                let path = e.path();
+                match con.special_paths.as_slice().find(&path) {
+                    SpecialHandling::Hide | SpecialHandling::NoEnter => continue,
+                    _ => {}
+                }
+                if con.cleanable_dirs.iter().any(|n| n == &name) {
+                    found.push(path);
+                } else {
+                    self.dirs.push(path);
+                }
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod cleanable_test {
+
+    use {
+        super::*,
+        crate::search::Searcher,
+    };
+
+    #[test]
+    fn check_cleanable_scan() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        for dir in &[
+            "rust/target/debug",
+            "rust/src",
+            "web/node_modules/lib/node_modules",
+            "web/build",
+            "py/pkg/__pycache__",
+            ".git/target",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let searcher = Searcher::new().unwrap();
+        let mut scan = CleanableScan::new(root);
+        assert!(!scan.is_done());
+        let mut found = scan.advance(&Dam::unlimited(), searcher.context());
+        assert!(scan.is_done());
+        found.sort();
+        // the nested node_modules isn't listed, as its parent is
+        assert_eq!(found, vec![
+            root.join("py/pkg/__pycache__"),
+            root.join("rust/target"),
+            root.join("web/node_modules"),
+        ]);
+    }
+}
@@ -1,8 +1,13 @@
 
 use {
     crate::{
+        conf::Conf,
+        errors::ConfError,
+        pick::PickConfig,
+        print::ExportConfig,
         tree::TreeOptions,
     },
+    clap::ArgMatches,
     std::{
         path::PathBuf,
     },
@@ -11,15 +16,43 @@ use {
 
 /// the parsed program launch arguments which are kept for the
 /// life of the program
+#[derive(Clone)]
 pub struct AppLaunchArgs {
     pub root: PathBuf,                    // what should be the initial root
     pub file_export_path: Option<String>, // where to write the produced path (if required with --out)
     pub cmd_export_path: Option<String>,  // where to write the produced command (if required with --outcmd)
     pub tree_options: TreeOptions,        // initial tree options
+    pub flags: ArgMatches<'static>,       // the launch arguments, kept to build the tree options again
     pub commands: Option<String>,         // commands passed as cli argument, still unparsed
     pub height: Option<u16>,              // an optional height to replace the screen's one
     pub no_style: bool,                   // whether to remove all styles (including colors)
+    pub export_config: ExportConfig,      // how the tree is written by `:print_tree`
+    pub pick: Option<PickConfig>,         // settings of the pick mode, if broot is used as a picker
+    pub dry_run: bool,                    // whether the verbs modifying files are only listed
+    pub must_show_selection_mark: bool,   // whether the selection must be marked (as it has no style)
 
     #[cfg(feature = "client-server")]
     pub listen: Option<String>,
 }
+
+impl AppLaunchArgs {
+    /// build the initial tree options from the default_flags
+    /// found in the config file(s) (if any) then overriden
+    /// by the launch arguments
+    pub fn make_tree_options(&self, config: &Conf) -> Result<TreeOptions, ConfError> {
+        let mut tree_options = TreeOptions::default();
+        tree_options.apply_config(config)?;
+        tree_options.apply_launch_args(&self.flags)?;
+        // when a list of paths is given, the tree contains only them,
+        // including the hidden and gitignored ones
+        if let Some(path_list) = &self.tree_options.path_list {
+            tree_options.path_list = Some(path_list.clone());
+            tree_options.show_hidden = true;
+            tree_options.respect_git_ignore = false;
+        }
+        if self.must_show_selection_mark {
+            tree_options.show_selection_mark = true;
+        }
+        Ok(tree_options)
+    }
+}
@@ -0,0 +1,103 @@
+//! implementation of `--bench`: the tree is built several times,
+//! with an optional search, and the durations of the steps of
+//! the build are printed, so that performance problems can be
+//! reported with actual numbers
+
+use {
+    crate::{
+        app::AppContext,
+        browser::BrowserState,
+        command::CommandParts,
+        display::Screen,
+        errors::ProgramError,
+        pattern::InputPattern,
+        task_sync::Dam,
+        tree_build::{BuildReport, Timing, TreeBuilder},
+    },
+    std::{
+        sync::{atomic::Ordering, Arc},
+        time::Duration,
+    },
+};
+
+/// number of builds
+const RUNS: u32 = 5;
+
+/// extraction of a timing from a report
+type TimingGetter = fn(&BuildReport) -> &Timing;
+
+/// the columns of the printed table: header and extraction of the timing
+static COLUMNS: &[(&str, TimingGetter)] = &[
+    ("total", |r| &r.total),
+    ("gathering", |r| &r.gathering),
+    ("dir reading", |r| &r.dir_reading),
+    ("scoring", |r| &r.scoring),
+    ("git ignore", |r| &r.git_ignore),
+    ("trimming", |r| &r.trimming),
+    ("making lines", |r| &r.making_lines),
+];
+
+fn print_row(label: &str, durations: &[Duration], counts: &str) {
+    print!("{:>6}", label);
+    for d in durations {
+        print!(" {:>12}", format!("{:.2?}", d));
+    }
+    if counts.is_empty() {
+        println!();
+    } else {
+        println!("  {}", counts);
+    }
+}
+
+/// build the tree of the root of the launch arguments, RUNS times,
+/// and print the timings
+pub fn run(
+    raw_pattern: Option<&str>,
+    con: &AppContext,
+) -> Result<(), ProgramError> {
+    let root = &con.launch_args.root;
+    let mut options = con.launch_args.tree_options.clone();
+    if let Some(raw_pattern) = raw_pattern {
+        let parts = CommandParts::from(raw_pattern.to_string());
+        options.pattern = InputPattern::new(raw_pattern.to_string(), &parts.pattern, con)?;
+    }
+    let page_height = BrowserState::page_height(Screen::new(con)?).max(1) as usize;
+    println!(
+        "broot {} - {} builds of {:?} with pattern {:?}",
+        env!("CARGO_PKG_VERSION"),
+        RUNS,
+        root,
+        raw_pattern.unwrap_or(""),
+    );
+    println!("(durations of steps run in parallel are summed over all threads)");
+    print!("{:>6}", "run");
+    for (header, _) in COLUMNS {
+        print!(" {:>12}", header);
+    }
+    println!("  dirs/entries read, lines");
+    let mut sums = vec![Duration::default(); COLUMNS.len()];
+    for run in 1..=RUNS {
+        let report = Arc::new(BuildReport::default());
+        let tree = TreeBuilder::from(root.clone(), options.clone(), page_height, con)?
+            .with_report(Arc::clone(&report))
+            .build(false, &Dam::unlimited())
+            .unwrap(); // can't be interrupted with an unlimited dam
+        let durations: Vec<Duration> = COLUMNS.iter()
+            .map(|(_, timing)| timing(&report).get())
+            .collect();
+        for (sum, d) in sums.iter_mut().zip(&durations) {
+            *sum += *d;
+        }
+        let counts = format!(
+            "{}/{}, {}{}",
+            report.nb_dirs_read.load(Ordering::Relaxed),
+            report.nb_entries.load(Ordering::Relaxed),
+            tree.lines.len(),
+            if report.from_index.load(Ordering::Relaxed) { " (path index)" } else { "" },
+        );
+        print_row(&run.to_string(), &durations, &counts);
+    }
+    let means: Vec<Duration> = sums.iter().map(|sum| *sum / RUNS).collect();
+    print_row("mean", &means, "");
+    Ok(())
+}
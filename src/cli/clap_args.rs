@@ -117,6 +117,19 @@ pub fn clap_app() -> clap::App<'static, 'static> {
                 .long("sort-by-date")
                 .help("Sort by date (only show one level of the tree)"),
         )
+        .arg(
+            clap::Arg::with_name("ignored-sizes")
+                .long("ignored-sizes")
+                .help("Show sizes, with the gitignored part of the directories"),
+        )
+        .arg(
+            clap::Arg::with_name("date-kind")
+                .long("date-kind")
+                .takes_value(true)
+                .value_name("kind")
+                .possible_values(&["modified", "changed", "created"])
+                .help("Which date is shown and used for sorting"),
+        )
         .arg(
             clap::Arg::with_name("sort-by-size")
                 .long("sort-by-size")
@@ -145,7 +158,56 @@ pub fn clap_app() -> clap::App<'static, 'static> {
                 .long("no-trim-root")
                 .help("Don't trim the root level, show a scrollbar"),
         )
+        .arg(
+            clap::Arg::with_name("search-time-budget")
+                .long("search-time-budget")
+                .takes_value(true)
+                .value_name("ms")
+                .help("How long a search goes on looking for better matches once the screen is filled"),
+        )
+        .arg(
+            clap::Arg::with_name("search-result-cap")
+                .long("search-result-cap")
+                .takes_value(true)
+                .value_name("count")
+                .help("Number of matches after which a search stops"),
+        )
         // other options
+        .arg(
+            clap::Arg::with_name("bench")
+                .long("bench")
+                .takes_value(true)
+                .value_name("path [pattern]")
+                .min_values(1)
+                .max_values(2)
+                .help("Build the tree of the path several times, searching the optional pattern, and print timings"),
+        )
+        .arg(
+            clap::Arg::with_name("paths-from")
+                .long("paths-from")
+                .takes_value(true)
+                .value_name("file")
+                .help("Display only the paths read from the file (or stdin for `-`), newline or NUL separated"),
+        )
+        .arg(
+            clap::Arg::with_name("pick")
+                .long("pick")
+                .takes_value(true)
+                .min_values(0)
+                .require_equals(true)
+                .value_name("n")
+                .help("Use broot as a picker: the chosen paths (at most n) are printed on exit, and verbs modifying files are disabled"),
+        )
+        .arg(
+            clap::Arg::with_name("print0")
+                .long("print0")
+                .help("In pick mode, end the printed paths with NUL chars instead of quoting them on separate lines"),
+        )
+        .arg(
+            clap::Arg::with_name("dry-run")
+                .long("dry-run")
+                .help("Don't execute the verbs modifying files, but list what they would do"),
+        )
         .arg(
             clap::Arg::with_name("cmd-export-path")
                 .long("outcmd")
@@ -193,9 +255,22 @@ pub fn clap_app() -> clap::App<'static, 'static> {
         )
         .arg(
             clap::Arg::with_name("no-style")
-                .hidden(true) // we're deprecating this in favor of --color
                 .long("no-style")
-                .help("Whether to remove all style and colors from exported tree"),
+                .help("Remove all styles and colors from the exported tree, and use only ASCII chars"),
+        )
+        .arg(
+            clap::Arg::with_name("export-depth")
+                .long("export-depth")
+                .takes_value(true)
+                .value_name("depth")
+                .help("Max depth of the tree written by :print_tree"),
+        )
+        .arg(
+            clap::Arg::with_name("export-cols")
+                .long("export-cols")
+                .takes_value(true)
+                .value_name("cols")
+                .help("Columns of the tree written by :print_tree, among git, size, date, permission and count (eg `sdg` or `size,date`)"),
         )
         .arg(
             clap::Arg::with_name("set-install-state")
@@ -3,7 +3,9 @@
 
 pub mod clap_args;
 mod app_launch_args;
+mod bench;
 mod install_launch_args;
+mod paths_from;
 
 pub use {
     app_launch_args::*,
@@ -17,6 +19,8 @@ use {
         display,
         errors::{ProgramError, TreeBuildError},
         launchable::Launchable,
+        pick::PickConfig,
+        print::ExportConfig,
         shell_install::ShellInstall,
         tree::TreeOptions,
         verb::VerbStore,
@@ -34,6 +38,7 @@ use {
         env,
         io::{self, Write, stdout},
         path::{Path, PathBuf},
+        sync::Arc,
     },
 };
 
@@ -53,7 +58,9 @@ fn canonicalize_root(root: &Path) -> io::Result<PathBuf> {
 
 fn get_root_path(cli_args: &ArgMatches<'_>) -> Result<PathBuf, ProgramError> {
     let mut root = cli_args
-        .value_of("ROOT")
+        .values_of("bench")
+        .and_then(|mut bench_args| bench_args.next())
+        .or_else(|| cli_args.value_of("ROOT"))
         .map_or(env::current_dir()?, PathBuf::from);
     if !root.exists() {
         return Err(TreeBuildError::FileNotFound {
@@ -109,9 +116,13 @@ pub fn run() -> Result<Option<Launchable>, ProgramError> {
         .value_of("conf")
         .map(|s| s.split(';').map(PathBuf::from).collect());
 
-    // if we don't run on a specific config file, we check the
-    // configuration
-    if specific_conf.is_none() && install_args.install != Some(false) {
+    // if we don't run on a specific config file (or just benchmark,
+    // or read paths which may come from stdin), we check the configuration
+    if specific_conf.is_none()
+        && install_args.install != Some(false)
+        && !cli_matches.is_present("bench")
+        && !cli_matches.is_present("paths-from")
+    {
         let mut shell_install = ShellInstall::new(install_args.install == Some(true));
         shell_install.check()?;
         if shell_install.should_quit {
@@ -122,24 +133,11 @@ pub fn run() -> Result<Option<Launchable>, ProgramError> {
     // read the configuration file(s): either the standard one
     // or the ones required by the launch args
     let mut config = match &specific_conf {
-        Some(conf_paths) => {
-            let mut conf = Conf::default();
-            for path in conf_paths {
-                conf.read_file(path.to_path_buf())?;
-            }
-            conf
-        }
+        Some(conf_paths) => Conf::from_files(conf_paths)?,
         _ => time!(Conf::from_default_location())?,
     };
     debug!("config: {:#?}", &config);
 
-    // tree options are built from the default_flags
-    // found in the config file(s) (if any) then overriden
-    // by the cli args
-    let mut tree_options = TreeOptions::default();
-    tree_options.apply_config(&config)?;
-    tree_options.apply_launch_args(&cli_matches);
-
     // verb store is completed from the config file(s)
     let mut verb_store = VerbStore::default();
     verb_store.init(&mut config)?;
@@ -160,8 +158,20 @@ pub fn run() -> Result<Option<Launchable>, ProgramError> {
         }
     };
     let height = cli_matches.value_of("height").and_then(|s| s.parse().ok());
+    let export_config = ExportConfig::from_launch_args(&cli_matches)?;
+    let pick = PickConfig::from_launch_args(&cli_matches)?;
 
-    let root = get_root_path(&cli_matches)?;
+    // the tree options are completed once the launch args are known
+    let mut tree_options = TreeOptions::default();
+    let root = match cli_matches.value_of("paths-from") {
+        Some(source) => {
+            let path_list = paths_from::path_list(source)?;
+            let root = path_list.root.clone();
+            tree_options.path_list = Some(Arc::new(path_list));
+            root
+        }
+        None => get_root_path(&cli_matches)?,
+    };
 
     #[cfg(feature = "client-server")]
     if let Some(server_name) = cli_matches.value_of("send") {
@@ -188,18 +198,29 @@ pub fn run() -> Result<Option<Launchable>, ProgramError> {
         file_export_path,
         cmd_export_path,
         tree_options,
+        flags: cli_matches.clone(),
         commands,
         height,
         no_style,
+        export_config,
+        pick,
+        dry_run: cli_matches.is_present("dry-run"),
+        must_show_selection_mark,
 
         #[cfg(feature = "client-server")]
         listen: cli_matches.value_of("listen").map(str::to_string),
     };
-    if must_show_selection_mark {
-        launch_args.tree_options.show_selection_mark = true;
-    }
+    launch_args.tree_options = launch_args.make_tree_options(&config)?;
 
     let context = AppContext::from(launch_args, verb_store, &config)?;
+
+    if let Some(bench_args) = cli_matches.values_of("bench") {
+        // the first value is the path, already used as root
+        let pattern = bench_args.into_iter().nth(1);
+        bench::run(pattern, &context)?;
+        return Ok(None);
+    }
+
     let mut w = display::writer();
     let app = App::new(&context)?;
     w.queue(EnterAlternateScreen)?;
@@ -208,7 +229,7 @@ pub fn run() -> Result<Option<Launchable>, ProgramError> {
     if capture_mouse {
         w.queue(EnableMouseCapture)?;
     }
-    let r = app.run(&mut w, &context, &config);
+    let r = app.run(&mut w, context, &config);
     if capture_mouse {
         w.queue(DisableMouseCapture)?;
     }
@@ -0,0 +1,55 @@
+//! implementation of `--paths-from`: the paths read from a file
+//! or from stdin are displayed as a virtual tree containing only them
+
+use {
+    crate::{
+        errors::ProgramError,
+        path,
+        path_index::PathIndex,
+    },
+    std::{
+        env,
+        fs,
+        io::{self, Read},
+        path::PathBuf,
+    },
+};
+
+/// read the paths, NUL or newline separated, from the given
+/// file or from stdin when the source is "-"
+fn read_paths(source: &str) -> io::Result<Vec<PathBuf>> {
+    let bytes = if source == "-" {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        bytes
+    } else {
+        fs::read(source)?
+    };
+    // NUL separated lists (eg from `fd -0`) may contain newlines in paths
+    let separator = if bytes.contains(&0) { 0 } else { b'\n' };
+    let cwd = env::current_dir()?;
+    Ok(bytes
+        .split(|&b| b == separator)
+        .map(|s| String::from_utf8_lossy(s).trim_end_matches('\r').to_string())
+        .filter(|s| !s.is_empty())
+        .map(|s| path::normalize_path(cwd.join(s)))
+        .collect())
+}
+
+/// read the paths and build the virtual index of the tree, whose
+/// root is the deepest directory containing them all
+pub fn path_list(source: &str) -> Result<PathIndex, ProgramError> {
+    let paths: Vec<PathBuf> = read_paths(source)?
+        .into_iter()
+        .filter(|path| fs::symlink_metadata(path).is_ok())
+        .collect();
+    if paths.is_empty() {
+        return Err(ProgramError::EmptyPathList);
+    }
+    let mut root = path::longest_common_ancestor(&paths);
+    if !root.is_dir() {
+        root = root.parent().map(PathBuf::from).unwrap_or(root);
+    }
+    info!("{} paths read, root: {:?}", paths.len(), &root);
+    Ok(PathIndex::from_paths(root, &paths))
+}
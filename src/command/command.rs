@@ -51,6 +51,22 @@ pub enum Command {
     /// a mouse double-click
     /// Always come after a simple click at same position
     DoubleClick(u16, u16),
+
+    /// a mouse click with shift or control, ending a range
+    /// of lines which starts at the selection
+    RangeClick(u16, u16),
+
+    /// a move of the mouse with its left button pressed
+    Drag {
+        start: (u16, u16),
+        pos: (u16, u16),
+    },
+
+    /// the release of the left button ending a drag
+    DragEnd {
+        start: (u16, u16),
+        pos: (u16, u16),
+    },
 }
 
 impl Command {
@@ -90,6 +106,20 @@ impl Command {
         matches!(self, Self::VerbInvocate(_))
     }
 
+    /// return the trimmed arguments of the verb invocation
+    /// of the command, if any
+    pub fn invocation_args(&self) -> Option<&str> {
+        match self {
+            Self::VerbInvocate(invocation) => invocation.args.as_deref(),
+            Self::Internal { input_invocation, .. } | Self::VerbTrigger { input_invocation, .. } => {
+                input_invocation.as_ref().and_then(|inv| inv.args.as_deref())
+            }
+            _ => None,
+        }
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    }
+
     /// create a command from a raw input.
     ///
     /// `finished` makes the command an executed form,
@@ -6,6 +6,7 @@ mod sequence;
 mod sel;
 mod scroll;
 mod trigger_type;
+mod wheel;
 
 pub use {
     command::Command,
@@ -16,4 +17,5 @@ pub use {
     sel::move_sel,
     scroll::ScrollCommand,
     trigger_type::TriggerType,
+    wheel::WheelBehavior,
 };
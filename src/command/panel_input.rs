@@ -4,24 +4,47 @@ use {
         app::*,
         display::W,
         errors::ProgramError,
+        event::Event,
         keys,
         skin::PanelSkin,
-        verb::{Internal, Verb, VerbExecution},
+        verb::{Internal, Verb, VerbExecution, VerbInvocation},
     },
     crossterm::{
         cursor,
-        event::KeyEvent,
+        event::{KeyEvent, KeyModifiers},
         queue,
     },
-    termimad::{Area, Event, InputField},
+    std::time::{Duration, Instant},
+    termimad::{Area, InputField},
 };
 
+/// the delay after which the keys of an unfinished chord
+/// (a sequence of keys like "g g") are forgotten
+static CHORD_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// the result of a key in the chord state machine
+enum ChordStep {
+    /// the key completes the chord of the verb at this index
+    Complete(usize),
+    /// the key starts or continues a chord
+    Pending,
+    /// the key completes a register command, like `"a y`
+    Register {
+        internal: Internal,
+        register: char,
+    },
+    /// the key isn't part of a chord
+    None,
+}
+
 /// wrap the input of a panel,
 /// receive events and make commands
 pub struct PanelInput {
     pub input_field: InputField,
     tab_cycle_count: usize,
     input_before_cycle: Option<String>,
+    pending_keys: Vec<KeyEvent>, // the start of a chord
+    last_key_time: Instant,
 }
 
 impl PanelInput {
@@ -31,6 +54,18 @@ impl PanelInput {
             input_field: InputField::new(area),
             tab_cycle_count: 0,
             input_before_cycle: None,
+            pending_keys: Vec::new(),
+            last_key_time: Instant::now(),
+        }
+    }
+
+    /// return the description of the keys of the chord being
+    /// typed, if any
+    pub fn pending_keys_desc(&self) -> Option<String> {
+        if self.pending_keys.is_empty() || self.last_key_time.elapsed() > CHORD_TIMEOUT {
+            None
+        } else {
+            Some(keys::key_sequence_desc(&self.pending_keys))
         }
     }
 
@@ -124,6 +159,99 @@ impl PanelInput {
         }
     }
 
+    /// advance the chord state machine with the key.
+    ///
+    /// When the key can't continue the pending chord, the chord is
+    /// forgotten and the key is checked as a possible chord start.
+    fn chord_step(
+        &mut self,
+        key: KeyEvent,
+        con: &AppContext,
+        mode: Mode,
+    ) -> ChordStep {
+        let now = Instant::now();
+        if now.duration_since(self.last_key_time) > CHORD_TIMEOUT {
+            self.pending_keys.clear();
+        }
+        self.last_key_time = now;
+        let mut sequence = std::mem::take(&mut self.pending_keys);
+        if sequence.is_empty() && !keys::is_key_allowed_in_mode(key, mode) {
+            return ChordStep::None;
+        }
+        sequence.push(key);
+        if mode == Mode::Command && sequence[0] == keys::DOUBLE_QUOTE {
+            // register commands: `"a y` yanks the selected path in
+            // register a, `"a p` pastes it into the input
+            let register = sequence.get(1).and_then(|&k| keys::as_letter(k));
+            match (sequence.len(), register) {
+                (1, _) | (2, Some(_)) => {
+                    self.pending_keys = sequence;
+                    return ChordStep::Pending;
+                }
+                (3, Some(register)) => {
+                    match keys::as_letter(key) {
+                        Some('y') => return ChordStep::Register { internal: Internal::register_yank, register },
+                        Some('p') => return ChordStep::Register { internal: Internal::register_paste, register },
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+            return ChordStep::None;
+        }
+        loop {
+            let mut is_prefix = false;
+            for (index, verb) in con.verb_store.verbs.iter().enumerate() {
+                for chord in &verb.chords {
+                    if chord.starts_with(&sequence) {
+                        if chord.len() == sequence.len() {
+                            return ChordStep::Complete(index);
+                        }
+                        is_prefix = true;
+                    }
+                }
+            }
+            if is_prefix {
+                self.pending_keys = sequence;
+                return ChordStep::Pending;
+            }
+            if sequence.len() == 1 || !keys::is_key_allowed_in_mode(key, mode) {
+                return ChordStep::None;
+            }
+            // the key may be the start of another chord
+            sequence = vec![key];
+        }
+    }
+
+    /// return the command triggering the verb, when it's allowed,
+    /// or applies it to the input when it's an input related verb
+    fn verb_command(
+        &mut self,
+        index: usize,
+        key: KeyEvent,
+        parts: &CommandParts,
+        con: &AppContext,
+        stype: Option<SelectionType>,
+        mode: Mode,
+    ) -> Option<Command> {
+        let verb = &con.verb_store.verbs[index];
+        if self.handle_input_related_verb(verb, con) {
+            return Some(Command::from_raw(self.input_field.get_content(), false));
+        }
+        if verb.selection_condition.is_respected_by(stype) {
+            if mode != Mode::Input && verb.is_internal(Internal::mode_input) {
+                self.enter_input_mode_with_key(key, parts);
+            }
+            Some(Command::VerbTrigger {
+                index,
+                input_invocation: parts.verb_invocation.clone(),
+            })
+        } else {
+            debug!("verb not allowed on current selection");
+            None
+        }
+    }
+
     /// when a key is used to enter input mode, we don't always
     /// consume it. Sometimes it should be consumed, sometimes it
     /// should be added to the input
@@ -156,9 +284,11 @@ impl PanelInput {
         mode: Mode,
     ) -> Command {
         match event {
-            Event::Click(x, y, ..) => {
-                return if self.input_field.apply_event(&event) {
+            Event::Click(x, y, modifiers) => {
+                return if self.input_field.apply_event(&termimad::Event::Click(x, y, modifiers)) {
                     Command::empty()
+                } else if modifiers.intersects(KeyModifiers::SHIFT | KeyModifiers::CONTROL) {
+                    Command::RangeClick(x, y)
                 } else {
                     Command::Click(x, y)
                 };
@@ -166,6 +296,12 @@ impl PanelInput {
             Event::DoubleClick(x, y) => {
                 return Command::DoubleClick(x, y);
             }
+            Event::Drag { start, pos } => {
+                return Command::Drag { start, pos };
+            }
+            Event::DragEnd { start, pos } => {
+                return Command::DragEnd { start, pos };
+            }
             Event::Key(key) => {
                 // value of raw and parts before any key related change
                 let raw = self.input_field.get_content();
@@ -174,6 +310,12 @@ impl PanelInput {
                 // we first handle the cases that MUST absolutely
                 // not be overriden by configuration
 
+                if key == keys::ESC && self.pending_keys_desc().is_some() {
+                    // we cancel the chord
+                    self.pending_keys.clear();
+                    return Command::None;
+                }
+
                 if key == keys::ESC {
                     // tab cycling
                     self.tab_cycle_count = 0;
@@ -261,25 +403,36 @@ impl PanelInput {
                     };
                 }
 
+                // chords (sequences of keys) have precedence over
+                // the single key triggers
+                match self.chord_step(key, con, mode) {
+                    ChordStep::Complete(index) => {
+                        return self
+                            .verb_command(index, key, &parts, con, sel_info.common_stype(), mode)
+                            .unwrap_or(Command::None);
+                    }
+                    ChordStep::Pending => {
+                        return Command::None;
+                    }
+                    ChordStep::Register { internal, register } => {
+                        return Command::Internal {
+                            internal,
+                            input_invocation: Some(VerbInvocation::new(
+                                internal.name().to_string(),
+                                Some(register.to_string()),
+                                false,
+                            )),
+                        };
+                    }
+                    ChordStep::None => {}
+                }
+
                 // we now check if the key is the trigger key of one of the verbs
                 if keys::is_key_allowed_in_mode(key, mode) {
                     for (index, verb) in con.verb_store.verbs.iter().enumerate() {
-                        for verb_key in &verb.keys {
-                            if *verb_key == key {
-                                if self.handle_input_related_verb(verb, con) {
-                                    return Command::from_raw(self.input_field.get_content(), false);
-                                }
-                                if verb.selection_condition.is_respected_by(sel_info.common_stype()) {
-                                    if mode != Mode::Input && verb.is_internal(Internal::mode_input) {
-                                        self.enter_input_mode_with_key(key, &parts);
-                                    }
-                                    return Command::VerbTrigger {
-                                        index,
-                                        input_invocation: parts.verb_invocation,
-                                    };
-                                } else {
-                                    debug!("verb not allowed on current selection");
-                                }
+                        if verb.keys.contains(&key) {
+                            if let Some(command) = self.verb_command(index, key, &parts, con, sel_info.common_stype(), mode) {
+                                return command;
                             }
                         }
                     }
@@ -302,16 +455,17 @@ impl PanelInput {
 
                 // input field management
                 if mode == Mode::Input {
-                    if self.input_field.apply_event(&event) {
+                    if self.input_field.apply_event(&termimad::Event::Key(key)) {
                         return Command::from_raw(self.input_field.get_content(), false);
                     }
                 }
             }
             Event::Wheel(lines_count) => {
-                let internal = if lines_count > 0 {
-                    Internal::line_down_no_cycle
-                } else {
-                    Internal::line_up_no_cycle
+                let internal = match (con.mouse_wheel, lines_count > 0) {
+                    (WheelBehavior::MoveSelection, true) => Internal::line_down_no_cycle,
+                    (WheelBehavior::MoveSelection, false) => Internal::line_up_no_cycle,
+                    (WheelBehavior::Scroll, true) => Internal::scroll_down,
+                    (WheelBehavior::Scroll, false) => Internal::scroll_up,
                 };
                 return Command::Internal {
                     internal,
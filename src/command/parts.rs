@@ -29,6 +29,7 @@ impl CommandParts {
     pub fn from(mut raw: String) -> Self {
         let mut invocation_start_pos: Option<usize> = None;
         let mut escaping = false;
+        let mut unbounded = false;
         let mut pt = BeTree::new();
         for (pos, c) in raw.char_indices() {
             if c == '\\' {
@@ -40,6 +41,12 @@ impl CommandParts {
                 }
             }
             if !escaping {
+                if c == '!' && (unbounded || starts_with_unbounded_suffix(&raw[pos..])) {
+                    // the "!!" suffix asks for a total search, it's not
+                    // part of the pattern
+                    unbounded = true;
+                    continue;
+                }
                 if c == ' ' || c == ':' {
                     invocation_start_pos = Some(pos);
                     break;
@@ -118,3 +125,36 @@ impl CommandParts {
 
 }
 
+
+#[cfg(test)]
+mod command_parts_test {
+
+    use super::*;
+
+    fn cores(parts: &CommandParts) -> Vec<String> {
+        parts.pattern
+            .iter_atoms()
+            .map(|atom| atom.core().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn check_unbounded_suffix_parsing() {
+        // the suffix is kept in the raw pattern but isn't part of the pattern
+        let parts = CommandParts::from("foo!!".to_string());
+        assert_eq!(parts.raw_pattern, "foo!!");
+        assert_eq!(cores(&parts), vec!["foo"]);
+        assert!(parts.verb_invocation.is_none());
+        // it may be followed by a verb
+        let parts = CommandParts::from("foo!! rm".to_string());
+        assert_eq!(parts.raw_pattern, "foo!!");
+        assert_eq!(cores(&parts), vec!["foo"]);
+        assert_eq!(parts.verb_invocation.unwrap().name, "rm");
+        // when not at the end of the pattern, `!` is the negation
+        let parts = CommandParts::from("!!foo".to_string());
+        assert_eq!(cores(&parts), vec!["foo"]);
+        // an escaped suffix is part of the pattern
+        let parts = CommandParts::from("foo\\!!".to_string());
+        assert_eq!(cores(&parts), vec!["foo!!"]);
+    }
+}
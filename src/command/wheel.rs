@@ -0,0 +1,32 @@
+use {
+    serde::{de::Error, Deserialize, Deserializer},
+};
+
+/// what a turn of the mouse wheel does
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WheelBehavior {
+    /// move the selection, the view following it
+    MoveSelection,
+    /// scroll the view, the selection being moved only
+    /// when it would leave it
+    Scroll,
+}
+
+impl<'de> Deserialize<'de> for WheelBehavior {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let s = String::deserialize(deserializer)?;
+        let s = s.to_lowercase();
+        // we remove non letters so to accept eg "move-selection"
+        let s = regex!(r"\W+").replace_all(&s, "");
+        match s.as_ref() {
+            "moveselection" | "select" | "selection" => Ok(Self::MoveSelection),
+            "scroll" => Ok(Self::Scroll),
+            _ => Err(D::Error::custom(format!(
+                "unrecognized wheel behavior: {:?}",
+                s
+            ))),
+        }
+    }
+}
@@ -4,6 +4,7 @@
 use {
     super::*,
     crate::{
+        command::WheelBehavior,
         display::ColsConf,
         errors::ProgramError,
         skin::SkinEntry,
@@ -45,29 +46,76 @@ pub struct Conf {
     #[serde(alias="default-flags")]
     pub default_flags: Option<String>, // the flags to apply before cli ones
 
+    /// named sets of flags which can be applied with `:profile`
+    #[serde(default)]
+    pub profiles: AHashMap<String, String>,
+
+    /// the preview modes to use by default, per file extension
+    #[serde(default, alias="preview-modes")]
+    pub preview_modes: AHashMap<String, String>,
+
+    /// names of the directories listed by `:cleanable`
+    #[serde(alias="cleanable-dirs")]
+    pub cleanable_dirs: Option<Vec<String>>,
+
     #[serde(alias="date-time-format")]
     pub date_time_format: Option<String>,
 
     #[serde(default)]
     pub verbs: Vec<VerbConf>,
 
+    /// the programs opening files, by extension or mime type
+    #[serde(default)]
+    pub openers: Vec<OpenerConf>,
+
     pub skin: Option<AHashMap<String, SkinEntry>>,
 
+    /// skin entries overriding the ones of `skin` on light backgrounds
+    #[serde(alias="light-skin")]
+    pub light_skin: Option<AHashMap<String, SkinEntry>>,
+
+    /// skin entries overriding the ones of `skin` on dark backgrounds
+    #[serde(alias="dark-skin")]
+    pub dark_skin: Option<AHashMap<String, SkinEntry>>,
+
+    /// skin entries overriding the other ones in the preview panel
+    #[serde(alias="preview-skin")]
+    pub preview_skin: Option<AHashMap<String, SkinEntry>>,
+
+    /// how much the colors of unfocused panels are dimmed, from 0 to 1
+    #[serde(alias="unfocused-dimming")]
+    pub unfocused_dimming: Option<f32>,
+
     #[serde(default, alias="special-paths")]
     pub special_paths: AHashMap<Glob, SpecialHandling>,
 
+    /// changes of the scores of the matches, by glob on their subpath
+    #[serde(default, alias="score-adjustments")]
+    pub score_adjustments: AHashMap<Glob, i32>,
+
     #[serde(alias="search-modes")]
     pub search_modes: Option<FnvHashMap<String, String>>,
 
     #[serde(alias="disable-mouse-capture")]
     pub disable_mouse_capture: Option<bool>,
 
+    /// whether the mouse wheel moves the selection or
+    /// scrolls the view
+    #[serde(alias="mouse-wheel")]
+    pub mouse_wheel: Option<WheelBehavior>,
+
     #[serde(alias="cols-order")]
     pub cols_order: Option<ColsConf>,
 
     #[serde(alias="show-selection-mark")]
     pub show_selection_mark: Option<bool>,
 
+    /// whether to show, after directories, the numbers of
+    /// their children hidden because they're hidden files
+    /// or excluded by a gitignore rule
+    #[serde(alias="show-filtered-counts")]
+    pub show_filtered_counts: Option<bool>,
+
     #[serde(default, alias="ext-colors")]
     pub ext_colors: AHashMap<String, String>,
 
@@ -84,6 +132,27 @@ pub struct Conf {
 
     pub max_panels_count: Option<usize>,
 
+    #[serde(alias="templates-dir")]
+    pub templates_dir: Option<PathBuf>,
+
+    #[serde(alias="max-loaded-children")]
+    pub max_loaded_children: Option<usize>,
+
+    /// how long, in milliseconds, a search goes on looking for better
+    /// matches once it found enough of them to fill the screen
+    #[serde(alias="search-time-budget")]
+    pub search_time_budget: Option<u64>,
+
+    /// number of matches after which a search stops
+    #[serde(alias="search-result-cap")]
+    pub search_result_cap: Option<usize>,
+
+    /// whether searches explore first the directories where matches
+    /// were previously found, then the recently modified ones (true
+    /// by default)
+    #[serde(alias="search-priority-heuristic")]
+    pub search_priority_heuristic: Option<bool>,
+
 }
 
 impl Conf {
@@ -124,6 +193,16 @@ impl Conf {
         }
     }
 
+    /// read the configuration from the given files, which
+    /// must exist
+    pub fn from_files(paths: &[PathBuf]) -> Result<Conf, ProgramError> {
+        let mut conf = Conf::default();
+        for path in paths {
+            conf.read_file(path.to_path_buf())?;
+        }
+        Ok(conf)
+    }
+
     /// assume the file doesn't yet exist
     pub fn write_sample(filepath: &Path) -> Result<(), io::Error> {
         fs::create_dir_all(filepath.parent().unwrap())?;
@@ -138,22 +217,38 @@ impl Conf {
     pub fn read_file(&mut self, path: PathBuf) -> Result<(), ProgramError> {
         let mut conf: Conf = SerdeFormat::read_file(&path)?;
         overwrite!(self, default_flags, conf);
+        overwrite!(self, cleanable_dirs, conf);
         overwrite!(self, date_time_format, conf);
         overwrite!(self, icon_theme, conf);
         overwrite!(self, syntax_theme, conf);
         overwrite!(self, disable_mouse_capture, conf);
+        overwrite!(self, mouse_wheel, conf);
         overwrite!(self, true_colors, conf);
         overwrite!(self, show_selection_mark, conf);
+        overwrite!(self, show_filtered_counts, conf);
         overwrite!(self, cols_order, conf);
         overwrite!(self, skin, conf);
+        overwrite!(self, light_skin, conf);
+        overwrite!(self, dark_skin, conf);
+        overwrite!(self, preview_skin, conf);
+        overwrite!(self, unfocused_dimming, conf);
         overwrite!(self, search_modes, conf);
         overwrite!(self, max_panels_count, conf);
         overwrite!(self, modal, conf);
+        overwrite!(self, templates_dir, conf);
+        overwrite!(self, max_loaded_children, conf);
+        overwrite!(self, search_time_budget, conf);
+        overwrite!(self, search_result_cap, conf);
+        overwrite!(self, search_priority_heuristic, conf);
         self.verbs.append(&mut conf.verbs);
+        self.openers.append(&mut conf.openers);
         // the following maps are "additive": we can add entries from several
         // config files and they still make sense
         overwrite_map!(self, special_paths, conf);
+        overwrite_map!(self, score_adjustments, conf);
         overwrite_map!(self, ext_colors, conf);
+        overwrite_map!(self, profiles, conf);
+        overwrite_map!(self, preview_modes, conf);
         self.files.push(path);
         Ok(())
     }
@@ -0,0 +1,42 @@
+//! Watching of the configuration files, so that the
+//! configuration can be reloaded when the user edits them.
+
+use {
+    std::{
+        fs,
+        path::PathBuf,
+        thread,
+        time::{Duration, SystemTime},
+    },
+};
+
+/// how often the modification dates of the files are checked
+const PERIOD: Duration = Duration::from_secs(1);
+
+fn modification_dates(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths.iter()
+        .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// call `on_change` in a background thread each time one of the files
+/// is modified, until it returns false
+pub fn watch<F>(paths: Vec<PathBuf>, mut on_change: F)
+where
+    F: FnMut() -> bool + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut dates = modification_dates(&paths);
+        loop {
+            thread::sleep(PERIOD);
+            let new_dates = modification_dates(&paths);
+            if new_dates != dates {
+                debug!("configuration file(s) changed");
+                dates = new_dates;
+                if !on_change() {
+                    break;
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,225 @@
+//! Modification of configuration files by broot itself.
+//!
+//! Files are changed by inserting text, never by serializing a
+//! configuration again, so that the comments and the formatting
+//! of the user are kept.
+
+use {
+    super::{Conf, SerdeFormat},
+    crate::errors::ConfError,
+    std::{
+        fs,
+        path::Path,
+    },
+};
+
+/// insert, in the configuration file at the given path, a verb
+/// binding the key to the execution (eg `internal: ":back"` or
+/// `cmd: ":edit"`).
+///
+/// The verb is inserted before the other ones so that it takes
+/// precedence. The file isn't changed if the result can't be parsed.
+pub fn add_key_binding(
+    path: &Path,
+    key: &str,
+    execution_field: &str,
+    execution: &str,
+) -> Result<(), ConfError> {
+    let format = SerdeFormat::from_path(path)?;
+    let text = fs::read_to_string(path)?;
+    let fields = [("key", key), (execution_field, execution)];
+    let text = insert_verb(&text, format, &fields);
+    // we check the new configuration is still valid
+    match format {
+        SerdeFormat::Hjson => {
+            deser_hjson::from_str::<Conf>(&text)?;
+        }
+        SerdeFormat::Toml => {
+            toml::from_str::<Conf>(&text)?;
+        }
+    }
+    fs::write(path, text)?;
+    Ok(())
+}
+
+fn quoted(s: &str) -> String {
+    format!("{:?}", s)
+}
+
+/// return the whitespace at the start of the line
+fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// insert a verb with the given fields at the start of the
+/// verbs list of the configuration text
+fn insert_verb(
+    text: &str,
+    format: SerdeFormat,
+    fields: &[(&str, &str)],
+) -> String {
+    match format {
+        SerdeFormat::Hjson => insert_hjson_verb(text, fields),
+        SerdeFormat::Toml => insert_toml_verb(text, fields),
+    }
+}
+
+fn insert_hjson_verb(text: &str, fields: &[(&str, &str)]) -> String {
+    let entry = fields.iter()
+        .map(|(name, value)| format!("{}: {}", name, quoted(value)))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let entry = format!("{{ {} }}", entry);
+    let lines: Vec<&str> = text.lines().collect();
+    let mut res = String::with_capacity(text.len() + entry.len() + 20);
+    let verbs_line_idx = lines.iter().position(|line| {
+        let line = line.trim_start().trim_start_matches('"');
+        line.starts_with("verbs") && line[5..].trim_start_matches('"').trim_start().starts_with(':')
+    });
+    if let Some(idx) = verbs_line_idx {
+        let line = lines[idx];
+        let unit = if line.starts_with('\t') { "\t" } else { "    " };
+        let entry_indent = format!("{}{}", indentation(line), unit);
+        for (i, line) in lines.iter().enumerate() {
+            if i != idx {
+                res.push_str(line);
+                res.push('\n');
+                continue;
+            }
+            match line.find('[') {
+                Some(bracket_idx) => {
+                    let (start, rest) = line.split_at(bracket_idx + 1);
+                    res.push_str(start);
+                    res.push('\n');
+                    res.push_str(&entry_indent);
+                    res.push_str(&entry);
+                    res.push('\n');
+                    if !rest.trim().is_empty() {
+                        res.push_str(&entry_indent);
+                        res.push_str(rest.trim_start());
+                        res.push('\n');
+                    }
+                }
+                None => {
+                    // strange formatting, with the bracket on another line,
+                    // we give up keeping things pretty
+                    res.push_str(line);
+                    res.push('\n');
+                    res.push_str(&entry_indent);
+                    res.push_str(&entry);
+                    res.push('\n');
+                }
+            }
+        }
+        return res;
+    }
+    // there's no verbs list yet: we add one at the start of the root object
+    let opening_idx = lines.iter().position(|line| {
+        let line = line.trim();
+        !line.is_empty() && !line.starts_with('#') && !line.starts_with("//")
+    });
+    for (i, line) in lines.iter().enumerate() {
+        match opening_idx {
+            Some(idx) if idx == i && line.trim_start().starts_with('{') => {
+                let (start, rest) = line.split_at(line.find('{').unwrap() + 1);
+                res.push_str(start);
+                res.push_str("\n\tverbs: [\n\t\t");
+                res.push_str(&entry);
+                res.push_str("\n\t]\n");
+                if !rest.trim().is_empty() {
+                    res.push('\t');
+                    res.push_str(rest.trim_start());
+                    res.push('\n');
+                }
+            }
+            _ => {
+                res.push_str(line);
+                res.push('\n');
+            }
+        }
+    }
+    res
+}
+
+fn insert_toml_verb(text: &str, fields: &[(&str, &str)]) -> String {
+    let mut entry = "[[verbs]]\n".to_string();
+    for (name, value) in fields {
+        entry.push_str(&format!("{} = {}\n", name, quoted(value)));
+    }
+    let mut res = String::with_capacity(text.len() + entry.len() + 2);
+    let mut inserted = false;
+    for line in text.lines() {
+        if !inserted && line.trim() == "[[verbs]]" {
+            res.push_str(&entry);
+            res.push('\n');
+            inserted = true;
+        }
+        res.push_str(line);
+        res.push('\n');
+    }
+    if !inserted {
+        if !res.is_empty() {
+            res.push('\n');
+        }
+        res.push_str(&entry);
+    }
+    res
+}
+
+#[cfg(test)]
+mod conf_writer_test {
+
+    use super::*;
+
+    fn check(text: &str, format: SerdeFormat, expected_verbs: usize) {
+        let fields = [("key", "ctrl-b"), ("internal", ":back")];
+        let new_text = insert_verb(text, format, &fields);
+        // comments must be kept
+        for line in text.lines() {
+            if line.trim().starts_with('#') {
+                assert!(new_text.contains(line), "lost line {:?} in {}", line, new_text);
+            }
+        }
+        let verbs = match format {
+            SerdeFormat::Hjson => deser_hjson::from_str::<Conf>(&new_text).unwrap().verbs,
+            SerdeFormat::Toml => toml::from_str::<Conf>(&new_text).unwrap().verbs,
+        };
+        assert_eq!(verbs.len(), expected_verbs, "bad result: {}", new_text);
+        let first = format!("{:?}", verbs[0]);
+        assert!(first.contains("ctrl-b"), "bad first verb: {}", first);
+    }
+
+    #[test]
+    fn test_insert_verb() {
+        check(
+            "# my conf\n{\n\tverbs: [\n\t\t# a comment\n\t\t{ invocation: \"e\", external: \"nvim {file}\" }\n\t]\n}\n",
+            SerdeFormat::Hjson,
+            2,
+        );
+        check(
+            "{\n    verbs: [ { invocation: \"e\", external: \"nvim {file}\" } ]\n}\n",
+            SerdeFormat::Hjson,
+            2,
+        );
+        check(
+            "# nothing\n{\n    modal: true\n}\n",
+            SerdeFormat::Hjson,
+            1,
+        );
+        check(
+            "{ modal: true }\n",
+            SerdeFormat::Hjson,
+            1,
+        );
+        check(
+            "# my conf\nmodal = true\n\n[[verbs]]\n# the editor\ninvocation = \"e\"\nexternal = \"nvim {file}\"\n",
+            SerdeFormat::Toml,
+            2,
+        );
+        check(
+            "modal = true\n",
+            SerdeFormat::Toml,
+            1,
+        );
+    }
+}
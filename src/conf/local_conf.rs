@@ -0,0 +1,172 @@
+use {
+    super::SerdeFormat,
+    crate::{
+        path::{SpecialHandling, SpecialPath},
+        tree::TreeOptions,
+    },
+    ahash::AHashMap,
+    serde::Deserialize,
+    std::path::{Path, PathBuf},
+};
+
+/// name of the files defining a local configuration, which applies
+/// to the directory they're in and to its descendants
+pub const LOCAL_CONF_FILENAME: &str = ".broot.toml";
+
+/// the content of a `.broot.toml` file
+#[derive(Default, Debug, Deserialize)]
+struct LocalConfFile {
+    hidden: Option<bool>,
+    show_gitignored: Option<bool>,
+    default_pattern: Option<String>,
+    #[serde(default)]
+    special_paths: AHashMap<String, SpecialHandling>,
+}
+
+/// the merged content of the `.broot.toml` files found in a
+/// directory and its ancestors, the closest files having
+/// precedence over the farthest ones
+#[derive(Default, Debug)]
+pub struct LocalConf {
+
+    /// the files which were read, farthest first
+    pub files: Vec<PathBuf>,
+
+    pub show_hidden: Option<bool>,
+
+    pub show_gitignored: Option<bool>,
+
+    /// the pattern to apply when there's none
+    pub default_pattern: Option<String>,
+
+    /// special paths, with absolute patterns, closest first
+    pub special_paths: Vec<SpecialPath>,
+}
+
+impl LocalConf {
+
+    /// find and read the local configuration files applying
+    /// to the given directory. Invalid files are ignored (with a
+    /// warning in the log).
+    pub fn find(dir: &Path) -> Option<Self> {
+        let mut files: Vec<PathBuf> = dir.ancestors()
+            .map(|dir| dir.join(LOCAL_CONF_FILENAME))
+            .filter(|path| path.is_file())
+            .collect();
+        if files.is_empty() {
+            return None;
+        }
+        files.reverse();
+        let mut local_conf = LocalConf::default();
+        for file in files {
+            match SerdeFormat::read_file::<LocalConfFile>(&file) {
+                Ok(file_conf) => {
+                    let dir = file.parent().unwrap_or(dir);
+                    local_conf.add(dir, file_conf);
+                }
+                Err(e) => {
+                    warn!("ignoring local configuration {:?} : {}", file, e);
+                }
+            }
+            local_conf.files.push(file);
+        }
+        Some(local_conf)
+    }
+
+    fn add(&mut self, dir: &Path, file_conf: LocalConfFile) {
+        if file_conf.hidden.is_some() {
+            self.show_hidden = file_conf.hidden;
+        }
+        if file_conf.show_gitignored.is_some() {
+            self.show_gitignored = file_conf.show_gitignored;
+        }
+        if file_conf.default_pattern.is_some() {
+            self.default_pattern = file_conf.default_pattern;
+        }
+        // relative globs are relative to the directory of the file
+        let dir = glob::Pattern::escape(&dir.to_string_lossy());
+        for (glob, handling) in file_conf.special_paths {
+            let glob = if glob.starts_with('/') {
+                glob
+            } else {
+                format!("{}/{}", dir, glob)
+            };
+            match glob::Pattern::new(&glob) {
+                Ok(pattern) => {
+                    self.special_paths.insert(0, SpecialPath { pattern, handling });
+                }
+                Err(e) => {
+                    warn!("invalid special path {:?} : {}", glob, e);
+                }
+            }
+        }
+    }
+
+    /// change the tree options according to this local configuration,
+    /// returning the values they had before
+    pub fn apply_to(&self, options: &mut TreeOptions) -> LocalConfBase {
+        let base = LocalConfBase {
+            show_hidden: options.show_hidden,
+            respect_git_ignore: options.respect_git_ignore,
+        };
+        if let Some(b) = self.show_hidden {
+            options.show_hidden = b;
+        }
+        if let Some(b) = self.show_gitignored {
+            options.respect_git_ignore = !b;
+        }
+        base
+    }
+}
+
+/// the values of the tree options a local configuration may change,
+/// as they were before it applied
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalConfBase {
+    show_hidden: bool,
+    respect_git_ignore: bool,
+}
+
+impl LocalConfBase {
+    /// set back the options to the values they had before
+    /// the local configuration applied
+    pub fn restore(self, options: &mut TreeOptions) {
+        options.show_hidden = self.show_hidden;
+        options.respect_git_ignore = self.respect_git_ignore;
+    }
+}
+
+#[cfg(test)]
+mod local_conf_test {
+
+    use {
+        super::*,
+        crate::path::SpecialPathList,
+        std::fs,
+    };
+
+    #[test]
+    fn test_chain() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let sub = root.join("sub");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(
+            root.join(LOCAL_CONF_FILENAME),
+            "hidden = true\ndefault_pattern = \"rs$\"\n[special_paths]\n\"target\" = \"no-enter\"\n",
+        ).unwrap();
+        fs::write(sub.join(LOCAL_CONF_FILENAME), "hidden = false\n").unwrap();
+        let local_conf = LocalConf::find(&sub).unwrap();
+        assert_eq!(local_conf.files.len(), 2);
+        assert_eq!(local_conf.show_hidden, Some(false));
+        assert_eq!(local_conf.default_pattern.as_deref(), Some("rs$"));
+        assert_eq!(
+            local_conf.special_paths.as_slice().find(&root.join("target")),
+            SpecialHandling::NoEnter,
+        );
+        assert_eq!(
+            local_conf.special_paths.as_slice().find(&sub.join("target")),
+            SpecialHandling::None,
+        );
+    }
+}
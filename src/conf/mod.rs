@@ -4,12 +4,20 @@ use {
 };
 
 mod conf;
+mod conf_watcher;
+mod conf_writer;
 mod format;
+mod local_conf;
+mod opener_conf;
 mod verb_conf;
 
 pub use {
     conf::Conf,
+    conf_watcher::watch,
+    conf_writer::add_key_binding,
     format::*,
+    local_conf::{LocalConf, LocalConfBase, LOCAL_CONF_FILENAME},
+    opener_conf::OpenerConf,
     verb_conf::VerbConf,
 };
 
@@ -0,0 +1,103 @@
+use {
+    crate::{
+        errors::ConfError,
+        verb::*,
+    },
+    serde::Deserialize,
+    std::convert::TryFrom,
+};
+
+/// a deserializable entry of the `openers` configuration section
+#[derive(Default, Debug, Clone, Deserialize)]
+pub struct OpenerConf {
+
+    #[serde(default)]
+    extensions: Vec<String>,
+
+    mime: Option<String>,
+
+    execution: Option<ExecPattern>,
+
+    terminal: Option<bool>,
+
+}
+
+impl TryFrom<&OpenerConf> for Opener {
+    type Error = ConfError;
+    fn try_from(oc: &OpenerConf) -> Result<Self, Self::Error> {
+        let exec_pattern = match oc.execution.as_ref().filter(|ep| !ep.is_empty()) {
+            Some(ep) => ep.clone(),
+            None => {
+                return Err(ConfError::InvalidOpenerConf {
+                    details: "an opener needs an execution".to_string(),
+                });
+            }
+        };
+        if oc.extensions.is_empty() && oc.mime.is_none() {
+            return Err(ConfError::InvalidOpenerConf {
+                details: "an opener needs extensions or a mime type".to_string(),
+            });
+        }
+        let extensions = oc.extensions.iter()
+            .map(|ext| ext.trim_start_matches('.').to_lowercase())
+            .collect();
+        let mime = oc.mime.as_ref()
+            .map(|mime| glob::Pattern::new(mime))
+            .transpose()
+            .map_err(|e| ConfError::InvalidOpenerConf {
+                details: format!("invalid mime pattern: {}", e),
+            })?;
+        Ok(Opener {
+            extensions,
+            mime,
+            exec_pattern,
+            terminal: oc.terminal.unwrap_or(false),
+        })
+    }
+}
+
+#[cfg(test)]
+mod opener_conf_test {
+
+    use {
+        super::*,
+        std::path::Path,
+    };
+
+    fn parse(hjson: &str) -> Result<Opener, ConfError> {
+        let oc: OpenerConf = deser_hjson::from_str(hjson).unwrap();
+        Opener::try_from(&oc)
+    }
+
+    #[test]
+    fn check_opener_conf() {
+        let opener = parse(r#"{
+            extensions: [ ".MD", "txt" ]
+            execution: "$EDITOR {file}"
+            terminal: true
+        }"#).unwrap();
+        assert_eq!(opener.extensions, vec!["md".to_string(), "txt".to_string()]);
+        assert!(opener.mime.is_none());
+        assert!(opener.terminal);
+        assert!(opener.applies_to(Path::new("README.md")));
+        let opener = parse(r#"{
+            mime: "image/*"
+            execution: "feh {file}"
+        }"#).unwrap();
+        assert!(opener.extensions.is_empty());
+        assert!(!opener.terminal);
+        assert!(opener.applies_to(Path::new("a.png")));
+        assert!(!opener.applies_to(Path::new("a.pdf")));
+    }
+
+    #[test]
+    fn check_invalid_opener_conf() {
+        // no execution
+        assert!(parse(r#"{ extensions: [ "md" ] }"#).is_err());
+        assert!(parse(r#"{ extensions: [ "md" ], execution: "" }"#).is_err());
+        // nothing to match
+        assert!(parse(r#"{ execution: "feh {file}" }"#).is_err());
+        // invalid glob
+        assert!(parse(r#"{ mime: "image/[", execution: "feh {file}" }"#).is_err());
+    }
+}
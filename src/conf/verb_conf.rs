@@ -43,6 +43,10 @@ pub struct VerbConf {
 
     description: Option<String>,
 
+    on_double_click: Option<bool>,
+
+    confirm: Option<String>,
+
 }
 
 /// read a deserialized verb conf item into a verb,
@@ -115,15 +119,22 @@ impl TryFrom<&VerbConf> for Verb {
         if let Some(key) = &vc.key {
             unchecked_keys.push(key.clone());
         }
+        // a key definition may also be a sequence of space separated keys
         let mut checked_keys = Vec::new();
         for key in &unchecked_keys {
-            let key = keys::parse_key(key)?;
-            if keys::is_reserved(key) {
-                return Err(ConfError::ReservedKey {
-                    key: keys::key_event_desc(key),
-                });
+            let mut sequence = keys::parse_key_sequence(key)?;
+            for &key in &sequence {
+                if keys::is_reserved(key) {
+                    return Err(ConfError::ReservedKey {
+                        key: keys::key_event_desc(key),
+                    });
+                }
+            }
+            if sequence.len() == 1 {
+                checked_keys.push(sequence.remove(0));
+            } else {
+                verb.add_chord(sequence);
             }
-            checked_keys.push(key);
         }
         if !checked_keys.is_empty() {
             verb.add_keys(checked_keys);
@@ -142,6 +153,8 @@ impl TryFrom<&VerbConf> for Verb {
                 });
             }
         };
+        verb.on_double_click = vc.on_double_click.unwrap_or(false);
+        verb.confirmation = Confirmation::from_conf(vc.confirm.as_deref())?;
         Ok(verb)
     }
 }
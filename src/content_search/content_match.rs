@@ -6,6 +6,7 @@ pub struct ContentMatch {
     pub extract: String,
     pub needle_start: usize, // position in the extract, in bytes
     pub needle_end: usize,   // length in bytes
+    pub line: usize,         // number of the line of the match, starting at 1
 }
 
 impl ContentMatch {
@@ -45,10 +46,12 @@ impl ContentMatch {
         // the from_utf8_lossy
         let extract = String::from_utf8_lossy(&hay[extract_start..extract_end]).to_string();
         let needle_start = extract.find(needle).unwrap_or(0);
+        let line = hay[..pos].iter().filter(|&&b| b == b'\n').count() + 1;
         Self {
             extract,
             needle_start,
             needle_end: needle_start + needle.len(),
+            line,
         }
     }
 }
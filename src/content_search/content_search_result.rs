@@ -14,6 +14,9 @@ pub enum ContentSearchResult {
 
     /// the file wasn't searched because it's binary or too big
     NotSuitable,
+
+    /// the search was interrupted before the end of the file
+    Cancelled,
 }
 
 impl ContentSearchResult {
@@ -4,7 +4,7 @@
 
 use {
     super::*,
-    memmap::Mmap,
+    crate::task_sync::CancellationToken,
     std::{
         convert::TryInto,
         fmt,
@@ -13,6 +13,10 @@ use {
     },
 };
 
+/// size of the parts of a file searched between two checks
+/// of the cancellation token
+const CHUNK_SIZE: usize = 1024 * 1024;
+
 /// a strict (non fuzzy, case sensitive) pattern which may
 /// be searched in file contents
 #[derive(Clone)]
@@ -49,12 +53,12 @@ impl Needle {
     }
 
     // no, it doesn't bring more than a few % in speed
-    fn find_naive_1(&self, hay: &Mmap) -> Option<usize> {
+    fn find_naive_1(&self, hay: &[u8]) -> Option<usize> {
         let n = self.bytes[0];
         hay.iter().position(|&b| b == n)
     }
 
-    fn find_naive_2(&self, mut pos: usize, hay: &Mmap) -> Option<usize> {
+    fn find_naive_2(&self, mut pos: usize, hay: &[u8]) -> Option<usize> {
         let max_pos = hay.len() - 2;
         let b0 = self.bytes[0];
         let b1 = self.bytes[1];
@@ -69,7 +73,7 @@ impl Needle {
         None
     }
 
-    fn find_naive_3(&self, mut pos: usize, hay: &Mmap) -> Option<usize> {
+    fn find_naive_3(&self, mut pos: usize, hay: &[u8]) -> Option<usize> {
         let max_pos = hay.len() - 3;
         let b0 = self.bytes[0];
         let b1 = self.bytes[1];
@@ -88,7 +92,7 @@ impl Needle {
         None
     }
 
-    fn find_naive_4(&self, mut pos: usize, hay: &Mmap) -> Option<usize> {
+    fn find_naive_4(&self, mut pos: usize, hay: &[u8]) -> Option<usize> {
         use std::mem::transmute;
         let max_pos = hay.len() - 4;
         unsafe {
@@ -103,7 +107,7 @@ impl Needle {
         None
     }
 
-    fn find_naive_6(&self, mut pos: usize, hay: &Mmap) -> Option<usize> {
+    fn find_naive_6(&self, mut pos: usize, hay: &[u8]) -> Option<usize> {
         let max_pos = hay.len() - 6;
         let b0 = self.bytes[0];
         let b1 = self.bytes[1];
@@ -128,7 +132,7 @@ impl Needle {
         None
     }
 
-    fn is_at_pos(&self, hay_stack: &Mmap, pos: usize) -> bool {
+    fn is_at_pos(&self, hay_stack: &[u8], pos: usize) -> bool {
         unsafe {
             for (i, b) in self.bytes.iter().enumerate() {
                 if hay_stack.get_unchecked(i + pos) != b {
@@ -139,7 +143,7 @@ impl Needle {
         true
     }
 
-    fn find_naive(&self, mut pos: usize, hay: &Mmap) -> Option<usize> {
+    fn find_naive(&self, mut pos: usize, hay: &[u8]) -> Option<usize> {
         let max_pos = hay.len() - self.bytes.len();
         while pos <= max_pos {
             if self.is_at_pos(&hay, pos) {
@@ -164,7 +168,11 @@ impl Needle {
     /// as their impact is dwarfed by the whole mem map related set
     /// of problems. An alternate implementation should probably focus
     /// on avoiding mem maps.
-    fn search_mmap(&self, hay: &Mmap) -> ContentSearchResult {
+    fn search_mmap(
+        &self,
+        hay: &[u8],
+        cancellation: Option<&CancellationToken>,
+    ) -> ContentSearchResult {
         if hay.len() < self.bytes.len() {
             return ContentSearchResult::NotFound;
         }
@@ -182,26 +190,47 @@ impl Needle {
             // TODO the Windows equivalent might be PrefetchVirtualMemory
         }
 
-        let pos = match self.bytes.len() {
-            1 => self.find_naive_1(&hay),
-            2 => self.find_naive_2(0, &hay),
-            3 => self.find_naive_3(0, &hay),
-            4 => self.find_naive_4(0, &hay),
-            6 => self.find_naive_6(0, &hay),
-            _ => self.find_naive(0, &hay),
-        };
-        pos.map_or(
-            ContentSearchResult::NotFound,
-            |pos| ContentSearchResult::Found { pos },
-        )
+        // the hay is searched in chunks (overlapping so that a match
+        // can't be missed) so that the search can be cancelled between
+        // two chunks
+        let mut chunk_start = 0;
+        loop {
+            let chunk_end = hay.len().min(chunk_start + CHUNK_SIZE + self.bytes.len() - 1);
+            let chunk = &hay[chunk_start..chunk_end];
+            let pos = match self.bytes.len() {
+                1 => self.find_naive_1(chunk),
+                2 => self.find_naive_2(0, chunk),
+                3 => self.find_naive_3(0, chunk),
+                4 => self.find_naive_4(0, chunk),
+                6 => self.find_naive_6(0, chunk),
+                _ => self.find_naive(0, chunk),
+            };
+            if let Some(pos) = pos {
+                return ContentSearchResult::Found { pos: chunk_start + pos };
+            }
+            if chunk_end == hay.len() {
+                return ContentSearchResult::NotFound;
+            }
+            if matches!(cancellation, Some(c) if c.is_cancelled()) {
+                return ContentSearchResult::Cancelled;
+            }
+            chunk_start += CHUNK_SIZE;
+        }
     }
 
-    /// determine whether the file contains the needle
-    pub fn search<P: AsRef<Path>>(&self, hay_path: P) -> io::Result<ContentSearchResult> {
+    /// determine whether the file contains the needle.
+    ///
+    /// The search stops early, returning `Cancelled`, when
+    /// the cancellation token is cancelled.
+    pub fn search<P: AsRef<Path>>(
+        &self,
+        hay_path: P,
+        cancellation: Option<&CancellationToken>,
+    ) -> io::Result<ContentSearchResult> {
         super::get_mmap_if_not_binary(hay_path)
             .map(|om| om.map_or(
                 ContentSearchResult::NotSuitable,
-                |hay| self.search_mmap(&hay),
+                |hay| self.search_mmap(&hay, cancellation),
             ))
     }
 
@@ -216,7 +245,7 @@ impl Needle {
             Ok(hay) => hay,
             _ => { return None; }
         };
-        match self.search_mmap(&hay) {
+        match self.search_mmap(&hay, None) {
             ContentSearchResult::Found { pos } => {
                 Some(ContentMatch::build(&hay, pos, self.as_str(), desired_len))
             }
@@ -232,8 +261,35 @@ mod content_search_tests {
     #[test]
     fn test_found() -> Result<(), io::Error> {
         let needle = Needle::new("inception");
-        let res = needle.search("src/content_search/needle.rs")?;
+        let res = needle.search("src/content_search/needle.rs", None)?;
         assert!(res.is_found());
         Ok(())
     }
+
+    #[test]
+    fn test_found_across_chunks() {
+        let needle = Needle::new("broot");
+        let mut hay = vec![b'-'; 3 * CHUNK_SIZE];
+        let pos = CHUNK_SIZE - 2;
+        hay[pos..pos + 5].copy_from_slice(b"broot");
+        assert_eq!(needle.search_mmap(&hay, None), ContentSearchResult::Found { pos });
+        let cancellation = CancellationToken::never();
+        cancellation.cancel();
+        assert_eq!(
+            needle.search_mmap(&hay[CHUNK_SIZE..], Some(&cancellation)),
+            ContentSearchResult::Cancelled,
+        );
+    }
+
+    #[test]
+    fn test_match_line() {
+        let hay = b"first line\nsecond line\nthird broot line\n";
+        let needle = Needle::new("broot");
+        let pos = match needle.search_mmap(hay, None) {
+            ContentSearchResult::Found { pos } => pos,
+            res => panic!("unexpected result: {:?}", res),
+        };
+        let content_match = ContentMatch::build(hay, pos, needle.as_str(), 20);
+        assert_eq!(content_match.line, 3);
+    }
 }
@@ -1,5 +1,6 @@
 use {
     super::{
+        Layout,
         Screen,
         WIDE_STATUS,
     },
@@ -24,7 +25,7 @@ pub struct Areas {
 }
 
 const MINIMAL_PANEL_HEIGHT: u16 = 10;
-const MINIMAL_PANEL_WIDTH: u16 = 20;
+pub const MINIMAL_PANEL_WIDTH: u16 = 20;
 
 enum Slot<'a> {
     Panel(usize),
@@ -39,6 +40,7 @@ impl Areas {
         mut insertion_idx: usize,
         screen: Screen,
         with_preview: bool, // slightly larger last panel
+        layout: &Layout,
     ) -> Result<Self, ProgramError> {
         if insertion_idx > present_panels.len() {
             insertion_idx = present_panels.len();
@@ -59,7 +61,7 @@ impl Areas {
         for i in insertion_idx..present_panels.len() {
             slots.push(Slot::Panel(i));
         }
-        Self::compute_areas(present_panels, &mut slots, screen, with_preview)?;
+        Self::compute_areas(present_panels, &mut slots, screen, with_preview, layout)?;
         Ok(areas)
     }
 
@@ -67,12 +69,13 @@ impl Areas {
         panels: &mut [Panel],
         screen: Screen,
         with_preview: bool, // slightly larger last panel
+        layout: &Layout,
     ) -> Result<(), ProgramError> {
         let mut slots = Vec::new();
         for i in 0..panels.len() {
             slots.push(Slot::Panel(i));
         }
-        Self::compute_areas(panels, &mut slots, screen, with_preview)
+        Self::compute_areas(panels, &mut slots, screen, with_preview, layout)
     }
 
     fn compute_areas(
@@ -80,26 +83,33 @@ impl Areas {
         slots: &mut Vec<Slot>,
         screen: Screen,
         with_preview: bool, // slightly larger last panel
+        layout: &Layout,
     ) -> Result<(), ProgramError> {
         if screen.height < MINIMAL_PANEL_HEIGHT {
             return Err(ProgramError::TerminalTooSmallError);
         }
-        let n = slots.len() as u16;
-        let mut panel_width = if with_preview {
-            3 * screen.width / (3 * n + 1)
-        } else {
-            screen.width / n
+        let nb_pos = slots.len();
+        let widths = match layout.widths(nb_pos, screen.width) {
+            Some(widths) => widths,
+            None => {
+                let n = nb_pos as u16;
+                let panel_width = if with_preview {
+                    3 * screen.width / (3 * n + 1)
+                } else {
+                    screen.width / n
+                };
+                if panel_width < MINIMAL_PANEL_WIDTH {
+                    return Err(ProgramError::TerminalTooSmallError);
+                }
+                let mut widths = vec![panel_width; nb_pos];
+                widths[nb_pos - 1] = screen.width - panel_width * (n - 1);
+                widths
+            }
         };
-        if panel_width < MINIMAL_PANEL_WIDTH {
-            return Err(ProgramError::TerminalTooSmallError);
-        }
         let mut x = 0;
-        let nb_pos = slots.len();
         #[allow(clippy::needless_range_loop)]
         for slot_idx in 0..nb_pos {
-            if slot_idx == nb_pos - 1 {
-                panel_width = screen.width - x;
-            }
+            let panel_width = widths[slot_idx];
             let areas: &mut Areas = match &mut slots[slot_idx] {
                 Slot::Panel(panel_idx) => &mut panels[*panel_idx].areas,
                 Slot::New(areas) => areas,
@@ -2,6 +2,7 @@ use {
     crate::{
         app::AppState,
         errors::ConfError,
+        tag,
         tree::Tree,
     },
     serde::Deserialize,
@@ -12,7 +13,7 @@ use {
 };
 
 // number of columns in enum
-const COLS_COUNT: usize = 9;
+const COLS_COUNT: usize = 10;
 
 /// One of the "columns" of the tree view
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -41,6 +42,9 @@ pub enum Col {
     /// marks whether the path is staged (not used for now, may be removed)
     Staged,
 
+    /// tags attached to the path with `:tag`
+    Tags,
+
     /// name of the file, or subpath if relevant due to filtering mode
     Name,
 }
@@ -66,6 +70,7 @@ pub static DEFAULT_COLS: Cols = [
     Col::Count,
     Col::Branch,
     Col::Staged,
+    Col::Tags,
     Col::Name,
 ];
 
@@ -82,6 +87,7 @@ impl FromStr for Col {
             "s" | "size" => Ok(Self::Size),
             "c" | "count" => Ok(Self::Count),
             "staged" => Ok(Self::Staged),
+            "t" | "tags" => Ok(Self::Tags),
             "n" | "name" => Ok(Self::Name),
             _ => Err(ConfError::InvalidCols {
                 details: format!("column not recognized : {}", s),
@@ -111,6 +117,7 @@ impl Col {
             Col::Count => false,
             Col::Branch => false,
             Col::Staged => false,
+            Col::Tags => true,
             Col::Name => false,
         }
     }
@@ -122,7 +129,7 @@ impl Col {
         let tree_options = &tree.options;
         match self {
             Col::Mark => tree_options.show_selection_mark,
-            Col::Git => tree.git_status.is_some(),
+            Col::Git => tree.has_line_git_statuses,
             Col::Size => tree_options.show_sizes,
             Col::Date => tree_options.show_dates,
             Col::Permission => tree_options.show_permissions,
@@ -130,6 +137,7 @@ impl Col {
             Col::Branch => true,
             //Col::Staged => app_state.map_or(false, |a| !a.stage.is_empty()),
             Col::Staged => false,
+            Col::Tags => tree.lines.iter().any(|line| tag::is_tagged(&line.path)),
             Col::Name => true,
         }
 
@@ -2,8 +2,9 @@ use {
     super::{
         Col,
         CropWriter,
+        Filling,
         GitStatusDisplay,
-        SPACE_FILLING, BRANCH_FILLING,
+        SPACE_FILLING, BRANCH_FILLING, ASCII_BRANCH_FILLING,
         MatchedString,
     },
     crate::{
@@ -13,6 +14,7 @@ use {
         file_sum::FileSum,
         pattern::PatternObject,
         skin::{ExtColorMap, StyleMap},
+        tag,
         task_sync::ComputationResult,
         tree::{Tree, TreeLine, TreeLineType},
     },
@@ -27,6 +29,9 @@ use {
     termimad::{CompoundStyle, ProgressBar},
 };
 
+/// the max width of the tags column
+const MAX_TAGS_LEN: usize = 20;
+
 /// A tree wrapper which can be used either
 /// - to write on the screen in the application,
 /// - or to write in a file or an exported string.
@@ -40,7 +45,9 @@ pub struct DisplayableTree<'a, 's, 't> {
     pub skin: &'s StyleMap,
     pub area: termimad::Area,
     pub in_app: bool, // if true we show the selection and scrollbar
+    pub show_selection: bool, // false for trees which can't be navigated
     pub ext_colors: &'s ExtColorMap,
+    pub ascii: bool, // if true only ASCII chars are used for the decorations
 }
 
 impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
@@ -51,6 +58,7 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
         ext_colors: &'s ExtColorMap,
         width: u16,
         height: u16,
+        ascii: bool,
     ) -> DisplayableTree<'a, 's, 't> {
         DisplayableTree {
             app_state: None,
@@ -64,9 +72,16 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
                 height,
             },
             in_app: false,
+            show_selection: false,
+            ascii,
         }
     }
 
+    /// the string marking that some chars were cut
+    fn ellipsis(&self) -> &'static str {
+        if self.ascii { "..." } else { "…" }
+    }
+
     fn label_style(
         &self,
         line: &TreeLine,
@@ -119,7 +134,7 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
         selected: bool,
     ) -> Result<usize, termimad::Error> {
         Ok(if selected {
-            cw.queue_char(&style, '▶')?;
+            cw.queue_char(style, if self.ascii { '>' } else { '▶' })?;
             0
         } else {
             1
@@ -138,9 +153,38 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
                 style,
                 format!("{:>4}", file_size::fit_4(s.to_size())),
             )?;
-            1
+            self.write_line_ignored_size(cw, line, style)? + 1
+        } else {
+            self.ignored_size_len() + 5
+        })
+    }
+
+    /// width of the gitignored part of the size column, when shown
+    fn ignored_size_len(&self) -> usize {
+        if self.tree.options.show_ignored_sizes {
+            15
         } else {
-            5
+            0
+        }
+    }
+
+    /// write the gitignored part of the size of a directory, if any,
+    /// and return the width to fill
+    fn write_line_ignored_size<'w, W: Write>(
+        &self,
+        cw: &mut CropWriter<'w, W>,
+        line: &TreeLine,
+        style: &CompoundStyle,
+    ) -> Result<usize, termimad::Error> {
+        Ok(match line.ignored_size {
+            Some(size) if size > 0 && self.tree.options.show_ignored_sizes => {
+                cw.queue_g_string(
+                    style,
+                    format!(" ({:>4} ignored)", file_size::fit_4(size)),
+                )?;
+                0
+            }
+            _ => self.ignored_size_len(),
         })
     }
 
@@ -166,9 +210,9 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
                 if s.is_sparse() && line.is_file() { 's' } else { ' ' },
             )?;
             cw.queue_g_string(label_style, format!("{:<10}", pb))?;
-            1
+            self.write_line_ignored_size(cw, line, label_style)? + 1
         } else {
-            16
+            self.ignored_size_len() + 16
         })
     }
 
@@ -178,7 +222,7 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
         line: &TreeLine,
         selected: bool,
     ) -> Result<usize, termimad::Error> {
-        let (style, char) = if !line.is_selectable() {
+        let (style, char) = if line.is_pruning() {
             (&self.skin.tree, ' ')
         } else {
             match line.git_status.map(|s| s.status) {
@@ -229,19 +273,23 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
                     if self.tree.has_branch(line_index + 1, depth as usize) {
                         // TODO: If a theme is on, remove the horizontal lines
                         if depth == line.depth - 1 {
-                            if staged {
-                                "├◍─"
-                            } else {
-                                "├──"
+                            match (staged, self.ascii) {
+                                (true, false) => "├◍─",
+                                (false, false) => "├──",
+                                (true, true) => "|*-",
+                                (false, true) => "|--",
                             }
+                        } else if self.ascii {
+                            "|  "
                         } else {
                             "│  "
                         }
                     } else {
-                        if staged {
-                            "└◍─"
-                        } else {
-                            "└──"
+                        match (staged, self.ascii) {
+                            (true, false) => "└◍─",
+                            (false, false) => "└──",
+                            (true, true) => "`*-",
+                            (false, true) => "`--",
                         }
                     }
                 } else {
@@ -263,13 +311,65 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
         staged: bool,
     ) -> Result<usize, termimad::Error> {
         Ok(if staged {
-            cw.queue_char(&style, '◍')?; // ▣
+            cw.queue_char(style, if self.ascii { '*' } else { '◍' })?; // ▣
             0
         } else {
             1
         })
     }
 
+    /// write the tags of the path, comma separated, cropped if
+    /// longer than tags_len
+    fn write_line_tags<'w, W: Write>(
+        &self,
+        cw: &mut CropWriter<'w, W>,
+        line: &TreeLine,
+        tags_len: usize,
+        selected: bool,
+    ) -> Result<usize, termimad::Error> {
+        Ok(if let Some(tags) = tag::tags_string(&line.path) {
+            cond_bg!(tag_style, self, selected, self.skin.tag);
+            let count = tags.chars().count();
+            if count > tags_len {
+                let ellipsis = self.ellipsis();
+                let mut cropped: String = tags.chars()
+                    .take(tags_len.saturating_sub(ellipsis.chars().count()))
+                    .collect();
+                cropped.push_str(ellipsis);
+                cw.queue_g_string(tag_style, cropped)?;
+                1
+            } else {
+                cw.queue_g_string(tag_style, tags)?;
+                tags_len - count + 1
+            }
+        } else {
+            tags_len + 1
+        })
+    }
+
+    /// write the numbers of children of a directory which aren't
+    /// shown because they're hidden or gitignored, if required.
+    ///
+    /// Those counts aren't written when there's a pattern, as they
+    /// don't tell whether the excluded children would match.
+    fn write_filtered_counts<'w, W: Write>(
+        &self,
+        cw: &mut CropWriter<'w, W>,
+        line: &TreeLine,
+        selected: bool,
+    ) -> Result<(), ProgramError> {
+        if self.tree.options.show_filtered_counts && self.tree.options.pattern.is_none() {
+            cond_bg!(pruning_style, self, selected, self.skin.pruning);
+            if line.nb_hidden > 0 {
+                cw.queue_str(pruning_style, &format!(" +{} hidden", line.nb_hidden))?;
+            }
+            if line.nb_gitignored > 0 {
+                cw.queue_str(pruning_style, &format!(" +{} ignored", line.nb_gitignored))?;
+            }
+        }
+        Ok(())
+    }
+
     /// write the name or subpath, depending on the pattern_object
     fn write_line_label<'w, W: Write>(
         &self,
@@ -280,7 +380,7 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
         selected: bool,
     ) -> Result<usize, ProgramError> {
         cond_bg!(char_match_style, self, selected, self.skin.char_match);
-        if let Some(icon) = line.icon {
+        if let Some(icon) = line.icon.filter(|_| !self.ascii) {
             cw.queue_char(style, icon)?;
             cw.queue_char(style, ' ')?;
             cw.queue_char(style, ' ')?;
@@ -291,18 +391,24 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
             &line.name
         };
         let name_match = self.tree.options.pattern.pattern.search_string(label);
-        let matched_string = MatchedString::new(
+        let mut matched_string = MatchedString::new(
             name_match,
             label,
             &style,
             &char_match_style,
         );
+        if matched_string.skip_chars(self.tree.h_scroll) {
+            // the start of the name is hidden by horizontal scrolling
+            cw.queue_str(style, self.ellipsis())?;
+        }
         matched_string.queue_on(cw)?;
         match &line.line_type {
             TreeLineType::Dir => {
                 if line.unlisted > 0 {
-                    cw.queue_str(style, " …")?;
+                    cw.queue_char(style, ' ')?;
+                    cw.queue_str(style, self.ellipsis())?;
                 }
+                self.write_filtered_counts(cw, line, selected)?;
             }
             TreeLineType::BrokenSymLink(direct_path) => {
                 cw.queue_str(style, " -> ")?;
@@ -364,9 +470,16 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
                     format!("{:>4} ", file_size::fit_4(s.to_size())),
                 )?;
             }
+            if let Some(size) = line.ignored_size.filter(|&s| s > 0 && self.tree.options.show_ignored_sizes) {
+                cw.queue_g_string(
+                    style,
+                    format!("({} ignored) ", file_size::fit_4(size).trim()),
+                )?;
+            }
         }
         let title = line.path.to_string_lossy();
         cw.queue_str(&style, &title)?;
+        self.write_filtered_counts(cw, line, selected)?;
         if self.in_app && !cw.is_full() {
             if let ComputationResult::Done(git_status) = &self.tree.git_status {
                 let git_status_display = GitStatusDisplay::from(
@@ -426,7 +539,7 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
         }
         let mut cw = CropWriter::new(f, self.area.width as usize);
         let pattern_object = tree.options.pattern.pattern.object();
-        self.write_root_line(&mut cw, self.in_app && tree.selection == 0)?;
+        self.write_root_line(&mut cw, self.in_app && self.show_selection && tree.selection == 0)?;
         self.skin.queue_reset(f)?;
 
         let visible_cols: Vec<Col> = tree
@@ -449,6 +562,19 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
             0
         };
 
+        // the tags column is as wide as the longest tags list, up to a limit
+        let tags_len = if visible_cols.contains(&Col::Tags) {
+            tree.lines.iter()
+                .skip(1)
+                .filter_map(|l| tag::tags_string(&l.path))
+                .map(|tags| tags.chars().count())
+                .max()
+                .unwrap_or(0)
+                .min(MAX_TAGS_LEN)
+        } else {
+            0
+        };
+
         // we compute the length of the dates, depending on the format
         let date_len = if tree.options.show_dates {
             let date_time: DateTime<Local> = Local::now();
@@ -457,6 +583,13 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
             0 // we don't care
         };
 
+        // ancestors of the first visible lines, drawn over them
+        let sticky_lines = if self.in_app {
+            tree.sticky_lines()
+        } else {
+            Vec::new()
+        };
+
         for y in 1..self.area.height {
             if self.in_app {
                 f.queue(cursor::MoveTo(self.area.left, y + self.area.top))?;
@@ -467,12 +600,16 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
             if line_index > 0 {
                 line_index += tree.scroll as usize;
             }
+            if let Some(&sticky_index) = sticky_lines.get(y as usize - 1) {
+                line_index = sticky_index;
+            }
             let mut selected = false;
             let mut cw = CropWriter::new(f, self.area.width as usize);
             let cw = &mut cw;
             if line_index < tree.lines.len() {
                 let line = &tree.lines[line_index];
-                selected = self.in_app && line_index == tree.selection;
+                selected = self.in_app && self.show_selection
+                    && (line_index == tree.selection || tree.is_in_drag_range(line_index));
                 let label_style = self.label_style(line, selected);
                 let mut in_branch = false;
                 let space_style = if selected {
@@ -510,7 +647,7 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
                         }
 
                         Col::Date => {
-                            if let Some(seconds) = line.sum.and_then(|sum| sum.to_valid_seconds()) {
+                            if let Some(seconds) = line.sum.and_then(|sum| sum.to_valid_seconds(tree.options.date_kind)) {
                                 self.write_date(cw, seconds, selected)?
                             } else {
                                 date_len + 1
@@ -518,7 +655,7 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
                         }
 
                         Col::Size => {
-                            if tree.options.sort.is_some() {
+                            if tree.options.sort.is_some() && !self.ascii {
                                 // as soon as there's only one level displayed we can show the size bars
                                 self.write_line_size_with_bar(cw, line, &label_style, total_size, selected)?
                             } else {
@@ -534,6 +671,10 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
                             self.write_line_stage_mark(cw, &label_style, staged)?
                         }
 
+                        Col::Tags => {
+                            self.write_line_tags(cw, line, tags_len, selected)?
+                        }
+
                         Col::Name => {
                             in_branch = false;
                             self.write_line_label(cw, line, &label_style, pattern_object, selected)?
@@ -543,7 +684,8 @@ impl<'a, 's, 't> DisplayableTree<'a, 's, 't> {
                     // void: intercol & replacing missing cells
                     if in_branch && void_len > 2 {
                         cond_bg!(void_style, self, selected, &self.skin.tree);
-                        cw.repeat(void_style, &BRANCH_FILLING, void_len)?;
+                        let filling: &Filling = if self.ascii { &ASCII_BRANCH_FILLING } else { &BRANCH_FILLING };
+                        cw.repeat(void_style, filling, void_len)?;
                     } else {
                         cond_bg!(void_style, self, selected, &self.skin.default);
                         cw.repeat(void_style, &SPACE_FILLING, void_len)?;
@@ -7,10 +7,15 @@ use {
     },
 };
 
+/// shown when the root is in a linked worktree
+static WORKTREE_MARK: &str = "(worktree) ";
+
 pub struct GitStatusDisplay<'a, 's> {
     status: &'a TreeGitStatus,
     skin: &'s StyleMap,
     show_branch: bool,
+    ahead_behind: Option<String>,
+    show_worktree: bool,
     show_wide: bool,
     show_stats: bool,
     pub width: usize,
@@ -27,6 +32,29 @@ impl<'a, 's> GitStatusDisplay<'a, 's> {
                 show_branch = true;
             }
         }
+        // ahead/behind counts are only shown when the branch isn't in sync
+        let mut ahead_behind = None;
+        if show_branch {
+            if let Some((ahead, behind)) = status.ahead_behind {
+                let mut ab = String::new();
+                if ahead > 0 {
+                    ab.push_str(&format!("↑{}", ahead));
+                }
+                if behind > 0 {
+                    ab.push_str(&format!("↓{}", behind));
+                }
+                let ab_width = ab.chars().count() + 1;
+                if ahead + behind > 0 && width + ab_width < available_width {
+                    width += ab_width;
+                    ahead_behind = Some(ab);
+                }
+            }
+        }
+        let show_worktree = status.is_linked_worktree
+            && width + WORKTREE_MARK.len() < available_width;
+        if show_worktree {
+            width += WORKTREE_MARK.len();
+        }
         let mut show_stats = false;
         let unstyled_stats = format!("+{}-{}", status.insertions, status.deletions);
         let stats_width = unstyled_stats.len();
@@ -42,6 +70,8 @@ impl<'a, 's> GitStatusDisplay<'a, 's> {
             status,
             skin,
             show_branch,
+            ahead_behind,
+            show_worktree,
             show_stats,
             show_wide,
             width,
@@ -67,6 +97,14 @@ impl<'a, 's> GitStatusDisplay<'a, 's> {
                 cw.queue_str(&branch_style, name)?;
                 cw.queue_char(&branch_style, ' ')?;
             }
+            if let Some(ab) = &self.ahead_behind {
+                cw.queue_str(branch_style, ab)?;
+                cw.queue_char(branch_style, ' ')?;
+            }
+        }
+        if self.show_worktree {
+            cond_bg!(worktree_style, self, selected, self.skin.git_branch);
+            cw.queue_str(worktree_style, WORKTREE_MARK)?;
         }
         if self.show_stats {
             cond_bg!(insertions_style, self, selected, self.skin.git_insertions);
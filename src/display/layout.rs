@@ -0,0 +1,125 @@
+use {
+    super::MINIMAL_PANEL_WIDTH,
+    ahash::AHashMap,
+};
+
+/// the relative widths of the panels, as chosen by the user with
+/// `:layout`, `:panel_grow` or `:panel_shrink`, for each number of
+/// panels.
+///
+/// When nothing was chosen for the current number of panels, the
+/// width is evenly shared (the last panel being slightly larger
+/// when it's a preview).
+#[derive(Debug, Clone, Default)]
+pub struct Layout {
+    weights: AHashMap<usize, Vec<u16>>,
+}
+
+impl Layout {
+
+    /// apply a preset, which can be "even", "three-column", or
+    /// relative widths like "70/30".
+    ///
+    /// "even" applies to the current number of panels, the other
+    /// presets to the number of panels they describe.
+    pub fn apply_preset(&mut self, preset: &str, nb_panels: usize) -> Result<(), String> {
+        let preset = preset.trim();
+        let weights: Vec<u16> = match preset {
+            "even" => {
+                self.weights.remove(&nb_panels);
+                return Ok(());
+            }
+            "three-column" => vec![1, 1, 1],
+            _ => preset
+                .split('/')
+                .map(|s| s.trim().parse::<u16>().ok().filter(|&w| w > 0))
+                .collect::<Option<Vec<u16>>>()
+                .ok_or_else(|| format!("invalid layout: {:?}", preset))?,
+        };
+        if weights.len() < 2 {
+            return Err(format!("a layout needs at least two panels: {:?}", preset));
+        }
+        self.weights.insert(weights.len(), weights);
+        Ok(())
+    }
+
+    /// change the width of a panel by `delta` columns, taking them from
+    /// (or giving them to) the other panels, starting with the largest
+    /// (resp. smallest) one.
+    ///
+    /// `widths` are the current widths of the panels.
+    pub fn resize_panel(
+        &mut self,
+        mut widths: Vec<u16>,
+        panel_idx: usize,
+        delta: i32,
+    ) -> Result<(), &'static str> {
+        if widths.len() < 2 || panel_idx >= widths.len() {
+            return Err("there's no other panel");
+        }
+        let others = (0..widths.len()).filter(|&i| i != panel_idx);
+        let step = delta.unsigned_abs() as u16;
+        if delta > 0 {
+            let donor = others.max_by_key(|&i| widths[i]).unwrap();
+            if widths[donor] < MINIMAL_PANEL_WIDTH + step {
+                return Err("no room to grow the panel");
+            }
+            widths[donor] -= step;
+            widths[panel_idx] += step;
+        } else {
+            if widths[panel_idx] < MINIMAL_PANEL_WIDTH + step {
+                return Err("the panel can't be smaller");
+            }
+            let receiver = others.min_by_key(|&i| widths[i]).unwrap();
+            widths[panel_idx] -= step;
+            widths[receiver] += step;
+        }
+        self.weights.insert(widths.len(), widths);
+        Ok(())
+    }
+
+    /// compute the widths of the panels, or None if there's no
+    /// chosen layout for this number of panels or if it doesn't
+    /// fit the total width
+    pub fn widths(&self, nb_panels: usize, total_width: u16) -> Option<Vec<u16>> {
+        let weights = self.weights.get(&nb_panels)?;
+        let sum: u32 = weights.iter().map(|&w| w as u32).sum();
+        let mut widths: Vec<u16> = weights
+            .iter()
+            .map(|&w| (w as u32 * total_width as u32 / sum) as u16)
+            .collect();
+        // the rounding remainder goes to the last panel
+        let used: u16 = widths.iter().sum();
+        widths[nb_panels - 1] += total_width - used;
+        if widths.iter().any(|&w| w < MINIMAL_PANEL_WIDTH) {
+            return None;
+        }
+        Some(widths)
+    }
+}
+
+#[cfg(test)]
+mod layout_test {
+
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        let mut layout = Layout::default();
+        assert_eq!(layout.widths(2, 100), None);
+        layout.apply_preset("70/30", 2).unwrap();
+        assert_eq!(layout.widths(2, 100), Some(vec![70, 30]));
+        assert_eq!(layout.widths(3, 100), None);
+        layout.apply_preset("three-column", 2).unwrap();
+        assert_eq!(layout.widths(3, 100), Some(vec![33, 33, 34]));
+        assert!(layout.apply_preset("70/a", 2).is_err());
+        assert!(layout.apply_preset("100", 2).is_err());
+        layout.resize_panel(vec![70, 30], 1, 5).unwrap();
+        assert_eq!(layout.widths(2, 100), Some(vec![65, 35]));
+        assert!(layout.resize_panel(vec![65, 35], 1, -20).is_err());
+        layout.resize_panel(vec![65, 35], 0, -10).unwrap();
+        assert_eq!(layout.widths(2, 100), Some(vec![55, 45]));
+        layout.apply_preset("even", 2).unwrap();
+        assert_eq!(layout.widths(2, 100), None);
+    }
+}
@@ -30,6 +30,25 @@ impl<'a, 'w> MatchedString<'a> {
             align: Alignment::Left,
         }
     }
+    /// remove the first n chars of the string (all of them if it's
+    /// shorter), returning whether there was something to remove
+    pub fn skip_chars(&mut self, n: usize) -> bool {
+        if n == 0 || self.string.is_empty() {
+            return false;
+        }
+        let (byte_idx, skipped) = match self.string.char_indices().nth(n) {
+            Some((byte_idx, _)) => (byte_idx, n),
+            None => (self.string.len(), self.string.chars().count()),
+        };
+        self.string = &self.string[byte_idx..];
+        if let Some(m) = &mut self.name_match {
+            m.pos = m.pos.iter()
+                .filter(|&&p| p >= skipped)
+                .map(|&p| p - skipped)
+                .collect();
+        }
+        true
+    }
     pub fn fill(&mut self, width: usize, align: Alignment) {
         self.display_width = Some(width);
         self.align = align;
@@ -96,3 +115,25 @@ impl<'a, 'w> MatchedString<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod matched_string_test {
+
+    use {
+        super::*,
+        smallvec::smallvec,
+    };
+
+    #[test]
+    fn test_skip_chars() {
+        let style = CompoundStyle::default();
+        let name_match = NameMatch { score: 1, pos: smallvec![0, 3, 5] };
+        let mut ms = MatchedString::new(Some(name_match), "éabcdé", &style, &style);
+        assert!(ms.skip_chars(2));
+        assert_eq!(ms.string, "bcdé");
+        assert_eq!(ms.name_match.as_ref().unwrap().pos.as_slice(), &[1, 3]);
+        assert!(ms.skip_chars(10));
+        assert_eq!(ms.string, "");
+        assert!(!ms.skip_chars(1));
+    }
+}
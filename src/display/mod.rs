@@ -26,6 +26,7 @@ mod crop_writer;
 mod displayable_tree;
 mod filling;
 mod git_status_display;
+mod layout;
 pub mod flags_display;
 pub mod status_line;
 mod matched_string;
@@ -36,12 +37,13 @@ mod cell_size;
 mod permissions;
 
 pub use {
-    areas::Areas,
+    areas::{Areas, MINIMAL_PANEL_WIDTH},
     col::*,
     crop_writer::CropWriter,
     displayable_tree::DisplayableTree,
     filling::*,
     git_status_display::GitStatusDisplay,
+    layout::Layout,
     matched_string::MatchedString,
     screen::Screen,
     cell_size::*,
@@ -69,6 +71,7 @@ pub static TAB_REPLACEMENT: &str = "  ";
 lazy_static! {
     pub static ref SPACE_FILLING: Filling = Filling::from_char(' ');
     pub static ref BRANCH_FILLING: Filling = Filling::from_char('─');
+    pub static ref ASCII_BRANCH_FILLING: Filling = Filling::from_char('-');
 }
 
 /// if true then the status of a panel covers the whole width
@@ -107,7 +107,7 @@ impl<'s> PermWriter<'s> {
         line: &TreeLine,
         selected: bool,
     ) -> Result<usize, ProgramError> {
-        Ok(if line.is_selectable() {
+        Ok(if !line.is_pruning() {
             self.write_mode(cw, line.mode(), selected)?;
             let owner = permissions::user_name(line.metadata.uid());
             cond_bg!(owner_style, self, selected, self.skin.owner);
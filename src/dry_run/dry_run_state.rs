@@ -0,0 +1,196 @@
+use {
+    crate::{
+        app::*,
+        command::*,
+        display::{CropWriter, Screen, SPACE_FILLING, W},
+        errors::ProgramError,
+        tree::TreeOptions,
+        verb::*,
+    },
+    crossterm::{
+        cursor,
+        QueueableCommand,
+    },
+    std::path::Path,
+    termimad::Area,
+};
+
+static TITLE: &str = "Dry Run"; // no wide char allowed here
+
+/// a panel listing what the verbs modifying files would have
+/// done, if broot hadn't been launched with `--dry-run`
+pub struct DryRunState {
+
+    scroll: usize,
+
+    /// number of actions at last display, to scroll
+    /// to the new ones when they come
+    nb_actions: usize,
+
+    /// those options are only kept for transmission to child state
+    tree_options: TreeOptions,
+
+    /// the 'modal' mode
+    mode: Mode,
+
+    page_height: usize,
+}
+
+impl DryRunState {
+
+    pub fn new(
+        tree_options: TreeOptions,
+        con: &AppContext,
+    ) -> DryRunState {
+        Self {
+            scroll: 0,
+            nb_actions: 0,
+            tree_options,
+            mode: initial_mode(con),
+            page_height: 0,
+        }
+    }
+
+    pub fn try_scroll(
+        &mut self,
+        cmd: ScrollCommand,
+    ) -> bool {
+        let old_scroll = self.scroll;
+        self.scroll = cmd.apply(self.scroll, self.nb_actions, self.page_height);
+        self.scroll != old_scroll
+    }
+
+    /// scroll so that the last actions are visible
+    fn scroll_to_end(&mut self) {
+        self.scroll = self.nb_actions.saturating_sub(self.page_height);
+    }
+}
+
+impl PanelState for DryRunState {
+
+    fn get_type(&self) -> PanelStateType {
+        PanelStateType::DryRun
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    fn get_mode(&self) -> Mode {
+        self.mode
+    }
+
+    fn selected_path(&self) -> Option<&Path> {
+        None
+    }
+
+    fn selection(&self) -> Option<Selection<'_>> {
+        None
+    }
+
+    fn tree_options(&self) -> TreeOptions {
+        self.tree_options.clone()
+    }
+
+    fn with_new_options(
+        &mut self,
+        _screen: Screen,
+        change_options: &dyn Fn(&mut TreeOptions),
+        _in_new_panel: bool,
+        _con: &AppContext,
+    ) -> CmdResult {
+        change_options(&mut self.tree_options);
+        CmdResult::Keep
+    }
+
+    fn refresh(&mut self, _screen: Screen, _con: &AppContext) -> Command {
+        Command::empty()
+    }
+
+    fn display(
+        &mut self,
+        w: &mut W,
+        disc: &DisplayContext,
+    ) -> Result<(), ProgramError> {
+        let actions = &disc.app_state.dry_run_actions;
+        let area = &disc.state_area;
+        let styles = &disc.panel_skin.styles;
+        let width = area.width as usize;
+        let list_area = Area::new(area.left, area.top + 1, area.width, area.height - 1);
+        let page_height = list_area.height as usize;
+        if actions.len() != self.nb_actions || page_height != self.page_height {
+            self.nb_actions = actions.len();
+            self.page_height = page_height;
+            self.scroll_to_end();
+        }
+        //- title
+        w.queue(cursor::MoveTo(area.left, area.top))?;
+        let mut cw = CropWriter::new(w, width);
+        let count = format!("{}", actions.len());
+        if TITLE.len() + 1 + count.len() <= cw.allowed {
+            cw.queue_str(&styles.staging_area_title, TITLE)?;
+        }
+        if count.len() <= cw.allowed {
+            cw.repeat(&styles.staging_area_title, &SPACE_FILLING, cw.allowed - count.len())?;
+            cw.queue_g_string(&styles.staging_area_title, count)?;
+        }
+        cw.fill(&styles.staging_area_title, &SPACE_FILLING)?;
+        //- actions
+        let scrollbar = list_area.scrollbar(self.scroll as i32, actions.len() as i32);
+        for idx in 0..self.page_height {
+            let y = list_area.top + idx as u16;
+            w.queue(cursor::MoveTo(area.left, y))?;
+            let mut cw = CropWriter::new(w, width - 1); // -1 for scrollbar
+            if let Some(action) = actions.get(idx + self.scroll) {
+                cw.queue_str(&styles.default, action)?;
+            }
+            cw.fill(&styles.default, &SPACE_FILLING)?;
+            let scrollbar_style = if ScrollCommand::is_thumb(y, scrollbar) {
+                &styles.scrollbar_thumb
+            } else {
+                &styles.scrollbar_track
+            };
+            scrollbar_style.queue_str(w, "▐")?;
+        }
+        Ok(())
+    }
+
+    fn on_internal(
+        &mut self,
+        w: &mut W,
+        internal_exec: &InternalExecution,
+        input_invocation: Option<&VerbInvocation>,
+        trigger_type: TriggerType,
+        app_state: &mut AppState,
+        cc: &CmdContext,
+    ) -> Result<CmdResult, ProgramError> {
+        Ok(match internal_exec.internal {
+            Internal::line_down | Internal::line_down_no_cycle | Internal::scroll_down => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.try_scroll(ScrollCommand::Lines(count));
+                CmdResult::Keep
+            }
+            Internal::line_up | Internal::line_up_no_cycle | Internal::scroll_up => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.try_scroll(ScrollCommand::Lines(-count));
+                CmdResult::Keep
+            }
+            Internal::page_down => {
+                self.try_scroll(ScrollCommand::Pages(1));
+                CmdResult::Keep
+            }
+            Internal::page_up => {
+                self.try_scroll(ScrollCommand::Pages(-1));
+                CmdResult::Keep
+            }
+            _ => self.on_internal_generic(
+                w,
+                internal_exec,
+                input_invocation,
+                trigger_type,
+                app_state,
+                cc,
+            )?,
+        })
+    }
+}
@@ -0,0 +1,3 @@
+mod dry_run_state;
+
+pub use dry_run_state::DryRunState;
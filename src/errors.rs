@@ -28,6 +28,9 @@ custom_error! {pub ProgramError
     ImageError {source: ImageError } = "{}",
     Lfs {details: String} = "Failed to fetch mounts: {}",
     ZeroLenFile = "File seems empty",
+    Git {details: String} = "Git error: {details}",
+    Pattern {source: PatternError} = "Invalid pattern: {source}",
+    EmptyPathList = "No existing path in the given list",
 }
 
 custom_error! {pub TreeBuildError
@@ -44,13 +47,19 @@ custom_error! {pub ConfError
     MissingField {txt: String}                      = "missing field in conf",
     InvalidVerbInvocation {invocation: String}      = "invalid verb invocation: {}",
     InvalidVerbConf {details: String}               = "invalid verb conf: {}",
+    InvalidOpenerConf {details: String}             = "invalid opener conf: {details}",
     UnknownInternal {verb: String}                  = "not a known internal: {}",
     InvalidSearchMode {details: String}             = "invalid search mode: {}",
     InvalidKey {raw: String}                        = "not a valid key: {}",
     ReservedKey {key: String}                       = "reserved key: {}",
+    KeyConflict {details: String}                   = "conflicting key bindings: {}",
     UnexpectedInternalArg {invocation: String}      = "unexpected argument for internal: {}",
     InvalidCols {details: String}                   = "invalid cols definition: {}",
     InvalidSkin {source: InvalidSkinError}          = "invalid skin: {}",
+    InvalidFlags {flags: String, details: String}   = "invalid flags {flags:?}: {details}",
+    InvalidDateKind {kind: String}                  = "invalid date kind {kind:?}, expected modified, changed or created",
+    InvalidPreviewMode {mode: String}               = "invalid preview mode {mode:?}, expected image, text, unstyled or hex",
+    InvalidLaunchArg {arg: String, value: String, expected: String} = "invalid value {value:?} for --{arg}, expected {expected}",
 }
 
 // error which can be raised when parsing a pattern the user typed
@@ -60,6 +69,7 @@ custom_error! {pub PatternError
         format!("Invalid Regular Expression: {}", source.to_string().lines().last().unwrap_or(""))
     },
     UnknownRegexFlag {bad: char} = "Unknown regular expression flag: {:?}",
+    InvalidPermissions {raw: String} = "Invalid permissions predicate: {raw:?} (expected eg `+x` or `o+w`)",
 }
 
 custom_error! {pub InvalidSkinError
@@ -0,0 +1,78 @@
+use {
+    crossterm::{
+        self,
+        event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind},
+    },
+};
+
+/// a valid user event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+
+    Key(KeyEvent),
+
+    Click(u16, u16, KeyModifiers),
+
+    RightClick(u16, u16, KeyModifiers),
+
+    DoubleClick(u16, u16),
+
+    /// the mouse moved with the left button pressed since start
+    Drag {
+        start: (u16, u16),
+        pos: (u16, u16),
+    },
+
+    /// the left button was released at the end of a drag
+    DragEnd {
+        start: (u16, u16),
+        pos: (u16, u16),
+    },
+
+    /// terminal was resized. Contains the new dimensions
+    Resize(u16, u16),
+
+    /// mouse wheel turns. contains -1 if up or 1 if down
+    Wheel(i32),
+}
+
+impl Event {
+    /// convert a crossterm event into a broot one, when it's
+    /// neither part of a drag nor a double-click (those need
+    /// the state of the event source).
+    ///
+    /// \r and \n are normalized into Enter
+    pub fn from_crossterm_event(
+        crossterm_event: crossterm::event::Event,
+    ) -> Option<Event> {
+        match crossterm_event {
+            crossterm::event::Event::Key(mut key) => {
+                if key.code == KeyCode::Char('\r') || key.code == KeyCode::Char('\n') {
+                    key.code = KeyCode::Enter;
+                }
+                Some(Event::Key(key))
+            }
+            crossterm::event::Event::Resize(w, h) => Some(Event::Resize(w, h)),
+            crossterm::event::Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Up(MouseButton::Left) => {
+                    Some(Event::Click(mouse.column, mouse.row, mouse.modifiers))
+                }
+                MouseEventKind::Up(MouseButton::Right) => {
+                    Some(Event::RightClick(mouse.column, mouse.row, mouse.modifiers))
+                }
+                MouseEventKind::ScrollUp => Some(Event::Wheel(-1)),
+                MouseEventKind::ScrollDown => Some(Event::Wheel(1)),
+                _ => None,
+            },
+        }
+    }
+
+    /// the position where the drag started, if the event
+    /// is part of a drag
+    pub fn drag_start(self) -> Option<(u16, u16)> {
+        match self {
+            Event::Drag { start, .. } | Event::DragEnd { start, .. } => Some(start),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use {
+    super::Event,
+    crate::errors::ProgramError,
+    crossbeam::channel::{unbounded, Receiver, Sender},
+    crossterm::{
+        self,
+        event::{MouseButton, MouseEventKind},
+        terminal,
+    },
+    std::{
+        thread,
+        time::{Duration, Instant},
+    },
+};
+
+const DOUBLE_CLICK_MAX_DURATION: Duration = Duration::from_millis(700);
+
+struct TimedClick {
+    time: Instant,
+    x: u16,
+    y: u16,
+}
+
+/// the state of the left mouse button, between its press
+/// and its release
+struct Press {
+    start: (u16, u16),
+    dragged: bool,
+}
+
+/// a thread backed event listener emitting events on a channel.
+///
+/// It works like the termimad one but also reports the drags
+/// of the left mouse button.
+pub struct EventSource {
+    rx_events: Receiver<Event>,
+    tx_quit: Sender<bool>,
+}
+
+impl EventSource {
+    /// create a new source
+    ///
+    /// If desired, mouse support must be enabled and disabled in crossterm.
+    pub fn new() -> Result<EventSource, ProgramError> {
+        let (tx_events, rx_events) = unbounded();
+        let (tx_quit, rx_quit) = unbounded();
+        terminal::enable_raw_mode()?;
+        thread::spawn(move || {
+            let mut last_click: Option<TimedClick> = None;
+            let mut press: Option<Press> = None;
+            // return true when we must close the source
+            let send_and_wait = |event| {
+                if tx_events.send(event).is_err() {
+                    true // broken channel
+                } else {
+                    !matches!(rx_quit.recv(), Ok(false))
+                }
+            };
+            loop {
+                let ct_event = match crossterm::event::read() {
+                    Ok(e) => e,
+                    _ => { continue; }
+                };
+                let mut event = None;
+                if let crossterm::event::Event::Mouse(mouse) = ct_event {
+                    let pos = (mouse.column, mouse.row);
+                    match mouse.kind {
+                        MouseEventKind::Down(MouseButton::Left) => {
+                            press = Some(Press { start: pos, dragged: false });
+                        }
+                        MouseEventKind::Drag(MouseButton::Left) => {
+                            if let Some(press) = press.as_mut() {
+                                press.dragged = true;
+                                event = Some(Event::Drag { start: press.start, pos });
+                            }
+                        }
+                        MouseEventKind::Up(MouseButton::Left) => {
+                            if let Some(Press { start, dragged: true }) = press.take() {
+                                last_click = None;
+                                event = Some(Event::DragEnd { start, pos });
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(mut event) = event.or_else(|| Event::from_crossterm_event(ct_event)) {
+                    // a click may be changed into a double-click
+                    if let Event::Click(x, y, ..) = event {
+                        if let Some(TimedClick { time, x: last_x, y: last_y }) = last_click {
+                            if
+                                last_x == x && last_y == y
+                                && time.elapsed() < DOUBLE_CLICK_MAX_DURATION
+                            {
+                                event = Event::DoubleClick(x, y);
+                            }
+                        }
+                        last_click = Some(TimedClick { time: Instant::now(), x, y });
+                    }
+                    // we send the event to the receiver in the main event loop
+                    if send_and_wait(event) {
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(EventSource {
+            rx_events,
+            tx_quit,
+        })
+    }
+
+    /// either start listening again, or quit, depending on the passed bool.
+    /// It's mandatory to call this with quit=true at end for a proper ending
+    /// of the thread (and its resources)
+    pub fn unblock(&self, quit: bool) {
+        self.tx_quit.send(quit).unwrap();
+    }
+
+    /// return a new receiver for the channel emitting events
+    pub fn receiver(&self) -> Receiver<Event> {
+        self.rx_events.clone()
+    }
+}
+
+impl Drop for EventSource {
+    fn drop(&mut self) {
+        terminal::disable_raw_mode().unwrap();
+    }
+}
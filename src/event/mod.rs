@@ -0,0 +1,12 @@
+//! The user events, read from the terminal in a dedicated thread.
+//!
+//! Those events are the ones of termimad, with the addition of
+//! the mouse drags which termimad doesn't report.
+
+mod event;
+mod event_source;
+
+pub use {
+    event::Event,
+    event_source::EventSource,
+};
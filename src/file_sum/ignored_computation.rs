@@ -0,0 +1,127 @@
+use {
+    super::{
+        sum_computation::{is_ignored, special_paths_of},
+        FileSum,
+    },
+    crate::{
+        app::*,
+        git::{self, GitIgnorer},
+        task_sync::Dam,
+    },
+    ahash::AHashMap,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+/// compute the size of the gitignored content of a directory, walking
+/// the not ignored directories and measuring the ignored entries as wholes.
+///
+/// The first level subdirectories already computed are taken from the cache.
+/// Return None if the computation was interrupted by an event.
+pub fn compute_ignored_size(
+    path: &Path,
+    cache: &AHashMap<PathBuf, u64>,
+    dam: &Dam,
+    con: &AppContext,
+) -> Option<u64> {
+    if git::why_ignored(path).is_some() {
+        // the directory itself is ignored, so is all its content
+        return FileSum::from_dir(path, dam, con).map(FileSum::to_size);
+    }
+    let mut ignorer = GitIgnorer::default();
+    let root_chain = ignorer.root_chain(path);
+    if !root_chain.in_repo() {
+        // outside a repository, nothing is gitignored
+        return Some(0);
+    }
+    let special_paths = special_paths_of(path, con);
+    let mut size = 0;
+    let mut dirs = vec![(path.to_path_buf(), root_chain)];
+    let mut first_level = true;
+    while let Some((dir, chain)) = dirs.pop() {
+        if dam.has_event() {
+            return None;
+        }
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for e in entries.flatten() {
+            let is_dir = match e.file_type() {
+                Ok(ft) => ft.is_dir(),
+                Err(_) => continue,
+            };
+            let entry_path = e.path();
+            if is_ignored(&entry_path, &special_paths) {
+                continue;
+            }
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            if ignorer.accepts(&chain, &entry_path, &name, is_dir) {
+                if is_dir {
+                    if first_level {
+                        if let Some(entry_size) = cache.get(&entry_path) {
+                            size += entry_size;
+                            continue;
+                        }
+                    }
+                    let entry_chain = ignorer.deeper_chain(&chain, &entry_path);
+                    dirs.push((entry_path, entry_chain));
+                }
+            } else if is_dir {
+                size += FileSum::from_dir(&entry_path, dam, con)?.to_size();
+            } else {
+                size += FileSum::from_file(&entry_path).to_size();
+            }
+        }
+        first_level = false;
+    }
+    Some(size)
+}
+
+#[cfg(test)]
+mod ignored_computation_test {
+
+    use {
+        super::*,
+        crate::search::Searcher,
+    };
+
+    #[test]
+    fn check_ignored_size() {
+        let searcher = Searcher::new().unwrap();
+        let con = searcher.context();
+        let dam = Dam::unlimited();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        let empty = AHashMap::default();
+        // not yet a repository: nothing is ignored
+        assert_eq!(compute_ignored_size(root, &empty, &dam, con), Some(0));
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::write(root.join("target/debug/app"), vec![0u8; 50_000]).unwrap();
+        fs::write(root.join("src/notes.log"), "some logs").unwrap();
+        fs::write(root.join(".gitignore"), "target\n*.log\n").unwrap();
+        let expected = FileSum::from_dir(&root.join("target"), &dam, con).unwrap().to_size()
+            + FileSum::from_file(&root.join("src/notes.log")).to_size();
+        assert!(expected > 0);
+        assert_eq!(compute_ignored_size(root, &empty, &dam, con), Some(expected));
+        // the content of an ignored directory is entirely ignored
+        let target_size = FileSum::from_dir(&root.join("target"), &dam, con).unwrap().to_size();
+        assert_eq!(
+            compute_ignored_size(&root.join("target"), &empty, &dam, con),
+            Some(target_size),
+        );
+        // the sizes of first level directories are taken from the cache
+        let mut cache = AHashMap::default();
+        cache.insert(root.join("src"), 7);
+        assert_eq!(
+            compute_ignored_size(root, &cache, &dam, con),
+            Some(FileSum::from_dir(&root.join("target"), &dam, con).unwrap().to_size() + 7),
+        );
+    }
+}
@@ -2,12 +2,14 @@
 /// A cache is used to avoid recomputing the same directories again and again.
 /// On unix, hard links are checked to avoid counting twice an inode.
 
+mod ignored_computation;
 mod sum_computation;
 
 use {
     crate::{
         app::*,
         task_sync::Dam,
+        tree::DateKind,
     },
     ahash::AHashMap,
     std::{
@@ -20,20 +22,72 @@ use {
 lazy_static! {
     static ref SUM_CACHE_MUTEX: Mutex<AHashMap<PathBuf, FileSum>> =
         Mutex::new(AHashMap::default());
+    static ref IGNORED_CACHE_MUTEX: Mutex<AHashMap<PathBuf, u64>> =
+        Mutex::new(AHashMap::default());
 }
 
 pub fn clear_cache() {
     let mut sum_cache = SUM_CACHE_MUTEX.lock().unwrap();
     sum_cache.clear();
+    let mut ignored_cache = IGNORED_CACHE_MUTEX.lock().unwrap();
+    ignored_cache.clear();
+}
+
+/// Return the size of the gitignored content of the directory, either
+///  by computing it or by fetching it from cache.
+/// If the lifetime expires before complete computation, None is returned.
+pub fn ignored_size(path: &Path, dam: &Dam, con: &AppContext) -> Option<u64> {
+    let mut ignored_cache = IGNORED_CACHE_MUTEX.lock().unwrap();
+    match ignored_cache.get(path) {
+        Some(size) => Some(*size),
+        None => {
+            let size = time!(
+                "ignored size computation",
+                path,
+                ignored_computation::compute_ignored_size(path, &ignored_cache, dam, con),
+            );
+            if let Some(size) = size {
+                ignored_cache.insert(PathBuf::from(path), size);
+            }
+            size
+        }
+    }
+}
+
+/// The timestamps of a file, in seconds from Epoch, each one
+/// being 0 when unknown. For a directory, they're the most recent
+/// ones of its content.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct FileDates {
+    pub modified: u32,
+    pub changed: u32,
+    pub created: u32,
+}
+
+impl FileDates {
+    pub fn get(self, kind: DateKind) -> u32 {
+        match kind {
+            DateKind::Modified => self.modified,
+            DateKind::Changed => self.changed,
+            DateKind::Created => self.created,
+        }
+    }
+    fn max(self, other: Self) -> Self {
+        Self {
+            modified: self.modified.max(other.modified),
+            changed: self.changed.max(other.changed),
+            created: self.created.max(other.created),
+        }
+    }
 }
 
 /// Reduction of counts, dates and sizes on a file or directory
 #[derive(Debug, Copy, Clone)]
 pub struct FileSum {
-    real_size: u64, // bytes, the space it takes on disk
-    count: usize,   // number of files
-    modified: u32,  // seconds from Epoch to last modification, or 0 if there was an error
-    sparse: bool,   // only for non directories: tells whether the file is sparse
+    real_size: u64,   // bytes, the space it takes on disk
+    count: usize,     // number of files
+    dates: FileDates, // last modification, change and creation
+    sparse: bool,     // only for non directories: tells whether the file is sparse
 }
 
 impl FileSum {
@@ -41,13 +95,13 @@ impl FileSum {
         real_size: u64,
         sparse: bool,
         count: usize,
-        modified: u32,
+        dates: FileDates,
     ) -> Self {
-        Self { real_size, sparse, count, modified }
+        Self { real_size, sparse, count, dates }
     }
 
     pub fn zero() -> Self {
-        Self::new(0, false, 0, 0)
+        Self::new(0, false, 0, FileDates::default())
     }
 
     pub fn incr(&mut self) {
@@ -92,20 +146,19 @@ impl FileSum {
     pub fn to_count(self) -> usize {
         self.count
     }
-    /// return the number of seconds from Epoch to last modification,
-    /// or 0 if the computation failed
-    pub fn to_seconds(self) -> u32 {
-        self.modified
+    /// return the number of seconds from Epoch to the date of
+    /// the given kind, or 0 if the computation failed
+    pub fn to_seconds(self, kind: DateKind) -> u32 {
+        self.dates.get(kind)
     }
     /// return the size in bytes
     pub fn to_size(self) -> u64 {
         self.real_size
     }
-    pub fn to_valid_seconds(self) -> Option<i64> {
-        if self.modified != 0 {
-            Some(self.modified as i64)
-        } else {
-            None
+    pub fn to_valid_seconds(self, kind: DateKind) -> Option<i64> {
+        match self.dates.get(kind) {
+            0 => None,
+            seconds => Some(seconds as i64),
         }
     }
     /// tell whether the file has holes (in which case the size displayed by
@@ -123,7 +176,7 @@ impl AddAssign for FileSum {
             self.real_size + other.real_size,
             self.sparse | other.sparse,
             self.count + other.count,
-            self.modified.max(other.modified),
+            self.dates.max(other.dates),
         );
     }
 }
@@ -1,7 +1,8 @@
 use {
-    super::FileSum,
+    super::{FileDates, FileSum},
     crate::{
         app::*,
+        conf::LocalConf,
         path::*,
         task_sync::Dam,
     },
@@ -43,13 +44,27 @@ const THREADS_COUNT: usize = 6;
 
 
 #[inline(always)]
-fn is_ignored(path: &Path, special_paths: &[SpecialPath]) -> bool {
+pub(super) fn is_ignored(path: &Path, special_paths: &[SpecialPath]) -> bool {
     match special_paths.find(path) {
         SpecialHandling::NoEnter | SpecialHandling::Hide => true,
-        SpecialHandling::None | SpecialHandling::Enter => false,
+        SpecialHandling::None
+        | SpecialHandling::Enter
+        | SpecialHandling::NoRecurse
+        | SpecialHandling::MaxDepth(_) => false,
     }
 }
 
+/// return the special paths which may apply to the directory: the
+/// ones of its local configuration (see `.broot.toml`), which have
+/// precedence, then the ones of the general configuration
+pub(super) fn special_paths_of(dir: &Path, con: &AppContext) -> Vec<SpecialPath> {
+    let mut special_paths = LocalConf::find(dir)
+        .map(|local_conf| local_conf.special_paths)
+        .unwrap_or_default();
+    special_paths.extend(con.special_paths.iter().cloned());
+    special_paths
+}
+
 /// compute the consolidated numbers for a directory, with implementation
 /// varying depending on the OS:
 /// On unix, the computation is done on blocks of 512 bytes
@@ -62,7 +77,8 @@ pub fn compute_dir_sum(
 ) -> Option<FileSum> {
     //debug!("compute size of dir {:?} --------------- ", path);
 
-    if is_ignored(path, &con.special_paths) {
+    let special_paths = special_paths_of(path, con);
+    if is_ignored(path, &special_paths) {
         return Some(FileSum::zero());
     }
 
@@ -86,9 +102,8 @@ pub fn compute_dir_sum(
     // A None means there's nothing left and the thread may send its result and stop
     let (dirs_sender, dirs_receiver) = channel::unbounded();
 
-    let special_paths: Vec<SpecialPath> = con.special_paths.iter()
+    let special_paths: Vec<SpecialPath> = special_paths.into_iter()
         .filter(|sp| sp.can_have_matches_in(path))
-        .cloned()
         .collect();
 
     // the first level is managed a little differently: we look at the cache
@@ -142,6 +157,10 @@ pub fn compute_dir_sum(
 
     let busy = Arc::new(AtomicIsize::new(busy));
 
+    // shared by all the threads so that they all stop as soon as
+    // one of them notices an event
+    let cancellation = dam.cancellation_token();
+
     // this MPMC channel is here for the threads to send their results
     // at end of computation
     let (thread_sum_sender, thread_sum_receiver) = channel::bounded(THREADS_COUNT);
@@ -158,7 +177,7 @@ pub fn compute_dir_sum(
 
         let special_paths = special_paths.clone();
 
-        let observer = dam.observer();
+        let cancellation = cancellation.clone();
         let thread_sum_sender = thread_sum_sender.clone();
         THREAD_POOL.spawn(move || {
             let mut thread_sum = FileSum::zero();
@@ -167,6 +186,9 @@ pub fn compute_dir_sum(
                 if let Ok(Some(open_dir)) = o {
                     if let Ok(entries) = fs::read_dir(&open_dir) {
                         for e in entries.flatten() {
+                            if cancellation.is_cancelled() {
+                                break; // the sum is useless now
+                            }
                             if let Ok(md) = e.metadata() {
                                 if md.is_dir() {
 
@@ -206,7 +228,7 @@ pub fn compute_dir_sum(
                     }
                     busy.fetch_sub(1, Ordering::Relaxed);
                 }
-                if observer.has_event() {
+                if cancellation.is_cancelled() {
                     dirs_sender.send(None).unwrap(); // to unlock the next waiting thread
                     break;
                 }
@@ -229,7 +251,7 @@ pub fn compute_dir_sum(
             }
         }
     }
-    if dam.has_event() {
+    if cancellation.is_cancelled() {
         return None;
     }
     Some(sum)
@@ -239,7 +261,7 @@ pub fn compute_dir_sum(
 pub fn compute_file_sum(path: &Path) -> FileSum {
     match fs::symlink_metadata(path) {
         Ok(md) => {
-            let seconds = extract_seconds(&md);
+            let dates = extract_dates(&md);
 
             #[cfg(unix)]
             {
@@ -249,27 +271,20 @@ pub fn compute_file_sum(path: &Path) -> FileSum {
                     block_size.min(nominal_size),
                     block_size < nominal_size,
                     1,
-                    seconds,
+                    dates,
                 )
             }
 
             #[cfg(not(unix))]
-            FileSum::new(md.len(), false, 1, seconds)
+            FileSum::new(md.len(), false, 1, dates)
         }
-        Err(_) => FileSum::new(0, false, 1, 0),
+        Err(_) => FileSum::new(0, false, 1, FileDates::default()),
     }
 }
 
-#[cfg(unix)]
-#[inline(always)]
-fn extract_seconds(md: &fs::Metadata) -> u32 {
-    md.mtime().try_into().unwrap_or(0)
-}
-
-#[cfg(not(unix))]
 #[inline(always)]
-fn extract_seconds(md: &fs::Metadata) -> u32 {
-    if let Ok(st) = md.modified() {
+fn system_time_seconds(st: std::io::Result<std::time::SystemTime>) -> u32 {
+    if let Ok(st) = st {
         if let Ok(d) = st.duration_since(std::time::UNIX_EPOCH) {
             if let Ok(secs) = d.as_secs().try_into() {
                 return secs;
@@ -279,6 +294,27 @@ fn extract_seconds(md: &fs::Metadata) -> u32 {
     0
 }
 
+#[cfg(unix)]
+#[inline(always)]
+fn extract_dates(md: &fs::Metadata) -> FileDates {
+    FileDates {
+        modified: md.mtime().try_into().unwrap_or(0),
+        changed: md.ctime().try_into().unwrap_or(0),
+        created: system_time_seconds(md.created()),
+    }
+}
+
+/// there's no ctime outside of unix
+#[cfg(not(unix))]
+#[inline(always)]
+fn extract_dates(md: &fs::Metadata) -> FileDates {
+    FileDates {
+        modified: system_time_seconds(md.modified()),
+        changed: 0,
+        created: system_time_seconds(md.created()),
+    }
+}
+
 
 #[inline(always)]
 fn md_sum(md: &fs::Metadata) -> FileSum {
@@ -288,6 +324,43 @@ fn md_sum(md: &fs::Metadata) -> FileSum {
     #[cfg(not(unix))]
     let size = md.len();
 
-    let seconds = extract_seconds(&md);
-    FileSum::new(size, false, 1, seconds)
+    let dates = extract_dates(md);
+    FileSum::new(size, false, 1, dates)
+}
+
+#[cfg(test)]
+mod sum_computation_test {
+
+    use {
+        super::*,
+        crate::{
+            conf::LOCAL_CONF_FILENAME,
+            search::Searcher,
+        },
+    };
+
+    #[test]
+    fn check_local_special_paths() {
+        let searcher = Searcher::new().unwrap();
+        let con = searcher.context();
+        let dam = Dam::unlimited();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let target = root.join("target");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("app"), vec![0u8; 50_000]).unwrap();
+        fs::write(root.join("main.rs"), "fn main() {}").unwrap();
+        let full = compute_dir_sum(root, &mut AHashMap::default(), &dam, con).unwrap();
+        assert!(compute_dir_sum(&target, &mut AHashMap::default(), &dam, con).unwrap().to_size() > 0);
+        fs::write(
+            root.join(LOCAL_CONF_FILENAME),
+            "[special_paths]\n\"target\" = \"no-enter\"\n",
+        ).unwrap();
+        let without_target = compute_dir_sum(root, &mut AHashMap::default(), &dam, con).unwrap();
+        assert!(without_target.to_size() < full.to_size());
+        assert_eq!(
+            compute_dir_sum(&target, &mut AHashMap::default(), &dam, con).unwrap().to_size(),
+            0,
+        );
+    }
 }
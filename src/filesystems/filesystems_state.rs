@@ -543,6 +543,16 @@ impl PanelState for FilesystemState {
                     CmdResult::HandleInApp(Internal::panel_right)
                 }
             }
+            Internal::scroll_down => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.try_scroll(ScrollCommand::Lines(count));
+                CmdResult::Keep
+            }
+            Internal::scroll_up => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.try_scroll(ScrollCommand::Lines(-count));
+                CmdResult::Keep
+            }
             Internal::page_down => {
                 self.try_scroll(ScrollCommand::Pages(1));
                 CmdResult::Keep
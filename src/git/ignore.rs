@@ -23,12 +23,23 @@ struct GitIgnoreRule {
     filename: bool,  // does this rule apply to just the filename
     pattern: glob::Pattern,
     pattern_options: glob::MatchOptions,
+    line_number: usize, // in the gitignore file, starting at 1
+    line: String,
+}
+
+/// the rule of a gitignore file which excludes a path
+#[derive(Debug, Clone)]
+pub struct IgnoreReason {
+    pub excluded_path: PathBuf, // the path or one of its ancestors
+    pub file: PathBuf,
+    pub line_number: usize,
+    pub line: String,
 }
 
 impl GitIgnoreRule {
     /// parse a line of a .gitignore file.
     /// The ref_dir is used if the line starts with '/'
-    fn from(line: &str, ref_dir: &Path) -> Option<GitIgnoreRule> {
+    fn from(line: &str, line_number: usize, ref_dir: &Path) -> Option<GitIgnoreRule> {
         if line.starts_with('#') {
             return None; // comment line
         }
@@ -60,6 +71,8 @@ impl GitIgnoreRule {
                         directory: c.get(3).is_some(),
                         filename: !has_separator,
                         pattern_options,
+                        line_number,
+                        line: line.trim().to_string(),
                     });
                 }
             }
@@ -71,6 +84,7 @@ impl GitIgnoreRule {
 /// The rules of a gitignore file
 #[derive(Clone)]
 pub struct GitIgnoreFile {
+    path: PathBuf,
     rules: Vec<GitIgnoreRule>,
 }
 impl GitIgnoreFile {
@@ -82,15 +96,18 @@ impl GitIgnoreFile {
     pub fn new(file_path: &Path, ref_dir: &Path) -> Result<GitIgnoreFile> {
         let f = File::open(file_path)?;
         let mut rules: Vec<GitIgnoreRule> = Vec::new();
-        for line in BufReader::new(f).lines() {
-            if let Some(rule) = GitIgnoreRule::from(&line?, ref_dir) {
+        for (idx, line) in BufReader::new(f).lines().enumerate() {
+            if let Some(rule) = GitIgnoreRule::from(&line?, idx + 1, ref_dir) {
                 rules.push(rule);
             }
         }
         // the last rule applicable to a path is the right one. So
         // we reverse the list to easily iterate from the last one to the first one
         rules.reverse();
-        Ok(GitIgnoreFile { rules })
+        Ok(GitIgnoreFile {
+            path: file_path.to_path_buf(),
+            rules,
+        })
     }
     /// return the global gitignore file interpreted for
     /// the given repo dir
@@ -128,6 +145,10 @@ impl GitIgnoreChain {
     pub fn push(&mut self, id: Id<GitIgnoreFile>) {
         self.file_ids.push(id);
     }
+    /// tell whether the directory of the chain is in a git repository
+    pub fn in_repo(&self) -> bool {
+        self.in_repo
+    }
 }
 
 #[derive(Default)]
@@ -192,10 +213,23 @@ impl GitIgnorer {
         filename: &str,
         directory: bool,
     ) -> bool {
+        match self.matching_rule(chain, path, filename, directory) {
+            Some((_, rule)) => rule.ok,
+            None => true,
+        }
+    }
+    /// return the rule deciding whether the path is ignored, if any
+    fn matching_rule(
+        &self,
+        chain: &GitIgnoreChain,
+        path: &Path,
+        filename: &str,
+        directory: bool,
+    ) -> Option<(&GitIgnoreFile, &GitIgnoreRule)> {
         if !chain.in_repo {
             // if we're not in a git repository, then .gitignore files, including
             // the global ones, are irrelevant
-            return true;
+            return None;
         }
         // we start with deeper files: deeper rules have a bigger priority
         for id in chain.file_ids.iter().rev() {
@@ -210,10 +244,72 @@ impl GitIgnorer {
                     rule.pattern.matches_path_with(path, rule.pattern_options)
                 };
                 if ok {
-                    return rule.ok;
+                    return Some((file, rule));
                 }
             }
         }
-        true
+        None
+    }
+}
+
+/// find the gitignore rule excluding the path from its repository,
+/// either directly or by excluding one of its ancestors (in which
+/// case the outermost excluded ancestor is the reported one)
+pub fn why_ignored(path: &Path) -> Option<IgnoreReason> {
+    let mut ignorer = GitIgnorer::default();
+    let mut reason = None;
+    let mut path = path;
+    while let Some(parent) = path.parent() {
+        let chain = ignorer.root_chain(parent);
+        if !chain.in_repo {
+            break;
+        }
+        let filename = path.file_name()?.to_string_lossy();
+        if let Some((file, rule)) = ignorer.matching_rule(&chain, path, &filename, path.is_dir()) {
+            if !rule.ok {
+                reason = Some(IgnoreReason {
+                    excluded_path: path.to_path_buf(),
+                    file: file.path.clone(),
+                    line_number: rule.line_number,
+                    line: rule.line.clone(),
+                });
+            }
+        }
+        if is_repo(parent) {
+            break;
+        }
+        path = parent;
+    }
+    reason
+}
+
+#[cfg(test)]
+mod ignore_test {
+
+    use {
+        super::*,
+        std::fs,
+    };
+
+    #[test]
+    fn test_why_ignored() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path();
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(repo.join("src/gen")).unwrap();
+        fs::write(repo.join(".gitignore"), "# comment\n*.log\n").unwrap();
+        fs::write(repo.join("src/.gitignore"), "gen/\n").unwrap();
+        fs::write(repo.join("src/main.rs"), "").unwrap();
+        fs::write(repo.join("src/gen/a.rs"), "").unwrap();
+        fs::write(repo.join("src/b.log"), "").unwrap();
+        assert!(why_ignored(&repo.join("src/main.rs")).is_none());
+        let reason = why_ignored(&repo.join("src/b.log")).unwrap();
+        assert_eq!(reason.file, repo.join(".gitignore"));
+        assert_eq!(reason.line_number, 2);
+        assert_eq!(reason.line, "*.log");
+        let reason = why_ignored(&repo.join("src/gen/a.rs")).unwrap();
+        assert_eq!(reason.excluded_path, repo.join("src/gen"));
+        assert_eq!(reason.file, repo.join("src/.gitignore"));
+        assert_eq!(reason.line_number, 1);
     }
 }
@@ -1,11 +1,13 @@
 mod ignore;
 mod status;
 mod status_computer;
+mod worktree;
 
 pub use {
-    ignore::{GitIgnoreChain, GitIgnorer},
+    ignore::{why_ignored, GitIgnoreChain, GitIgnorer, IgnoreReason},
     status::{LineGitStatus, LineStatusComputer, TreeGitStatus},
     status_computer::{clear_status_computer_cache, get_tree_status},
+    worktree::{list_worktrees, Worktree},
 };
 
 use std::path::{Path, PathBuf};
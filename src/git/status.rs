@@ -68,14 +68,20 @@ pub struct TreeGitStatus {
     pub current_branch_name: Option<String>,
     pub insertions: usize,
     pub deletions: usize,
+    /// number of commits ahead and behind the upstream branch,
+    /// None when there's no upstream
+    pub ahead_behind: Option<(usize, usize)>,
+    /// whether the repository is a linked worktree (ie not the main one)
+    pub is_linked_worktree: bool,
 }
 
 impl TreeGitStatus {
     pub fn from(repo: &Repository) -> Option<Self> {
-        let current_branch_name = repo
-            .head()
-            .ok()
+        let head = repo.head().ok();
+        let current_branch_name = head
+            .as_ref()
             .and_then(|head| head.shorthand().map(String::from));
+        let ahead_behind = head.and_then(|head| compute_ahead_behind(repo, head));
         let stats = match repo.diff_index_to_workdir(None, None) {
             Ok(diff) => {
                 match diff.stats() {
@@ -95,6 +101,25 @@ impl TreeGitStatus {
             current_branch_name,
             insertions: stats.insertions(),
             deletions: stats.deletions(),
+            ahead_behind,
+            is_linked_worktree: repo.is_worktree(),
         })
     }
 }
+
+/// compute the numbers of commits the head is ahead and behind its upstream
+fn compute_ahead_behind(repo: &Repository, head: git2::Reference) -> Option<(usize, usize)> {
+    if !head.is_branch() {
+        return None; // detached head
+    }
+    let local_oid = head.target()?;
+    let upstream = git2::Branch::wrap(head).upstream().ok()?;
+    let upstream_oid = upstream.get().target()?;
+    match repo.graph_ahead_behind(local_oid, upstream_oid) {
+        Ok(ahead_behind) => Some(ahead_behind),
+        Err(e) => {
+            debug!("get ahead/behind failed : {:?}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use {
+    git2::Repository,
+    std::path::{Path, PathBuf},
+};
+
+/// a git worktree, either the main one or a linked one
+#[derive(Debug, Clone)]
+pub struct Worktree {
+    pub name: String,
+    pub path: PathBuf,
+    pub branch: Option<String>,
+    pub is_main: bool,
+}
+
+fn head_branch_name(repo: &Repository) -> Option<String> {
+    repo.head()
+        .ok()
+        .and_then(|head| head.shorthand().map(String::from))
+}
+
+/// open the main repository, even when the given repository
+/// is a linked worktree
+fn main_repository(repo: Repository) -> Result<Repository, git2::Error> {
+    if repo.is_worktree() {
+        // the git dir of a linked worktree is <main git dir>/worktrees/<name>
+        match repo.path().parent().and_then(Path::parent) {
+            Some(main_git_dir) => Repository::open(main_git_dir),
+            None => Ok(repo),
+        }
+    } else {
+        Ok(repo)
+    }
+}
+
+/// list the worktrees of the repository containing the given path,
+/// the main one first. Pruned worktrees aren't listed.
+pub fn list_worktrees(path: &Path) -> Result<Vec<Worktree>, git2::Error> {
+    let repo = main_repository(Repository::discover(path)?)?;
+    let mut worktrees = Vec::new();
+    if let Some(workdir) = repo.workdir() {
+        let path = workdir.to_path_buf();
+        let name = path.file_name()
+            .map_or_else(|| "main".to_string(), |n| n.to_string_lossy().to_string());
+        worktrees.push(Worktree {
+            name,
+            branch: head_branch_name(&repo),
+            path,
+            is_main: true,
+        });
+    }
+    for name in repo.worktrees()?.iter().flatten() {
+        let worktree = match repo.find_worktree(name) {
+            Ok(worktree) => worktree,
+            Err(e) => {
+                debug!("worktree {:?} not found: {:?}", name, e);
+                continue;
+            }
+        };
+        if worktree.validate().is_err() {
+            debug!("skipping invalid worktree {:?}", name);
+            continue;
+        }
+        let branch = Repository::open_from_worktree(&worktree)
+            .ok()
+            .and_then(|wt_repo| head_branch_name(&wt_repo));
+        worktrees.push(Worktree {
+            name: name.to_string(),
+            path: worktree.path().to_path_buf(),
+            branch,
+            is_main: false,
+        });
+    }
+    Ok(worktrees)
+}
@@ -25,7 +25,8 @@ ${config-files
 ## Verbs
 
 To execute a verb, type a space or `:` then start of its name or shortcut.
-This table is searchable. Hit a few letters to filter it.
+This table is searchable. Hit a few letters to filter it by name or key,
+then select a verb with *↑* and *↓* and hit *enter* to execute it.
 |:-:|:-:|:-:|:-:
 |**name**|**shortcut**|**key**|**description**
 |-:|:-:|:-:|:-
@@ -33,6 +34,14 @@ ${verb-rows
 |${name}|${shortcut}|${key}|${description}`${execution}`
 }
 |-:
+To change the key of a verb, filter the table down to this verb then
+type `:rebind` followed by the key, eg `:rebind ctrl-b` (or `:rebind back ctrl-b`
+from anywhere). The binding is added to your configuration.
+
+${conflicts-text}
+${conflicts
+* *${conflict-key}* : ${conflict-verbs}
+}
 
 ## Search Modes
 
@@ -50,10 +59,9 @@ ${search-mode-rows
 ## Launch Arguments
 
 Some options can be set on launch:
-* `-h` or `--hidden` : show hidden files
-* `-i` : show files which are normally hidden due to .gitignore rules
-* `-d` or `--dates` : display last modified dates
-* `-w` : whale-spotting mode
+${launch-args
+* ${launch-arg} : ${launch-arg-description}
+}
  (for the complete list, run `broot --help`)
 
 ## Flags
@@ -17,7 +17,7 @@ pub fn list() -> Vec<(&'static str, &'static str)> {
     #[cfg(feature = "clipboard")]
     features.push((
         "clipboard",
-        ":copy_path (copying the current path), and :input_paste (pasting into the input)",
+        ":copy_path, :copy_relative_path, :copy_name and :copy_file_content (copying to the clipboard), and :input_paste (pasting into the input)",
     ));
 
     features
@@ -0,0 +1,9 @@
+/// the launch arguments listed in the help screen
+pub fn list() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("`-h` or `--hidden`", "show hidden files"),
+        ("`-i`", "show files which are normally hidden due to .gitignore rules"),
+        ("`-d` or `--dates`", "display last modified dates"),
+        ("`-w`", "whale-spotting mode"),
+    ]
+}
@@ -21,20 +21,26 @@ pub fn search_mode_rows(con: &AppContext) -> Vec<MatchingSearchModeRow> {
                 .search_modes
                 .key(*mode)
                 .map_or_else(|| "".to_string(), |k| format!("{:>3}/", k));
-            let description = format!(
-                "{} search on {}",
-                match mode.kind() {
-                    SearchKind::Exact => "exact string",
-                    SearchKind::Fuzzy => "fuzzy",
-                    SearchKind::Regex => "regex",
-                    _ => "???", // should not happen
-                },
-                match mode.object() {
-                    SearchObject::Name => "file name",
-                    SearchObject::Path => "sub path",
-                    SearchObject::Content => "file content",
-                },
-            );
+            let description = match mode.object() {
+                SearchObject::Permissions => "permissions predicate, eg `+x` or `o+w`".to_string(),
+                SearchObject::Owner => "file owner, by name or uid".to_string(),
+                object => format!(
+                    "{} search on {}",
+                    match mode.kind() {
+                        SearchKind::Exact => "exact string",
+                        SearchKind::Fuzzy => "fuzzy",
+                        SearchKind::Regex => "regex",
+                        _ => "???", // should not happen
+                    },
+                    match object {
+                        SearchObject::Name => "file name",
+                        SearchObject::Path => "sub path",
+                        SearchObject::Content => "file content",
+                        SearchObject::Tag => "file tags",
+                        _ => "???", // described above
+                    },
+                ),
+            };
             MatchingSearchModeRow {
                 prefix,
                 description,
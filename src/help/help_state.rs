@@ -2,7 +2,7 @@ use {
     super::help_content,
     crate::{
         app::*,
-        command::{Command, TriggerType},
+        command::{move_sel, Command, TriggerType},
         conf::Conf,
         display::{Screen, W},
         errors::ProgramError,
@@ -11,16 +11,22 @@ use {
         tree::TreeOptions,
         verb::*,
     },
+    minimad::CompositeStyle,
     std::path::{Path, PathBuf},
-    termimad::{Area, FmtText, TextView},
+    termimad::{Area, FmtLine, FmtText, TextView},
 };
 
+/// the mark of the selected verb in the filtered table
+static SELECTION_MARK: &str = "▶";
+
 /// an application state dedicated to help
 pub struct HelpState {
     pub scroll: i32, // scroll position
     pub text_area: Area,
     dirty: bool, // background must be cleared
     pattern: Pattern,
+    selection: usize, // index of the selected verb row, used when filtered
+    jump_to_verbs: bool, // whether the verbs table must be scrolled to
     tree_options: TreeOptions,
     config_path: PathBuf, // the last config path when several were used
     mode: Mode,
@@ -42,11 +48,63 @@ impl HelpState {
             scroll: 0,
             dirty: true,
             pattern: Pattern::None,
+            selection: 0,
+            jump_to_verbs: false,
             tree_options,
             config_path,
             mode: initial_mode(con),
         }
     }
+
+    /// make the help scroll to the verbs table, whose
+    /// keys column lists the key bindings
+    pub fn scrolled_to_verbs(mut self) -> Self {
+        self.jump_to_verbs = true;
+        self
+    }
+
+    /// return the verb selected in the filtered verbs table,
+    /// if the table is filtered
+    fn selected_verb_index(&self, con: &AppContext) -> Option<usize> {
+        if self.pattern.is_some() {
+            super::help_verbs::matching_verb_rows(&self.pattern, con)
+                .get(self.selection)
+                .map(|row| row.index)
+        } else {
+            None
+        }
+    }
+
+    /// move the selection in the filtered verbs table
+    fn move_selection(&mut self, dy: i32, cycle: bool, con: &AppContext) {
+        let count = super::help_verbs::matching_verb_rows(&self.pattern, con).len();
+        if count > 0 {
+            self.selection = move_sel(self.selection, count, dy, cycle);
+        }
+    }
+}
+
+/// return the index of the line of the title of the verbs section
+fn verbs_title_line(fmt_text: &FmtText<'_, '_>) -> Option<usize> {
+    fmt_text.lines.iter().position(|line| match line {
+        FmtLine::Normal(fc) => {
+            fc.composite.style == CompositeStyle::Header(2)
+                && fc.composite.compounds.iter().any(|c| c.as_str() == "Verbs")
+        }
+        _ => false,
+    })
+}
+
+/// return the index of the line of the selected verb row
+fn selected_row_line(fmt_text: &FmtText<'_, '_>) -> Option<usize> {
+    fmt_text.lines.iter().position(|line| match line {
+        FmtLine::TableRow(row) => matches!(
+            row.cells.first(),
+            Some(cell) if cell.composite.compounds.iter()
+                .any(|c| c.as_str().starts_with(SELECTION_MARK))
+        ),
+        _ => false,
+    })
 }
 
 impl PanelState for HelpState {
@@ -103,6 +161,7 @@ impl PanelState for HelpState {
         _con: &AppContext,
     ) -> Result<CmdResult, ProgramError> {
         self.pattern = pat.pattern;
+        self.selection = 0;
         Ok(CmdResult::Keep)
     }
 
@@ -133,12 +192,23 @@ impl PanelState for HelpState {
                 .set("path", path);
         }
         let verb_rows = super::help_verbs::matching_verb_rows(&self.pattern, con);
-        for row in &verb_rows {
+        let selected_name = verb_rows
+            .get(self.selection)
+            .filter(|_| self.pattern.is_some())
+            .map(|row| format!("{} {}", SELECTION_MARK, row.name()));
+        for (idx, row) in verb_rows.iter().enumerate() {
+            let name = match &selected_name {
+                Some(selected_name) if idx == self.selection => selected_name,
+                _ => row.name(),
+            };
             let sub = expander
                 .sub("verb-rows")
-                .set_md("name", row.name())
-                .set_md("shortcut", row.shortcut())
-                .set("key", &row.verb.keys_desc);
+                .set_md("name", name)
+                .set_md("shortcut", row.shortcut());
+            match row.matched_key() {
+                Some(key) => sub.set_md("key", key),
+                None => sub.set("key", &row.verb.keys_desc),
+            };
             if row.verb.description.code {
                 sub.set("description", "");
                 sub.set("execution", &row.verb.description.content);
@@ -147,6 +217,31 @@ impl PanelState for HelpState {
                 sub.set("execution", "");
             }
         }
+        let conflicts = super::help_verbs::key_conflicts(con);
+        expander.set(
+            "conflicts-text",
+            if conflicts.is_empty() {
+                "No key binding is hidden by another one."
+            } else {
+                "Those keys are bound to several verbs, only the first one applying to the selection is triggered:"
+            },
+        );
+        for conflict in &conflicts {
+            expander
+                .sub("conflicts")
+                .set("conflict-key", &conflict.key)
+                .set("conflict-verbs", &conflict.verbs);
+        }
+        let launch_args = super::help_launch_args::list();
+        for (arg, description) in &launch_args {
+            if self.pattern.is_some() && self.pattern.search_string(arg).is_none() {
+                continue;
+            }
+            expander
+                .sub("launch-args")
+                .set_md("launch-arg", arg)
+                .set("launch-arg-description", description);
+        }
         let search_rows = super::help_search_modes::search_mode_rows(con);
         for row in &search_rows {
             expander
@@ -175,6 +270,20 @@ impl PanelState for HelpState {
             text,
             Some((self.text_area.width - 1) as usize),
         );
+        let height = self.text_area.height as i32;
+        if self.jump_to_verbs {
+            if let Some(line) = verbs_title_line(&fmt_text) {
+                self.scroll = line as i32;
+            }
+            self.jump_to_verbs = false;
+        } else if let Some(line) = selected_row_line(&fmt_text) {
+            let line = line as i32;
+            if line < self.scroll {
+                self.scroll = line;
+            } else if line >= self.scroll + height {
+                self.scroll = line + 1 - height;
+            }
+        }
         let mut text_view = TextView::from(&self.text_area, &fmt_text);
         self.scroll = text_view.set_scroll(self.scroll);
         Ok(text_view.write_on(w)?)
@@ -200,14 +309,38 @@ impl PanelState for HelpState {
                 }
             }
             help => CmdResult::Keep,
-            line_down | line_down_no_cycle => {
+            keys => {
+                self.jump_to_verbs = true;
+                CmdResult::Keep
+            }
+            line_down | line_down_no_cycle if self.pattern.is_some() => {
+                let count = get_arg(input_invocation, internal_exec, 1);
+                self.move_selection(count, internal_exec.internal == line_down, cc.app.con);
+                CmdResult::Keep
+            }
+            line_up | line_up_no_cycle if self.pattern.is_some() => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.move_selection(-count, internal_exec.internal == line_up, cc.app.con);
+                CmdResult::Keep
+            }
+            line_down | line_down_no_cycle | scroll_down => {
                 self.scroll += get_arg(input_invocation, internal_exec, 1);
                 CmdResult::Keep
             }
-            line_up | line_up_no_cycle => {
+            line_up | line_up_no_cycle | scroll_up => {
                 self.scroll -= get_arg(input_invocation, internal_exec, 1);
                 CmdResult::Keep
             }
+            open_stay if self.pattern.is_some() => {
+                // the selected verb is executed on the previous state
+                match self.selected_verb_index(cc.app.con) {
+                    Some(index) => CmdResult::PopStateAndApply(Box::new(Command::VerbTrigger {
+                        index,
+                        input_invocation: None,
+                    })),
+                    None => CmdResult::error("no verb matches the filter"),
+                }
+            }
             open_stay => match open::that(&Conf::default_location()) {
                 Ok(exit_status) => {
                     info!("open returned with exit_status {:?}", exit_status);
@@ -221,6 +354,20 @@ impl PanelState for HelpState {
                     Conf::default_location()
                 ))
             }
+            rebind => match get_str_arg(input_invocation, internal_exec) {
+                Some(arg) => {
+                    // when the table is filtered down to one verb, it's the rebound one
+                    let verb_rows = super::help_verbs::matching_verb_rows(&self.pattern, cc.app.con);
+                    let default_verb = match verb_rows.as_slice() {
+                        [row] => Some(row.index),
+                        _ => None,
+                    };
+                    crate::app::rebind(arg, default_verb, cc.app.con)
+                }
+                None => CmdResult::error(
+                    "filter the verbs table down to one verb, then type eg `:rebind ctrl-b`"
+                ),
+            },
             page_down => {
                 self.scroll += self.text_area.height as i32;
                 CmdResult::Keep
@@ -1,6 +1,7 @@
 use {
     crate::{
         app::AppContext,
+        keys,
         pattern::*,
         verb::*,
     },
@@ -11,7 +12,10 @@ use {
 pub struct MatchingVerbRow<'v> {
     name: Option<String>,
     shortcut: Option<String>,
+    key: Option<String>,
     pub verb: &'v Verb,
+    /// index of the verb in the verb store
+    pub index: usize,
 }
 
 impl MatchingVerbRow<'_> {
@@ -35,18 +39,24 @@ impl MatchingVerbRow<'_> {
                 _ => " ",
             })
     }
+    /// the keys in markdown, with matching chars in bold, if
+    /// they matched the filter
+    pub fn matched_key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
 }
 
 /// return the rows of the verbs table in help, taking the current filter
-/// into account
+/// (on the name, the shortcut or the keys) into account
 pub fn matching_verb_rows<'v>(
     pat: &Pattern,
     con: &'v AppContext,
 ) -> Vec<MatchingVerbRow<'v>> {
     let mut rows = Vec::new();
-    for verb in &con.verb_store.verbs {
+    for (index, verb) in con.verb_store.verbs.iter().enumerate() {
         let mut name = None;
         let mut shortcut = None;
+        let mut key = None;
         if pat.is_some() {
             let mut ok = false;
             name = verb.names.get(0).and_then(|s| {
@@ -61,6 +71,10 @@ pub fn matching_verb_rows<'v>(
                     nm.wrap(s, "**", "**")
                 })
             });
+            if let Some(nm) = pat.search_string(&verb.keys_desc) {
+                ok = true;
+                key = Some(nm.wrap(&verb.keys_desc, "**", "**"));
+            }
             if !ok {
                 continue;
             }
@@ -68,8 +82,35 @@ pub fn matching_verb_rows<'v>(
         rows.push(MatchingVerbRow {
             name,
             shortcut,
+            key,
             verb,
+            index,
         });
     }
     rows
 }
+
+/// a key or chord triggering several verbs
+pub struct KeyConflict {
+    pub key: String,
+    /// names of the verbs, the first one being the triggered one
+    pub verbs: String,
+}
+
+/// return the keys bound to several verbs applying to the same selections
+pub fn key_conflicts(con: &AppContext) -> Vec<KeyConflict> {
+    let verbs = &con.verb_store.verbs;
+    con.verb_store.key_conflicts()
+        .into_iter()
+        .map(|(keys, indexes)| KeyConflict {
+            key: keys::key_sequence_desc(&keys),
+            verbs: indexes.iter()
+                .map(|&idx| match verbs[idx].names.first() {
+                    Some(name) => name.to_string(),
+                    None => verbs[idx].execution.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(", "),
+        })
+        .collect()
+}
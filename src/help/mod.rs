@@ -1,5 +1,6 @@
 mod help_content;
 mod help_features;
+mod help_launch_args;
 mod help_search_modes;
 mod help_state;
 mod help_verbs;
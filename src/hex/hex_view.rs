@@ -54,6 +54,12 @@ impl HexView {
         self.scroll = cmd.apply(self.scroll, self.line_count(), self.page_height);
         self.scroll != old_scroll
     }
+    pub fn get_scroll(&self) -> usize {
+        self.scroll
+    }
+    pub fn set_scroll(&mut self, scroll: usize) {
+        self.scroll = scroll.min(self.line_count().saturating_sub(1));
+    }
     pub fn select_first(&mut self) {
         self.scroll = 0;
     }
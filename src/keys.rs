@@ -33,6 +33,7 @@ const_key!(ENTER, Enter);
 const_key!(BACKSPACE, Backspace);
 const_key!(BACK_TAB, BackTab);
 const_key!(DELETE, Delete);
+const_key!(DOUBLE_QUOTE, Char('"'));
 const_key!(DOWN, Down);
 const_key!(PAGE_DOWN, PageDown);
 const_key!(END, End);
@@ -192,6 +193,30 @@ pub fn parse_key(raw: &str) -> Result<KeyEvent, ConfError> {
     }
     Ok(KeyEvent { code, modifiers })
 }
+/// parse a string as a sequence of space separated keys, for
+/// example "g g" or "ctrl-k d"
+pub fn parse_key_sequence(raw: &str) -> Result<Vec<KeyEvent>, ConfError> {
+    let sequence = raw
+        .split_whitespace()
+        .map(parse_key)
+        .collect::<Result<Vec<KeyEvent>, ConfError>>()?;
+    if sequence.is_empty() {
+        return Err(ConfError::InvalidKey {
+            raw: raw.to_owned(),
+        });
+    }
+    Ok(sequence)
+}
+
+/// build a human description of a sequence of keys
+pub fn key_sequence_desc(sequence: &[KeyEvent]) -> String {
+    sequence
+        .iter()
+        .map(|&key| key_event_desc(key))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
 #[cfg(test)]
 mod key_parsing_tests {
 
@@ -226,4 +251,16 @@ mod key_parsing_tests {
         check_ok("ctrl-Q", KeyEvent::new(Char('q'), KeyModifiers::CONTROL));
         check_ok("shift-Q", KeyEvent::new(Char('Q'), KeyModifiers::SHIFT));
     }
+
+    #[test]
+    fn check_key_sequence_parsing() {
+        let sequence = parse_key_sequence(" g  ctrl-g ").unwrap();
+        assert_eq!(
+            sequence,
+            vec![KeyEvent::from(Char('g')), KeyEvent::new(Char('g'), KeyModifiers::CONTROL)],
+        );
+        assert_eq!(key_sequence_desc(&sequence), "g ctrl-g");
+        assert!(parse_key_sequence("g nokey").is_err());
+        assert!(parse_key_sequence(" ").is_err());
+    }
 }
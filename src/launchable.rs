@@ -24,7 +24,8 @@ use {
         env,
         io::{self, Write},
         path::PathBuf,
-        process::Command,
+        process::{Command, Stdio},
+        thread,
     },
 };
 
@@ -38,6 +39,12 @@ pub enum Launchable {
         to_print: String,
     },
 
+    /// print something on stdout on end of broot, without
+    /// adding a newline
+    RawPrinter {
+        to_print: String,
+    },
+
     /// print the tree on end of broot
     TreePrinter {
         tree: Box<Tree>,
@@ -45,6 +52,7 @@ pub enum Launchable {
         ext_colors: ExtColorMap,
         width: u16,
         height: u16,
+        ascii: bool,
     },
 
     /// execute an external program
@@ -53,6 +61,7 @@ pub enum Launchable {
         args: Vec<String>,
         working_dir: Option<PathBuf>,
         mouse_capture_disabled: bool,
+        input: Option<Vec<u8>>, // written on the stdin of the program
     },
 
     /// open a path
@@ -84,11 +93,15 @@ impl Launchable {
     pub fn printer(to_print: String) -> Launchable {
         Launchable::Printer { to_print }
     }
+    pub fn raw_printer(to_print: String) -> Launchable {
+        Launchable::RawPrinter { to_print }
+    }
     pub fn tree_printer(
         tree: &Tree,
         screen: Screen,
         style_map: StyleMap,
         ext_colors: ExtColorMap,
+        ascii: bool,
     ) -> Launchable {
         Launchable::TreePrinter {
             tree: Box::new(tree.clone()),
@@ -96,6 +109,7 @@ impl Launchable {
             ext_colors,
             width: screen.width,
             height: (tree.lines.len() as u16).min(screen.height),
+            ascii,
         }
     }
 
@@ -111,11 +125,21 @@ impl Launchable {
                 args: parts.collect(),
                 working_dir,
                 mouse_capture_disabled: con.mouse_capture_disabled,
+                input: None,
             }),
             None => Err(io::Error::new(io::ErrorKind::Other, "Empty launch string")),
         }
     }
 
+    /// set what will be written on the stdin of the program
+    /// (does nothing if the launchable isn't a program)
+    pub fn with_input(mut self, new_input: Option<Vec<u8>>) -> Self {
+        if let Launchable::Program { input, .. } = &mut self {
+            *input = new_input;
+        }
+        self
+    }
+
     pub fn execute(
         &self,
         mut w: Option<&mut W>,
@@ -125,8 +149,14 @@ impl Launchable {
                 println!("{}", to_print);
                 Ok(())
             }
-            Launchable::TreePrinter { tree, skin, ext_colors, width, height } => {
-                let dp = DisplayableTree::out_of_app(&tree, &skin, &ext_colors, *width, *height);
+            Launchable::RawPrinter { to_print } => {
+                let mut stdout = io::stdout();
+                stdout.write_all(to_print.as_bytes())?;
+                stdout.flush()?;
+                Ok(())
+            }
+            Launchable::TreePrinter { tree, skin, ext_colors, width, height, ascii } => {
+                let dp = DisplayableTree::out_of_app(tree, skin, ext_colors, *width, *height, *ascii);
                 dp.write_on(&mut std::io::stdout())
             }
             Launchable::Program {
@@ -134,6 +164,7 @@ impl Launchable {
                 exe,
                 args,
                 mouse_capture_disabled,
+                input,
             } => {
                 // we restore the normal terminal in case the executable
                 // is a terminal application, and we'll switch back to
@@ -153,10 +184,21 @@ impl Launchable {
                     old_working_dir = std::env::current_dir().ok();
                     std::env::set_current_dir(working_dir).unwrap();
                 }
-                let exec_res = Command::new(&exe)
-                    .args(args.iter())
+                let mut command = Command::new(exe);
+                command.args(args.iter());
+                if input.is_some() {
+                    command.stdin(Stdio::piped());
+                }
+                let exec_res = command
                     .spawn()
-                    .and_then(|mut p| p.wait())
+                    .and_then(|mut p| {
+                        if let (Some(input), Some(mut stdin)) = (input.clone(), p.stdin.take()) {
+                            // written in another thread so that we're not blocked
+                            // if the program doesn't read it all
+                            thread::spawn(move || stdin.write_all(&input));
+                        }
+                        p.wait()
+                    })
                     .map_err(|source| ProgramError::LaunchError {
                         program: exe.clone(),
                         source,
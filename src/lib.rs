@@ -9,11 +9,14 @@
 
 pub mod app;
 pub mod browser;
+pub mod cleanable;
 pub mod cli;
 pub mod command;
 pub mod conf;
 pub mod content_search;
+pub mod dry_run;
 pub mod errors;
+pub mod event;
 pub mod file_sum;
 pub mod flag;
 pub mod git;
@@ -23,19 +26,25 @@ pub mod icon;
 pub mod image;
 pub mod keys;
 pub mod launchable;
+pub mod palette;
 pub mod path;
+pub mod path_index;
 pub mod pattern;
 pub mod permissions;
+pub mod pick;
 pub mod preview;
 pub mod print;
+pub mod search;
 pub mod stage;
 pub mod shell_install;
 pub mod skin;
 pub mod syntactic;
+pub mod tag;
 pub mod task_sync;
 pub mod tree;
 pub mod tree_build;
 pub mod verb;
+pub mod worktrees;
 
 #[cfg(unix)]
 pub mod filesystems;
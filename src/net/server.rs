@@ -1,6 +1,7 @@
 use {
     super::Message,
     crate::{
+        app::AppMessage,
         errors::NetError,
         command::Sequence,
     },
@@ -22,7 +23,7 @@ pub struct Server {
 impl Server {
     pub fn new(
         name: &str,
-        tx: Sender<Sequence>,
+        tx: Sender<AppMessage>,
         root: Arc<Mutex<PathBuf>>,
     ) -> Result<Self, NetError> {
         let path = super::socket_file_path(name);
@@ -67,7 +68,7 @@ impl Server {
                                 None
                             }
                         } {
-                            if let Err(e) = tx.send(sequence) {
+                            if let Err(e) = tx.send(AppMessage::Sequence(sequence)) {
                                 warn!("error while sending {:?}", e);
                                 return;
                             }
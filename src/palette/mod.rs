@@ -0,0 +1,3 @@
+mod palette_state;
+
+pub use palette_state::PaletteState;
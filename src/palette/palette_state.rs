@@ -0,0 +1,459 @@
+use {
+    crate::{
+        app::*,
+        command::*,
+        display::*,
+        errors::ProgramError,
+        pattern::*,
+        tree::TreeOptions,
+        verb::*,
+    },
+    crossterm::{
+        cursor,
+        QueueableCommand,
+    },
+    std::path::{Path, PathBuf},
+};
+
+/// max width of the command column
+const MAX_COMMAND_WIDTH: usize = 40;
+
+/// max width of the key column
+const MAX_KEY_WIDTH: usize = 25;
+
+/// what executing an entry of the palette means
+enum PaletteEntry {
+    /// a command recently typed in the input
+    Recent(VerbInvocation),
+    /// a verb of the verb store, given by its index
+    Verb(usize),
+}
+
+/// a line of the palette
+struct PaletteRow {
+    entry: PaletteEntry,
+    command: String,
+    key: String,
+    description: String,
+}
+
+impl PaletteRow {
+    fn score(&self, pattern: &Pattern) -> Option<i32> {
+        [&self.command, &self.key, &self.description]
+            .iter()
+            .filter_map(|s| pattern.score_of_string(s))
+            .max()
+    }
+    fn command(&self) -> Command {
+        match &self.entry {
+            PaletteEntry::Recent(invocation) => Command::VerbInvocate(invocation.clone()),
+            PaletteEntry::Verb(index) => Command::VerbTrigger {
+                index: *index,
+                input_invocation: None,
+            },
+        }
+    }
+}
+
+/// return the indexes of the rows matching the pattern, best first
+fn matching_rows(rows: &[PaletteRow], pattern: &Pattern) -> Vec<usize> {
+    if !pattern.is_some() {
+        return (0..rows.len()).collect();
+    }
+    let mut scored: Vec<(usize, i32)> = rows.iter()
+        .enumerate()
+        .filter_map(|(idx, row)| row.score(pattern).map(|score| (idx, score)))
+        .collect();
+    // the sort is stable: the recent commands stay before the verbs
+    scored.sort_by_key(|&(_, score)| -score);
+    scored.into_iter().map(|(idx, _)| idx).collect()
+}
+
+/// whether the verb only makes sense when typing in the input
+fn is_input_related(verb: &Verb) -> bool {
+    matches!(verb.get_internal(), Some(internal) if internal.name().starts_with("input_"))
+}
+
+/// an application state listing the verbs and the recently typed
+/// commands, to find one by fuzzy search and execute it on the
+/// selection of the previous state
+pub struct PaletteState {
+    rows: Vec<PaletteRow>,
+    /// indexes in rows of the ones matching the pattern, best first
+    displayed: Vec<usize>,
+    /// index in displayed
+    selection_idx: usize,
+    scroll: usize,
+    page_height: usize,
+    pattern: Pattern,
+    /// the selection of the previous state, the one the
+    /// commands apply to
+    selected_path: Option<PathBuf>,
+    selection_type: SelectionType,
+    tree_options: TreeOptions,
+    mode: Mode,
+}
+
+impl PaletteState {
+    pub fn new(
+        selection: Option<Selection<'_>>,
+        tree_options: TreeOptions,
+        app_state: &AppState,
+        con: &AppContext,
+    ) -> PaletteState {
+        let mut rows = Vec::new();
+        for invocation in &app_state.recent_invocations {
+            rows.push(PaletteRow {
+                entry: PaletteEntry::Recent(invocation.clone()),
+                command: invocation.to_string(),
+                key: String::new(),
+                description: "recently typed".to_string(),
+            });
+        }
+        for (index, verb) in con.verb_store.verbs.iter().enumerate() {
+            if is_input_related(verb) {
+                continue;
+            }
+            let command = match verb.names.first() {
+                Some(name) => format!(":{}", name),
+                None => verb.execution.to_string(),
+            };
+            let description = if verb.description.code {
+                verb.description.content.clone()
+            } else {
+                // the palette doesn't render the markdown of the description
+                verb.description.content.replace(&['*', '`'][..], "")
+            };
+            rows.push(PaletteRow {
+                entry: PaletteEntry::Verb(index),
+                command,
+                key: verb.keys_desc.clone(),
+                description,
+            });
+        }
+        PaletteState {
+            displayed: (0..rows.len()).collect(),
+            rows,
+            selection_idx: 0,
+            scroll: 0,
+            page_height: 0,
+            pattern: Pattern::None,
+            selected_path: selection.map(|s| s.path.to_path_buf()),
+            selection_type: selection.map_or(SelectionType::Any, |s| s.stype),
+            tree_options,
+            mode: initial_mode(con),
+        }
+    }
+
+    fn move_line(
+        &mut self,
+        internal_exec: &InternalExecution,
+        input_invocation: Option<&VerbInvocation>,
+        dir: i32, // -1 for up, 1 for down
+        cycle: bool,
+    ) -> CmdResult {
+        let count: i32 = get_arg(input_invocation, internal_exec, 1);
+        let dir = dir * count;
+        self.selection_idx = move_sel(self.selection_idx, self.displayed.len(), dir, cycle);
+        self.make_selection_visible();
+        CmdResult::Keep
+    }
+
+    /// change the scroll, if needed, so that the selection is visible
+    fn make_selection_visible(&mut self) {
+        if self.selection_idx < self.scroll {
+            self.scroll = self.selection_idx;
+        } else if self.page_height > 0 && self.selection_idx >= self.scroll + self.page_height {
+            self.scroll = self.selection_idx + 1 - self.page_height;
+        }
+    }
+
+    /// go back to the previous state and execute the selected command there
+    fn execute_selection(&self) -> CmdResult {
+        match self.displayed.get(self.selection_idx) {
+            Some(&idx) => CmdResult::PopStateAndApply(Box::new(self.rows[idx].command())),
+            None => CmdResult::error("no command matches the filter"),
+        }
+    }
+}
+
+impl PanelState for PaletteState {
+
+    fn get_type(&self) -> PanelStateType {
+        PanelStateType::Palette
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    fn get_mode(&self) -> Mode {
+        self.mode
+    }
+
+    fn selected_path(&self) -> Option<&Path> {
+        self.selected_path.as_deref()
+    }
+
+    fn tree_options(&self) -> TreeOptions {
+        self.tree_options.clone()
+    }
+
+    fn with_new_options(
+        &mut self,
+        _screen: Screen,
+        change_options: &dyn Fn(&mut TreeOptions),
+        _in_new_panel: bool,
+        _con: &AppContext,
+    ) -> CmdResult {
+        change_options(&mut self.tree_options);
+        CmdResult::Keep
+    }
+
+    fn selection(&self) -> Option<Selection<'_>> {
+        self.selected_path().map(|path| Selection {
+            path,
+            stype: self.selection_type,
+            is_exe: false,
+            line: 0,
+        })
+    }
+
+    fn refresh(&mut self, _screen: Screen, _con: &AppContext) -> Command {
+        Command::empty()
+    }
+
+    fn on_pattern(
+        &mut self,
+        pattern: InputPattern,
+        _app_state: &AppState,
+        _con: &AppContext,
+    ) -> Result<CmdResult, ProgramError> {
+        self.pattern = pattern.pattern;
+        self.displayed = matching_rows(&self.rows, &self.pattern);
+        self.selection_idx = 0;
+        self.scroll = 0;
+        Ok(CmdResult::Keep)
+    }
+
+    fn no_verb_status(
+        &self,
+        _has_previous_state: bool,
+        _con: &AppContext,
+    ) -> Status {
+        Status::from_message(
+            "Hit *enter* to execute the selected command, *esc* to get back, or a few letters to search"
+        )
+    }
+
+    fn display(
+        &mut self,
+        w: &mut W,
+        disc: &DisplayContext,
+    ) -> Result<(), ProgramError> {
+        let area = &disc.state_area;
+        let con = &disc.con;
+        let page_height = (area.height as usize).max(2) - 2;
+        if page_height != self.page_height {
+            // the panel was resized
+            self.page_height = page_height;
+            self.make_selection_visible();
+        }
+        let scrollbar = area.scrollbar(self.scroll as i32, self.displayed.len() as i32);
+        let styles = &disc.panel_skin.styles;
+        let border_style = &styles.help_table_border;
+        let width = area.width as usize;
+        let w_command = self.rows.iter()
+            .map(|row| row.command.chars().count())
+            .max().unwrap_or(0)
+            .clamp("command".len(), MAX_COMMAND_WIDTH);
+        let mut wc_command = w_command;
+        if con.show_selection_mark {
+            wc_command += 1;
+        }
+        let w_key = self.rows.iter()
+            .map(|row| row.key.chars().count())
+            .max().unwrap_or(0)
+            .clamp("key".len(), MAX_KEY_WIDTH);
+        //- titles
+        w.queue(cursor::MoveTo(area.left, area.top))?;
+        let mut cw = CropWriter::new(w, width);
+        cw.queue_g_string(&styles.default, format!("{:width$}", "command", width = wc_command))?;
+        cw.queue_char(border_style, '│')?;
+        cw.queue_g_string(&styles.default, format!("{:^width$}", "key", width = w_key))?;
+        cw.queue_char(border_style, '│')?;
+        cw.queue_str(&styles.default, "description")?;
+        cw.fill(border_style, &SPACE_FILLING)?;
+        //- horizontal line
+        w.queue(cursor::MoveTo(area.left, 1 + area.top))?;
+        let mut cw = CropWriter::new(w, width);
+        cw.queue_g_string(border_style, format!("{:─>width$}", '┼', width = wc_command + 1))?;
+        cw.queue_g_string(border_style, format!("{:─>width$}", '┼', width = w_key + 1))?;
+        cw.fill(border_style, &BRANCH_FILLING)?;
+        //- content
+        let mut idx = self.scroll;
+        for y in 2..area.height {
+            w.queue(cursor::MoveTo(area.left, y + area.top))?;
+            let selected = self.selection_idx == idx;
+            let mut cw = CropWriter::new(w, width - 1); // -1 for scrollbar
+            let txt_style = if selected { &styles.selected_line } else { &styles.default };
+            if let Some(row) = self.displayed.get(idx).map(|&i| &self.rows[i]) {
+                let mut border_style = border_style.clone();
+                if let Some(bg) = txt_style.get_bg() {
+                    border_style.set_bg(bg);
+                }
+                if con.show_selection_mark {
+                    cw.queue_char(txt_style, if selected { '▶' } else { ' ' })?;
+                }
+                let command_style = match row.entry {
+                    PaletteEntry::Recent(_) => &styles.directory,
+                    PaletteEntry::Verb(_) => &styles.file,
+                };
+                let mut command_style = command_style.clone();
+                let mut key_style = styles.help_bold.clone();
+                if let Some(bg) = txt_style.get_bg() {
+                    command_style.set_bg(bg);
+                    key_style.set_bg(bg);
+                }
+                let command: String = row.command.chars().take(w_command).collect();
+                cw.queue_g_string(&command_style, format!("{:width$}", command, width = w_command))?;
+                cw.queue_char(&border_style, '│')?;
+                let key: String = row.key.chars().take(w_key).collect();
+                cw.queue_g_string(&key_style, format!("{:^width$}", key, width = w_key))?;
+                cw.queue_char(&border_style, '│')?;
+                cw.queue_str(txt_style, &row.description)?;
+                idx += 1;
+            }
+            cw.fill(txt_style, &SPACE_FILLING)?;
+            let scrollbar_style = if ScrollCommand::is_thumb(y, scrollbar) {
+                &styles.scrollbar_thumb
+            } else {
+                &styles.scrollbar_track
+            };
+            scrollbar_style.queue_str(w, "▐")?;
+        }
+        Ok(())
+    }
+
+    fn on_internal(
+        &mut self,
+        w: &mut W,
+        internal_exec: &InternalExecution,
+        input_invocation: Option<&VerbInvocation>,
+        trigger_type: TriggerType,
+        app_state: &mut AppState,
+        cc: &CmdContext,
+    ) -> Result<CmdResult, ProgramError> {
+        let con = &cc.app.con;
+        Ok(match internal_exec.internal {
+            Internal::back => {
+                if self.pattern.is_some() {
+                    self.on_pattern(InputPattern::none(), app_state, con)?
+                } else {
+                    CmdResult::PopState
+                }
+            }
+            Internal::line_down => {
+                self.move_line(internal_exec, input_invocation, 1, true)
+            }
+            Internal::line_up => {
+                self.move_line(internal_exec, input_invocation, -1, true)
+            }
+            Internal::line_down_no_cycle | Internal::scroll_down => {
+                self.move_line(internal_exec, input_invocation, 1, false)
+            }
+            Internal::line_up_no_cycle | Internal::scroll_up => {
+                self.move_line(internal_exec, input_invocation, -1, false)
+            }
+            Internal::open_stay | Internal::open_leave => self.execute_selection(),
+            Internal::page_down => {
+                let page_height = self.page_height as i32;
+                self.move_line(internal_exec, input_invocation, page_height, false)
+            }
+            Internal::page_up => {
+                let page_height = self.page_height as i32;
+                self.move_line(internal_exec, input_invocation, -page_height, false)
+            }
+            Internal::palette => CmdResult::Keep,
+            _ => self.on_internal_generic(
+                w,
+                internal_exec,
+                input_invocation,
+                trigger_type,
+                app_state,
+                cc,
+            )?,
+        })
+    }
+
+    fn on_click(
+        &mut self,
+        _x: u16,
+        y: u16,
+        _screen: Screen,
+        _con: &AppContext,
+    ) -> Result<CmdResult, ProgramError> {
+        if y >= 2 {
+            let y = y as usize - 2 + self.scroll;
+            if y < self.displayed.len() {
+                self.selection_idx = y;
+            }
+        }
+        Ok(CmdResult::Keep)
+    }
+
+    fn on_double_click(
+        &mut self,
+        _w: &mut W,
+        _x: u16,
+        y: u16,
+        _app_state: &mut AppState,
+        _cc: &CmdContext,
+    ) -> Result<CmdResult, ProgramError> {
+        // the click preceding the double click selected the row, if any
+        if y >= 2 && y as usize - 2 + self.scroll == self.selection_idx {
+            Ok(self.execute_selection())
+        } else {
+            Ok(CmdResult::Keep)
+        }
+    }
+}
+
+#[cfg(test)]
+mod palette_state_test {
+
+    use super::*;
+
+    fn row(entry: PaletteEntry, command: &str, key: &str, description: &str) -> PaletteRow {
+        PaletteRow {
+            entry,
+            command: command.to_string(),
+            key: key.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    fn pattern(raw: &str) -> Pattern {
+        let parts = CommandParts::from(raw.to_string());
+        Pattern::new(&parts.pattern, &SearchModeMap::default()).unwrap()
+    }
+
+    #[test]
+    fn check_palette_filtering() {
+        let rows = vec![
+            row(PaletteEntry::Recent(VerbInvocation::from("rm")), "rm", "", "recently typed"),
+            row(PaletteEntry::Verb(0), ":toggle_hidden", "alt-h", "toggle showing hidden files"),
+            row(PaletteEntry::Verb(1), ":rm", "", "remove the file"),
+            row(PaletteEntry::Verb(2), ":focus", "enter", "display the directory"),
+        ];
+        assert_eq!(matching_rows(&rows, &Pattern::None), vec![0, 1, 2, 3]);
+        // the command, the key and the description are searched
+        assert_eq!(matching_rows(&rows, &pattern("hidden")), vec![1]);
+        assert_eq!(matching_rows(&rows, &pattern("alt-h")), vec![1]);
+        assert_eq!(matching_rows(&rows, &pattern("directory")), vec![3]);
+        // with equal scores, the recent command stays first
+        let matches = matching_rows(&rows, &pattern("rm"));
+        assert_eq!(&matches[..2], &[0, 2]);
+        assert!(matching_rows(&rows, &pattern("zzz")).is_empty());
+    }
+}
@@ -3,6 +3,7 @@ mod common;
 mod closest;
 mod from;
 mod normalize;
+mod score_adjustment;
 mod special_path;
 
 pub use {
@@ -11,5 +12,6 @@ pub use {
     common::*,
     from::*,
     normalize::*,
+    score_adjustment::*,
     special_path::*,
 };
@@ -0,0 +1,77 @@
+use {
+    super::Glob,
+    ahash::AHashMap,
+};
+
+/// the adjustments applied when no configuration overrides them: they
+/// push down the matches in build artifacts and dependencies
+static DEFAULT_SCORE_ADJUSTMENTS: &[(&str, i32)] = &[
+    ("**/target/**", -500),
+    ("**/node_modules/**", -500),
+    ("**/__pycache__/**", -500),
+    ("**/*.min.js", -300),
+    ("**/*.min.css", -300),
+];
+
+/// a change of the score of the matching paths whose subpath
+/// (relative to the root of the tree) matches the glob
+#[derive(Debug, Clone)]
+pub struct ScoreAdjustment {
+    pub pattern: glob::Pattern,
+    pub score: i32,
+}
+
+pub trait ScoreAdjustmentList {
+    /// return the sum of the adjustments applying to the subpath
+    fn adjustment(self, subpath: &str) -> i32;
+}
+
+impl ScoreAdjustment {
+    /// build the list of adjustments from the configured ones
+    /// and the default ones (which can be cancelled by configuring
+    /// the same glob with a score of 0)
+    pub fn list(configured: &AHashMap<Glob, i32>) -> Vec<Self> {
+        let mut list: Vec<Self> = configured
+            .iter()
+            .filter(|(_, &score)| score != 0)
+            .map(|(glob, &score)| Self {
+                pattern: glob.pattern().clone(),
+                score,
+            })
+            .collect();
+        for &(glob, score) in DEFAULT_SCORE_ADJUSTMENTS {
+            if !configured.keys().any(|g| g.pattern().as_str() == glob) {
+                list.push(Self {
+                    pattern: glob::Pattern::new(glob).unwrap(),
+                    score,
+                });
+            }
+        }
+        list
+    }
+}
+
+impl ScoreAdjustmentList for &[ScoreAdjustment] {
+    fn adjustment(self, subpath: &str) -> i32 {
+        self.iter()
+            .filter(|sa| sa.pattern.matches(subpath))
+            .map(|sa| sa.score)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod score_adjustment_test {
+
+    use super::*;
+
+    #[test]
+    fn test_default_adjustments() {
+        let list = ScoreAdjustment::list(&AHashMap::default());
+        let list = list.as_slice();
+        assert_eq!(list.adjustment("src/main.rs"), 0);
+        assert_eq!(list.adjustment("target/debug/main"), -500);
+        assert_eq!(list.adjustment("web/node_modules/lib.min.js"), -800);
+        assert_eq!(list.adjustment("web/app.min.js"), -300);
+    }
+}
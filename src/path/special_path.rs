@@ -15,6 +15,8 @@ pub enum SpecialHandling {
     Enter,
     NoEnter,
     Hide,
+    NoRecurse, // shown with its children, but not explored by searches
+    MaxDepth(u16), // not explored deeper than this number of levels
 }
 
 #[derive(Debug, Clone)]
@@ -40,10 +42,14 @@ impl<'de> Deserialize<'de> for SpecialHandling {
             "enter" => Ok(SpecialHandling::Enter),
             "noenter" => Ok(SpecialHandling::NoEnter),
             "hide" => Ok(SpecialHandling::Hide),
-            _ => Err(D::Error::custom(format!(
-                "unrecognized special handling: {:?}",
-                s
-            ))),
+            "norecurse" | "norecursebutshow" => Ok(SpecialHandling::NoRecurse),
+            _ => match s.strip_prefix("maxdepth").and_then(|n| n.parse().ok()) {
+                Some(depth) => Ok(SpecialHandling::MaxDepth(depth)),
+                None => Err(D::Error::custom(format!(
+                    "unrecognized special handling: {:?}",
+                    s
+                ))),
+            },
         }
     }
 }
@@ -59,6 +65,12 @@ impl<'de> Deserialize<'de> for Glob {
     }
 }
 
+impl Glob {
+    pub fn pattern(&self) -> &glob::Pattern {
+        &self.pattern
+    }
+}
+
 impl SpecialPath {
     pub fn new(glob: Glob, handling: SpecialHandling) -> Self {
         Self {
@@ -82,3 +94,29 @@ impl SpecialPathList for &[SpecialPath] {
         SpecialHandling::None
     }
 }
+
+#[cfg(test)]
+mod special_path_test {
+
+    use {
+        super::*,
+        std::collections::HashMap,
+    };
+
+    #[test]
+    fn test_parse_handling() {
+        let handlings: HashMap<String, SpecialHandling> = toml::from_str(r#"
+            a = "no-enter"
+            b = "no-recurse"
+            c = "no-recurse-but-show"
+            d = "max-depth 2"
+            e = "Hide"
+        "#).unwrap();
+        assert_eq!(handlings["a"], SpecialHandling::NoEnter);
+        assert_eq!(handlings["b"], SpecialHandling::NoRecurse);
+        assert_eq!(handlings["c"], SpecialHandling::NoRecurse);
+        assert_eq!(handlings["d"], SpecialHandling::MaxDepth(2));
+        assert_eq!(handlings["e"], SpecialHandling::Hide);
+        assert!(toml::from_str::<HashMap<String, SpecialHandling>>("a = \"max-depth\"").is_err());
+    }
+}
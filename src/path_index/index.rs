@@ -0,0 +1,399 @@
+use {
+    ahash::{AHashMap, AHashSet},
+    std::{
+        fs,
+        io::{self, BufRead, BufReader, BufWriter, Write},
+        path::{Path, PathBuf},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+};
+
+static HEADER: &str = "broot-path-index 1";
+
+/// what's known of a directory: its mtime when it was read
+/// and the names of its entries
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct IndexedDir {
+    pub mtime: u64,
+    pub dirs: Vec<String>,
+    pub files: Vec<String>,
+}
+
+/// A persistent index of all the paths under a root, which
+/// can be incrementally updated: a directory is read again only
+/// when its mtime changed.
+///
+/// Hidden and gitignored files are indexed too, filtering is done
+/// by the tree builder at query time.
+#[derive(Debug, Clone)]
+pub struct PathIndex {
+    pub root: PathBuf,
+    /// last time the index was brought up to date, in seconds since epoch
+    pub updated: u64,
+    /// the indexed directories, by subpath relative to the root
+    dirs: AHashMap<String, IndexedDir>,
+}
+
+/// return the mtime of the path, in nanoseconds since epoch
+pub fn mtime_of(path: &Path) -> Option<u64> {
+    fs::metadata(path)
+        .and_then(|md| md.modified())
+        .ok()
+        .and_then(|st| st.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// join a parent subpath and a name
+pub fn join_subpath(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+impl IndexedDir {
+    fn read(path: &Path, mtime: u64) -> io::Result<Self> {
+        let mut dir = IndexedDir {
+            mtime,
+            ..Default::default()
+        };
+        for entry in fs::read_dir(path)?.filter_map(Result::ok) {
+            let name = match entry.file_name().into_string() {
+                Ok(name) if !name.contains('\n') => name,
+                _ => continue, // can't be stored
+            };
+            // symlinks aren't followed
+            match entry.file_type() {
+                Ok(ft) if ft.is_dir() => dir.dirs.push(name),
+                Ok(_) => dir.files.push(name),
+                Err(_) => {}
+            }
+        }
+        Ok(dir)
+    }
+}
+
+impl PathIndex {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            updated: 0,
+            dirs: AHashMap::default(),
+        }
+    }
+
+    /// build a virtual index containing only the given paths, which
+    /// must be under the root, and their ancestors. Directories of this
+    /// list appear without children, unless some are listed too.
+    pub fn from_paths(root: PathBuf, paths: &[PathBuf]) -> Self {
+        let mut index = Self::new(root);
+        index.updated = now_secs();
+        let mut inserted = AHashSet::default();
+        for path in paths {
+            let is_dir = match fs::symlink_metadata(path) {
+                Ok(md) => md.is_dir(),
+                Err(_) => continue,
+            };
+            match index.subpath_of(path) {
+                Some(subpath) if !subpath.is_empty() => {
+                    index.insert(subpath, is_dir, &mut inserted);
+                }
+                _ => {}
+            }
+        }
+        index
+    }
+
+    fn insert(&mut self, subpath: String, is_dir: bool, inserted: &mut AHashSet<String>) {
+        if !inserted.insert(subpath.clone()) {
+            return;
+        }
+        let (parent, name) = match subpath.rfind('/') {
+            Some(idx) => (&subpath[..idx], &subpath[idx + 1..]),
+            None => ("", subpath.as_str()),
+        };
+        let dir = self.dirs.entry(parent.to_string()).or_default();
+        if is_dir {
+            dir.dirs.push(name.to_string());
+        } else {
+            dir.files.push(name.to_string());
+        }
+        if !parent.is_empty() {
+            let parent = parent.to_string();
+            self.insert(parent, true, inserted);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.dirs
+            .values()
+            .map(|dir| dir.dirs.len() + dir.files.len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dirs.is_empty()
+    }
+
+    pub fn dir(&self, subpath: &str) -> Option<&IndexedDir> {
+        self.dirs.get(subpath)
+    }
+
+    /// return the subpath, relative to the root of the index,
+    /// of a path in the indexed tree
+    pub fn subpath_of(&self, path: &Path) -> Option<String> {
+        let rel = path.strip_prefix(&self.root).ok()?;
+        let components: Option<Vec<&str>> = rel
+            .components()
+            .map(|c| c.as_os_str().to_str())
+            .collect();
+        Some(components?.join("/"))
+    }
+
+    /// tell whether the index may be used to search the tree
+    /// rooted at the given subpath: it must be recent and none
+    /// of the directories of this tree may have changed since
+    /// they were indexed.
+    ///
+    /// Only the mtimes of the directories are checked, which is
+    /// much cheaper than reading them.
+    pub fn is_fresh(&self, subpath: &str, max_age: Duration) -> bool {
+        if now_secs() > self.updated + max_age.as_secs() {
+            return false;
+        }
+        if !self.dirs.contains_key(subpath) {
+            return false;
+        }
+        let mut stack = vec![subpath.to_string()];
+        while let Some(subpath) = stack.pop() {
+            let dir = match self.dirs.get(&subpath) {
+                Some(dir) => dir,
+                None => continue, // it couldn't be read when indexing
+            };
+            if mtime_of(&self.root.join(&subpath)) != Some(dir.mtime) {
+                debug!("indexed dir {:?} changed", &subpath);
+                return false;
+            }
+            for name in &dir.dirs {
+                stack.push(join_subpath(&subpath, name));
+            }
+        }
+        true
+    }
+
+    /// bring the index up to date, reading again only the
+    /// directories whose mtime changed
+    pub fn update(&mut self) {
+        let mut old_dirs = std::mem::take(&mut self.dirs);
+        let mut stack = vec![String::new()];
+        let mut nb_read = 0;
+        while let Some(subpath) = stack.pop() {
+            let path = self.root.join(&subpath);
+            let mtime = match mtime_of(&path) {
+                Some(mtime) => mtime,
+                None => continue,
+            };
+            let dir = match old_dirs.remove(&subpath) {
+                Some(dir) if dir.mtime == mtime => dir,
+                _ => {
+                    nb_read += 1;
+                    match IndexedDir::read(&path, mtime) {
+                        Ok(dir) => dir,
+                        Err(e) => {
+                            debug!("can't index {:?}: {}", &path, e);
+                            continue;
+                        }
+                    }
+                }
+            };
+            for name in &dir.dirs {
+                stack.push(join_subpath(&subpath, name));
+            }
+            self.dirs.insert(subpath, dir);
+        }
+        self.updated = now_secs();
+        debug!(
+            "path index of {:?} updated: {} dirs, {} read",
+            &self.root,
+            self.dirs.len(),
+            nb_read,
+        );
+    }
+
+    pub fn parse<R: BufRead>(reader: R) -> io::Result<Self> {
+        let invalid = |s: &str| io::Error::new(io::ErrorKind::InvalidData, s.to_string());
+        let mut lines = reader.lines();
+        if lines.next().transpose()?.as_deref() != Some(HEADER) {
+            return Err(invalid("not a path index"));
+        }
+        let root = lines.next().transpose()?.ok_or_else(|| invalid("no root"))?;
+        let updated = lines
+            .next()
+            .transpose()?
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| invalid("no update time"))?;
+        let mut index = PathIndex {
+            root: PathBuf::from(root),
+            updated,
+            dirs: AHashMap::default(),
+        };
+        let mut current: Option<(String, IndexedDir)> = None;
+        for line in lines {
+            let line = line?;
+            let mut parts = line.splitn(3, '\t');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("D"), Some(mtime), Some(subpath)) => {
+                    let mtime = mtime.parse().map_err(|_| invalid("invalid mtime"))?;
+                    if let Some((subpath, dir)) = current.take() {
+                        index.dirs.insert(subpath, dir);
+                    }
+                    current = Some((
+                        subpath.to_string(),
+                        IndexedDir {
+                            mtime,
+                            ..Default::default()
+                        },
+                    ));
+                }
+                (Some("d"), Some(name), None) => {
+                    if let Some((_, dir)) = current.as_mut() {
+                        dir.dirs.push(name.to_string());
+                    }
+                }
+                (Some("f"), Some(name), None) => {
+                    if let Some((_, dir)) = current.as_mut() {
+                        dir.files.push(name.to_string());
+                    }
+                }
+                _ => {
+                    return Err(invalid("invalid line"));
+                }
+            }
+        }
+        if let Some((subpath, dir)) = current.take() {
+            index.dirs.insert(subpath, dir);
+        }
+        Ok(index)
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(w, "{}", HEADER)?;
+        writeln!(w, "{}", self.root.to_string_lossy())?;
+        writeln!(w, "{}", self.updated)?;
+        for (subpath, dir) in &self.dirs {
+            writeln!(w, "D\t{}\t{}", dir.mtime, subpath)?;
+            for name in &dir.dirs {
+                writeln!(w, "d\t{}", name)?;
+            }
+            for name in &dir.files {
+                writeln!(w, "f\t{}", name)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn load(file_path: &Path) -> io::Result<Self> {
+        Self::parse(BufReader::new(fs::File::open(file_path)?))
+    }
+
+    pub fn save(&self, file_path: &Path) -> io::Result<()> {
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // we write in a temporary file so that a broot reading
+        // the index never sees a partial file
+        let tmp_path = file_path.with_extension("tmp");
+        let mut w = BufWriter::new(fs::File::create(&tmp_path)?);
+        self.write(&mut w)?;
+        w.flush()?;
+        drop(w);
+        fs::rename(&tmp_path, file_path)
+    }
+}
+
+#[cfg(test)]
+mod index_test {
+
+    use super::*;
+
+    #[test]
+    fn test_path_index_round_trip() {
+        let mut index = PathIndex::new(PathBuf::from("/some/root"));
+        index.updated = 1234;
+        index.dirs.insert(
+            String::new(),
+            IndexedDir {
+                mtime: 12,
+                dirs: vec!["src".to_string()],
+                files: vec!["Cargo.toml".to_string(), "a b.txt".to_string()],
+            },
+        );
+        index.dirs.insert(
+            "src".to_string(),
+            IndexedDir {
+                mtime: 13,
+                dirs: vec![],
+                files: vec!["main.rs".to_string()],
+            },
+        );
+        let mut content = Vec::new();
+        index.write(&mut content).unwrap();
+        let parsed = PathIndex::parse(&content[..]).unwrap();
+        assert_eq!(parsed.root, index.root);
+        assert_eq!(parsed.updated, 1234);
+        assert_eq!(parsed.dirs, index.dirs);
+        assert_eq!(parsed.len(), 4);
+        assert_eq!(
+            parsed.subpath_of(Path::new("/some/root/src")).as_deref(),
+            Some("src"),
+        );
+        assert_eq!(parsed.subpath_of(Path::new("/other")), None);
+    }
+
+    #[test]
+    fn test_path_index_from_paths() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let paths = vec![
+            root.join("src/path_index/mod.rs"),
+            root.join("src/path_index"),
+            root.join("src/main.rs"),
+            root.join("src/not-a-file.rs"),
+        ];
+        let index = PathIndex::from_paths(root, &paths);
+        assert_eq!(index.dir("").unwrap().dirs, vec!["src".to_string()]);
+        let src = index.dir("src").unwrap();
+        assert_eq!(src.dirs, vec!["path_index".to_string()]);
+        assert_eq!(src.files, vec!["main.rs".to_string()]);
+        assert_eq!(index.dir("src/path_index").unwrap().files, vec!["mod.rs".to_string()]);
+        assert_eq!(index.len(), 4);
+    }
+
+    #[test]
+    fn test_path_index_freshness() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::write(root.join("a/b/c.txt"), "c").unwrap();
+        let mut index = PathIndex::new(root.clone());
+        index.update();
+        let max_age = Duration::from_secs(3600);
+        assert!(index.is_fresh("", max_age));
+        assert!(index.is_fresh("a", max_age));
+        assert!(!index.is_fresh("not-indexed", max_age));
+        // a change deep in the tree doesn't change the mtime of the root
+        // but makes the index stale for all the ancestors
+        fs::write(root.join("a/b/d.txt"), "d").unwrap();
+        assert!(!index.is_fresh("", max_age));
+        assert!(!index.is_fresh("a/b", max_age));
+        index.update();
+        assert!(index.is_fresh("", max_age));
+    }
+}
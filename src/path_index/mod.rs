@@ -0,0 +1,128 @@
+//! A persistent index of the paths of a tree, built in background
+//! with `:build_index`, and which the tree builder queries instead
+//! of walking the disk when searching huge trees.
+//!
+//! An index is used only when it's fresh. When it's stale it's
+//! incrementally updated in background and the tree builder falls
+//! back to a live walk meanwhile.
+
+mod index;
+
+pub use index::*;
+
+use {
+    crate::conf,
+    ahash::{AHashMap, AHashSet},
+    std::{
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex},
+        thread,
+        time::{Duration, Instant},
+    },
+};
+
+/// an index not updated since this duration isn't used
+pub static MAX_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// a freshness check is reused during this duration, so that the
+/// directories aren't all checked again on every typed char
+static FRESHNESS_CHECK_TTL: Duration = Duration::from_secs(3);
+
+lazy_static! {
+    /// the indexes already loaded in memory, by root
+    static ref INDEXES: Mutex<AHashMap<PathBuf, Arc<PathIndex>>> = Mutex::default();
+    /// the roots whose index is being built or updated
+    static ref PENDING: Mutex<AHashSet<PathBuf>> = Mutex::default();
+    /// when the indexes were last found fresh, by root and subpath
+    static ref FRESH: Mutex<AHashMap<(PathBuf, String), Instant>> = Mutex::default();
+}
+
+/// return the path to the file where the index of the given root
+/// is stored (in the cache directory)
+pub fn index_file_path(root: &Path) -> PathBuf {
+    // FNV-1a, because we need a hash which is stable between versions
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in root.to_string_lossy().bytes() {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    conf::app_dirs()
+        .cache_dir()
+        .join("path-indexes")
+        .join(format!("{:016x}.txt", hash))
+}
+
+/// return the index of the given root, loading it from disk if
+/// it's not already in memory
+fn get_index(root: &Path) -> Option<Arc<PathIndex>> {
+    if let Some(index) = INDEXES.lock().unwrap().get(root) {
+        return Some(Arc::clone(index));
+    }
+    let file_path = index_file_path(root);
+    if !file_path.exists() {
+        return None;
+    }
+    match time!("load path index", PathIndex::load(&file_path)) {
+        Ok(index) if index.root == root => {
+            let index = Arc::new(index);
+            INDEXES.lock().unwrap().insert(root.to_path_buf(), Arc::clone(&index));
+            Some(index)
+        }
+        Ok(_) => None, // hash collision
+        Err(e) => {
+            warn!("invalid path index {:?}: {}", &file_path, e);
+            None
+        }
+    }
+}
+
+/// build or update the index of the given root, in a background
+/// thread. Does nothing if it's already being done.
+pub fn update_in_background(root: PathBuf) {
+    if !PENDING.lock().unwrap().insert(root.clone()) {
+        return;
+    }
+    thread::spawn(move || {
+        let mut index = match get_index(&root) {
+            Some(index) => PathIndex::clone(&index),
+            None => PathIndex::new(root.clone()),
+        };
+        time!("update path index", index.update());
+        let file_path = index_file_path(&root);
+        if let Err(e) = index.save(&file_path) {
+            warn!("failed to save path index {:?}: {}", &file_path, e);
+        }
+        INDEXES.lock().unwrap().insert(root.clone(), Arc::new(index));
+        FRESH.lock().unwrap().retain(|(fresh_root, _), _| fresh_root != &root);
+        PENDING.lock().unwrap().remove(&root);
+    });
+}
+
+/// return a fresh index covering the given path (which may be
+/// a directory deep inside the indexed root), with the subpath
+/// of the path in that index.
+///
+/// If an index covers the path but is stale, an update is launched
+/// in background and None is returned.
+pub fn fresh_index_for(path: &Path) -> Option<(Arc<PathIndex>, String)> {
+    for root in path.ancestors() {
+        if let Some(index) = get_index(root) {
+            let subpath = index.subpath_of(path)?;
+            let key = (root.to_path_buf(), subpath);
+            let recently_fresh = match FRESH.lock().unwrap().get(&key) {
+                Some(checked) => checked.elapsed() < FRESHNESS_CHECK_TTL,
+                None => false,
+            };
+            if recently_fresh || index.is_fresh(&key.1, MAX_AGE) {
+                if !recently_fresh {
+                    FRESH.lock().unwrap().insert(key.clone(), Instant::now());
+                }
+                return Some((index, key.1));
+            }
+            debug!("path index of {:?} is stale", root);
+            update_in_background(root.to_path_buf());
+            return None;
+        }
+    }
+    None
+}
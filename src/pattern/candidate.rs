@@ -1,5 +1,6 @@
 use {
     crate::{
+        task_sync::CancellationToken,
         tree::TreeLine,
     },
     std::{
@@ -23,6 +24,9 @@ pub struct Candidate<'c> {
 
     /// whether the file is regular (ie has a searchable content)
     pub regular_file: bool,
+
+    /// a token to check during long evaluations (eg content searches)
+    pub cancellation: Option<&'c CancellationToken>,
 }
 
 impl<'c> Candidate<'c> {
@@ -32,6 +36,7 @@ impl<'c> Candidate<'c> {
             subpath: &line.subpath,
             name: &line.name,
             regular_file: line.is_file(),
+            cancellation: None,
         }
     }
 }
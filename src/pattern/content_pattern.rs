@@ -46,9 +46,10 @@ impl ContentExactPattern {
         if !candidate.regular_file {
             return None;
         }
-        match self.needle.search(&candidate.path) {
+        match self.needle.search(candidate.path, candidate.cancellation) {
             Ok(ContentSearchResult::Found { .. }) => Some(1),
             Ok(ContentSearchResult::NotFound) => None,
+            Ok(ContentSearchResult::Cancelled) => None,
             Ok(ContentSearchResult::NotSuitable) => {
                 // debug!("{:?} isn't suitable for search", &candidate.path);
                 None
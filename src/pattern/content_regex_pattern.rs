@@ -3,6 +3,7 @@ use {
     super::*,
     crate::{
         content_search::*,
+        task_sync::CancellationToken,
     },
     regex,
     std::{
@@ -44,11 +45,18 @@ impl ContentRegexPattern {
     }
 
     // TODO optimize with regex::bytes ?
-    fn has_match(&self, path: &Path) -> io::Result<bool> {
+    fn has_match(
+        &self,
+        path: &Path,
+        cancellation: Option<&CancellationToken>,
+    ) -> io::Result<bool> {
         for line in BufReader::new(File::open(path)?).lines() {
             if self.rex.is_match(line?.as_str()) {
                 return Ok(true);
             }
+            if matches!(cancellation, Some(c) if c.is_cancelled()) {
+                return Ok(false);
+            }
         }
         Ok(false)
     }
@@ -57,7 +65,7 @@ impl ContentRegexPattern {
         if !candidate.regular_file || is_path_binary(&candidate.path) {
             return None;
         }
-        match self.has_match(&candidate.path) {
+        match self.has_match(candidate.path, candidate.cancellation) {
             Ok(true) => Some(1),
             Ok(false) => None,
             Err(e) => {
@@ -72,15 +80,17 @@ impl ContentRegexPattern {
         path: &Path,
         desired_len: usize,
     ) -> io::Result<Option<ContentMatch>> {
-        for line in BufReader::new(File::open(path)?).lines() {
+        for (idx, line) in BufReader::new(File::open(path)?).lines().enumerate() {
             let line = line?;
             if let Some(regex_match) = self.rex.find(line.as_str()) {
-                return Ok(Some(ContentMatch::build(
+                let mut content_match = ContentMatch::build(
                     line.as_bytes(),
                     regex_match.start(),
                     regex_match.as_str(),
                     desired_len,
-                )));
+                );
+                content_match.line = idx + 1;
+                return Ok(Some(content_match));
             }
         }
         Ok(None)
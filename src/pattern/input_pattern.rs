@@ -8,6 +8,19 @@ use {
     bet::BeTree,
 };
 
+/// the suffix of a pattern asking for a total search, which
+/// ignores the time budget and the result cap
+const UNBOUNDED_SUFFIX: &str = "!!";
+
+/// tell whether the string starts with the suffix asking for an
+/// unbounded search, and this suffix ends the pattern
+pub fn starts_with_unbounded_suffix(s: &str) -> bool {
+    match s.strip_prefix(UNBOUNDED_SUFFIX) {
+        Some(rest) => matches!(rest.chars().next(), None | Some(' ') | Some(':')),
+        None => false,
+    }
+}
+
 /// wraps both
 /// - the "pattern" (which may be used to filter and rank file entries)
 /// - the source raw string which was used to build it and which may
@@ -39,6 +52,11 @@ impl InputPattern {
     pub fn is_some(&self) -> bool {
         self.pattern.is_some()
     }
+    /// tell whether the raw pattern ends with the `!!` suffix
+    /// asking for a total search
+    pub fn is_unbounded(&self) -> bool {
+        self.raw.ends_with(UNBOUNDED_SUFFIX) && !self.raw.ends_with("\\!!")
+    }
     /// empties the pattern and return it
     /// Similar to Option::take
     pub fn take(&mut self) -> Self {
@@ -68,3 +86,30 @@ impl InputPattern {
             .unwrap_or_else(InputPattern::none)
     }
 }
+
+#[cfg(test)]
+mod input_pattern_test {
+
+    use super::*;
+
+    fn input_pattern(raw: &str) -> InputPattern {
+        InputPattern {
+            raw: raw.to_string(),
+            pattern: Pattern::None,
+        }
+    }
+
+    #[test]
+    fn check_unbounded_suffix() {
+        assert!(starts_with_unbounded_suffix("!!"));
+        assert!(starts_with_unbounded_suffix("!! rm"));
+        assert!(starts_with_unbounded_suffix("!!:rm"));
+        assert!(!starts_with_unbounded_suffix("!!a"));
+        assert!(!starts_with_unbounded_suffix("!a"));
+        assert!(input_pattern("foo!!").is_unbounded());
+        assert!(input_pattern("c/todo!!").is_unbounded());
+        assert!(!input_pattern("foo").is_unbounded());
+        assert!(!input_pattern("foo!").is_unbounded());
+        assert!(!input_pattern("foo\\!!").is_unbounded());
+    }
+}
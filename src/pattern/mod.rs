@@ -8,12 +8,15 @@ mod fuzzy_pattern;
 mod input_pattern;
 mod name_match;
 mod operator;
+mod owner_pattern;
 mod pattern;
 mod pattern_object;
 mod pattern_parts;
+mod perm_pattern;
 mod pos;
 mod regex_pattern;
 mod search_mode;
+mod tag_pattern;
 mod tok_pattern;
 
 pub use {
@@ -23,15 +26,18 @@ pub use {
     content_regex_pattern::ContentRegexPattern,
     exact_pattern::ExactPattern,
     fuzzy_pattern::FuzzyPattern,
-    input_pattern::InputPattern,
+    input_pattern::{starts_with_unbounded_suffix, InputPattern},
     name_match::NameMatch,
     pattern::Pattern,
     pattern_object::PatternObject,
     pattern_parts::PatternParts,
     pos::*,
     operator::PatternOperator,
+    owner_pattern::OwnerPattern,
+    perm_pattern::PermPattern,
     regex_pattern::RegexPattern,
     search_mode::*,
+    tag_pattern::TagPattern,
     tok_pattern::*,
 };
 
@@ -0,0 +1,41 @@
+use {
+    super::*,
+    std::fmt,
+};
+
+/// A pattern matching the files owned by a given user, designated
+/// either by name or by uid
+#[derive(Debug, Clone)]
+pub struct OwnerPattern {
+    owner: String,
+}
+
+impl fmt::Display for OwnerPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.owner.fmt(f)
+    }
+}
+
+impl OwnerPattern {
+    pub fn from(owner: &str) -> Self {
+        Self {
+            owner: owner.to_string(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.owner.is_empty()
+    }
+
+    pub fn score_of(&self, candidate: Candidate) -> Option<i32> {
+        #[cfg(not(any(target_family = "windows", target_os = "android")))]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let uid = std::fs::metadata(candidate.path).ok()?.uid();
+            if self.owner == uid.to_string() || self.owner == crate::permissions::user_name(uid) {
+                return Some(1);
+            }
+        }
+        None
+    }
+}
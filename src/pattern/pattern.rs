@@ -24,6 +24,9 @@ pub enum Pattern {
     PathTokens(TokPattern),
     ContentExact(ContentExactPattern),
     ContentRegex(ContentRegexPattern),
+    Tag(TagPattern),
+    Permissions(PermPattern),
+    Owner(OwnerPattern),
     Composite(CompositePattern),
 }
 
@@ -74,6 +77,15 @@ impl Pattern {
                             SearchMode::ContentRegex => Self::ContentRegex(
                                 ContentRegexPattern::from(core, flags.unwrap_or(""))?
                             ),
+                            SearchMode::Tag => Self::Tag(
+                                TagPattern::from(core)
+                            ),
+                            SearchMode::Permissions => Self::Permissions(
+                                PermPattern::from(core)?
+                            ),
+                            SearchMode::Owner => Self::Owner(
+                                OwnerPattern::from(core)
+                            ),
                         }
                     }
                 )
@@ -90,7 +102,7 @@ impl Pattern {
     pub fn object(&self) -> PatternObject {
         let mut object = PatternObject::default();
         match self {
-            Self::None => {}
+            Self::None | Self::Tag(_) | Self::Permissions(_) | Self::Owner(_) => {}
             Self::NameExact(_) | Self::NameFuzzy(_) | Self::NameRegex(_) | Self::NameTokens(_) => {
                 object.name = true;
             }
@@ -154,6 +166,9 @@ impl Pattern {
             Self::PathTokens(tp) => tp.score_of(&candidate.subpath),
             Self::ContentExact(cp) => cp.score_of(candidate),
             Self::ContentRegex(cp) => cp.score_of(candidate),
+            Self::Tag(tp) => tp.score_of(candidate),
+            Self::Permissions(pp) => pp.score_of(candidate),
+            Self::Owner(op) => op.score_of(candidate),
             Self::Composite(cp) => cp.score_of(candidate),
             Self::None => Some(1),
         }
@@ -171,6 +186,9 @@ impl Pattern {
             Self::PathTokens(tp) => tp.score_of(&candidate),
             Self::ContentExact(_) => None, // this isn't suitable
             Self::ContentRegex(_) => None, // this isn't suitable
+            Self::Tag(_) => None, // this isn't suitable
+            Self::Permissions(_) => None, // this isn't suitable
+            Self::Owner(_) => None, // this isn't suitable
             Self::Composite(cp) => cp.score_of_string(candidate),
             Self::None => Some(1),
         }
@@ -194,6 +212,9 @@ impl Pattern {
             Self::PathTokens(tp) => tp.is_empty(),
             Self::ContentExact(ep) => ep.is_empty(),
             Self::ContentRegex(rp) => rp.is_empty(),
+            Self::Tag(tp) => tp.is_empty(),
+            Self::Permissions(pp) => pp.is_empty(),
+            Self::Owner(op) => op.is_empty(),
             Self::Composite(cp) => cp.is_empty(),
             Self::None => true,
         }
@@ -0,0 +1,113 @@
+use {
+    super::*,
+    crate::errors::PatternError,
+    std::fmt,
+};
+
+/// A pattern matching the files whose unix permissions satisfy
+/// a chmod like predicate, eg `+x` (executable by someone),
+/// `o+w` (world writable) or `u-w` (not writable by its owner)
+#[derive(Debug, Clone)]
+pub struct PermPattern {
+    raw: String,
+    classes: Vec<u32>, // the shifts of the designated classes (6 for user, 3 for group, 0 for other)
+    perms: u32,        // the rwx bits, as for the "other" class
+    set: bool,         // whether the bits must be set (+) or unset (-)
+}
+
+impl fmt::Display for PermPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.raw.fmt(f)
+    }
+}
+
+impl PermPattern {
+    pub fn from(raw: &str) -> Result<Self, PatternError> {
+        let invalid = || PatternError::InvalidPermissions { raw: raw.to_string() };
+        let op_idx = raw.find(&['+', '-'][..]).ok_or_else(invalid)?;
+        let mut classes = Vec::new();
+        for c in raw[..op_idx].chars() {
+            match c {
+                'u' => classes.push(6),
+                'g' => classes.push(3),
+                'o' => classes.push(0),
+                'a' => classes.extend(&[6, 3, 0]),
+                _ => return Err(invalid()),
+            }
+        }
+        if classes.is_empty() {
+            classes.extend(&[6, 3, 0]);
+        }
+        let mut perms = 0;
+        for c in raw[op_idx + 1..].chars() {
+            perms |= match c {
+                'r' => 0o4,
+                'w' => 0o2,
+                'x' => 0o1,
+                _ => return Err(invalid()),
+            };
+        }
+        if perms == 0 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            raw: raw.to_string(),
+            classes,
+            perms,
+            set: raw[op_idx..].starts_with('+'),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// with `+`, tell whether one of the designated classes has all the
+    /// permissions, with `-`, whether none of them has any of them
+    pub fn matches_mode(&self, mode: u32) -> bool {
+        if self.set {
+            self.classes.iter().any(|s| (mode >> s) & self.perms == self.perms)
+        } else {
+            self.classes.iter().all(|s| (mode >> s) & self.perms == 0)
+        }
+    }
+
+    pub fn score_of(&self, candidate: Candidate) -> Option<i32> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let md = std::fs::metadata(candidate.path).ok()?;
+            if self.matches_mode(md.mode()) {
+                return Some(1);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod perm_pattern_test {
+
+    use super::*;
+
+    #[test]
+    fn check_perm_predicates() {
+        let executable = PermPattern::from("+x").unwrap();
+        assert!(executable.matches_mode(0o744));
+        assert!(executable.matches_mode(0o601));
+        assert!(!executable.matches_mode(0o644));
+        let world_writable = PermPattern::from("o+w").unwrap();
+        assert!(world_writable.matches_mode(0o666));
+        assert!(world_writable.matches_mode(0o1777));
+        assert!(!world_writable.matches_mode(0o664));
+        let readonly_for_owner = PermPattern::from("u-w").unwrap();
+        assert!(readonly_for_owner.matches_mode(0o444));
+        assert!(!readonly_for_owner.matches_mode(0o644));
+        let group_rw = PermPattern::from("g+rw").unwrap();
+        assert!(group_rw.matches_mode(0o660));
+        assert!(!group_rw.matches_mode(0o640));
+        assert!(PermPattern::from("x").is_err());
+        assert!(PermPattern::from("z+x").is_err());
+        assert!(PermPattern::from("+").is_err());
+    }
+}
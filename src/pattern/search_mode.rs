@@ -13,6 +13,9 @@ pub enum SearchObject {
     Name,
     Path,
     Content,
+    Tag,
+    Permissions,
+    Owner,
 }
 /// how to search
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -38,6 +41,9 @@ pub enum SearchMode {
     PathTokens,
     ContentExact,
     ContentRegex,
+    Tag,
+    Permissions,
+    Owner,
 }
 
 pub static SEARCH_MODES: &[SearchMode] = &[
@@ -51,6 +57,9 @@ pub static SEARCH_MODES: &[SearchMode] = &[
     SearchMode::PathTokens,
     SearchMode::ContentExact,
     SearchMode::ContentRegex,
+    SearchMode::Tag,
+    SearchMode::Permissions,
+    SearchMode::Owner,
 ];
 
 impl SearchMode {
@@ -77,6 +86,17 @@ impl SearchMode {
             (Content, Fuzzy) => None, // unsupported for now - could be but why ?
             (Content, Regex) => Some(Self::ContentRegex),
             (Content, Tokens) => None, // unsupported for now - could be but need bench
+
+            (Tag, Unspecified) => Some(Self::Tag),
+            (Tag, Exact) => Some(Self::Tag),
+            (Tag, _) => None, // tags are short and few, exact match is enough
+
+            (Permissions, Unspecified) => Some(Self::Permissions),
+            (Permissions, _) => None, // permissions are predicates, not strings
+
+            (Owner, Unspecified) => Some(Self::Owner),
+            (Owner, Exact) => Some(Self::Owner),
+            (Owner, _) => None,
         }
     }
     pub fn object(&self) -> SearchObject {
@@ -84,6 +104,9 @@ impl SearchMode {
             Self::NameExact | Self::NameFuzzy | Self::NameRegex | Self::NameTokens => SearchObject::Name,
             Self::PathExact | Self::PathFuzzy | Self::PathRegex | Self::PathTokens => SearchObject::Path,
             Self::ContentExact | Self::ContentRegex => SearchObject::Content,
+            Self::Tag => SearchObject::Tag,
+            Self::Permissions => SearchObject::Permissions,
+            Self::Owner => SearchObject::Owner,
         }
     }
     pub fn kind(&self) -> SearchKind {
@@ -98,6 +121,9 @@ impl SearchMode {
             Self::PathTokens => SearchKind::Tokens,
             Self::ContentExact => SearchKind::Exact,
             Self::ContentRegex => SearchKind::Regex,
+            Self::Tag => SearchKind::Exact,
+            Self::Permissions => SearchKind::Unspecified,
+            Self::Owner => SearchKind::Exact,
         }
     }
 }
@@ -125,14 +151,19 @@ impl SearchModeMapEntry {
         let name = s.contains("name");
         let path = s.contains("path");
         let content = s.contains("content");
-        let search_object = match (name, path, content) {
-            //(false, false, false) => SearchObject::Unspecified,
-            (true, false, false) => SearchObject::Name,
-            (false, true, false) => SearchObject::Path,
-            (false, false, true) => SearchObject::Content,
+        let tag = s.contains("tag");
+        let perm = s.contains("perm");
+        let owner = s.contains("owner");
+        let search_object = match (name, path, content, tag, perm, owner) {
+            (true, false, false, false, false, false) => SearchObject::Name,
+            (false, true, false, false, false, false) => SearchObject::Path,
+            (false, false, true, false, false, false) => SearchObject::Content,
+            (false, false, false, true, false, false) => SearchObject::Tag,
+            (false, false, false, false, true, false) => SearchObject::Permissions,
+            (false, false, false, false, false, true) => SearchObject::Owner,
             _ => {
                 return Err(ConfError::InvalidSearchMode {
-                    details: "you must have exactly one of \"name\", \"path\" or \"content".to_string()
+                    details: "you must have exactly one of \"name\", \"path\", \"content\", \"tag\", \"perm\" or \"owner\"".to_string()
                 });
             }
         };
@@ -195,6 +226,9 @@ impl Default for SearchModeMap {
         smm.setm(&["rx", "cr"], SearchMode::ContentRegex);
         smm.setm(&["pt", "tp", "t"], SearchMode::PathTokens);
         smm.setm(&["pn", "np"], SearchMode::NameTokens);
+        smm.setm(&["tag"], SearchMode::Tag);
+        smm.setm(&["perm"], SearchMode::Permissions);
+        smm.setm(&["owner"], SearchMode::Owner);
         smm.set(SearchModeMapEntry { key: None, mode: SearchMode::NameFuzzy });
         smm
     }
@@ -0,0 +1,37 @@
+use {
+    super::*,
+    crate::tag,
+    std::fmt,
+};
+
+/// A pattern matching the paths having a given tag
+#[derive(Debug, Clone)]
+pub struct TagPattern {
+    tag: String,
+}
+
+impl fmt::Display for TagPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tag.fmt(f)
+    }
+}
+
+impl TagPattern {
+    pub fn from(tag: &str) -> Self {
+        Self {
+            tag: tag.to_string(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tag.is_empty()
+    }
+
+    pub fn score_of(&self, candidate: Candidate) -> Option<i32> {
+        if tag::has_tag(candidate.path, &self.tag) {
+            Some(1)
+        } else {
+            None
+        }
+    }
+}
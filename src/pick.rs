@@ -0,0 +1,127 @@
+//! the "pick mode", in which broot is used as a path picker
+//! by scripts: the picked paths are printed on exit
+
+use {
+    crate::{
+        app::{AppContext, CmdResult},
+        errors::ConfError,
+        launchable::Launchable,
+        verb::shell_quote,
+    },
+    clap::ArgMatches,
+    std::{
+        fs::OpenOptions,
+        io::{self, Write},
+        path::PathBuf,
+    },
+};
+
+/// the settings of the pick mode, set with `--pick`
+#[derive(Debug, Clone, Default)]
+pub struct PickConfig {
+    /// the max number of paths which can be picked at once
+    pub max_count: Option<usize>,
+
+    /// whether paths are NUL terminated instead of being quoted
+    /// and newline separated
+    pub print0: bool,
+}
+
+impl PickConfig {
+    /// build the pick config, if the pick mode was asked
+    pub fn from_launch_args(cli_args: &ArgMatches<'_>) -> Result<Option<Self>, ConfError> {
+        if !cli_args.is_present("pick") {
+            return Ok(None);
+        }
+        let max_count = cli_args.value_of("pick")
+            .map(|s| match s.parse() {
+                Ok(n) if n > 0 => Ok(n),
+                _ => Err(ConfError::InvalidLaunchArg {
+                    arg: "pick".to_string(),
+                    value: s.to_string(),
+                    expected: "a number of paths greater than 0".to_string(),
+                }),
+            })
+            .transpose()?;
+        let print0 = cli_args.is_present("print0");
+        Ok(Some(Self { max_count, print0 }))
+    }
+
+    /// build the string written for the given paths
+    pub fn output(&self, paths: &[PathBuf]) -> String {
+        let mut output = String::new();
+        for path in paths {
+            let path = path.to_string_lossy();
+            if self.print0 {
+                output.push_str(&path);
+                output.push('\0');
+            } else {
+                output.push_str(&shell_quote(&path));
+                output.push('\n');
+            }
+        }
+        output
+    }
+
+    /// print the picked paths on exit, or in the `--out` file if one
+    /// was given
+    pub fn pick(&self, paths: &[PathBuf], con: &AppContext) -> io::Result<CmdResult> {
+        if paths.is_empty() {
+            return Ok(CmdResult::error("nothing to pick"));
+        }
+        if let Some(max_count) = self.max_count {
+            if paths.len() > max_count {
+                return Ok(CmdResult::DisplayError(format!(
+                    "{} paths can't be picked, the max is {}",
+                    paths.len(),
+                    max_count,
+                )));
+            }
+        }
+        let output = self.output(paths);
+        Ok(
+            if let Some(ref output_path) = con.launch_args.file_export_path {
+                let mut f = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(output_path)?;
+                f.write_all(output.as_bytes())?;
+                CmdResult::Quit
+            } else {
+                CmdResult::from(Launchable::raw_printer(output))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod pick_test {
+
+    use {
+        super::*,
+        crate::cli::clap_args,
+    };
+
+    fn pick_config(args: &[&str]) -> Result<Option<PickConfig>, ConfError> {
+        let cli_args = clap_args::clap_app().get_matches_from(args);
+        PickConfig::from_launch_args(&cli_args)
+    }
+
+    #[test]
+    fn test_pick_launch_args() {
+        assert!(pick_config(&["broot"]).unwrap().is_none());
+        assert_eq!(pick_config(&["broot", "--pick"]).unwrap().unwrap().max_count, None);
+        assert_eq!(pick_config(&["broot", "--pick=3"]).unwrap().unwrap().max_count, Some(3));
+        assert!(pick_config(&["broot", "--pick=0"]).is_err());
+        assert!(pick_config(&["broot", "--pick=abc"]).is_err());
+    }
+
+    #[test]
+    fn test_pick_output() {
+        let paths = vec![PathBuf::from("/tmp/a.txt"), PathBuf::from("/tmp/it's here")];
+        let config = PickConfig::default();
+        assert_eq!(config.output(&paths), "/tmp/a.txt\n'/tmp/it'\\''s here'\n");
+        let config = PickConfig { print0: true, ..PickConfig::default() };
+        assert_eq!(config.output(&paths), "/tmp/a.txt\0/tmp/it's here\0");
+    }
+}
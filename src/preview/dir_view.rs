@@ -0,0 +1,141 @@
+use {
+    crate::{
+        app::AppContext,
+        display::{CropWriter, DisplayableTree, Screen, SPACE_FILLING, W},
+        errors::ProgramError,
+        skin::PanelSkin,
+        task_sync::Dam,
+        tree::{Tree, TreeOptions},
+        tree_build::TreeBuilder,
+    },
+    crossterm::{
+        cursor,
+        QueueableCommand,
+    },
+    std::path::PathBuf,
+    termimad::Area,
+};
+
+/// a read-only small tree, for the preview of a directory
+pub struct DirView {
+    path: PathBuf,
+    options: TreeOptions,
+    tree: Option<Tree>, // built in a pending task, once the height is known
+    height: usize, // the height the tree was built for
+    wanted_height: usize, // the height of the area, known at display time
+}
+
+impl DirView {
+    pub fn new(path: PathBuf, options: TreeOptions) -> Self {
+        Self {
+            path,
+            options: options.without_pattern(),
+            tree: None,
+            height: 0,
+            wanted_height: 0,
+        }
+    }
+
+    /// tell whether the tree must be built (again) for the
+    /// height of the area
+    pub fn needs_build(&self) -> bool {
+        self.wanted_height > 0 && self.height != self.wanted_height
+    }
+
+    /// build the tree so that it fills the height of the area, with
+    /// the sizes of files when they're wanted (the ones of
+    /// directories would take too long to compute).
+    ///
+    /// The previous tree is kept if the build is interrupted
+    pub fn build_tree(&mut self, dam: &Dam, con: &AppContext) {
+        let height = self.wanted_height;
+        let builder = match TreeBuilder::from(self.path.clone(), self.options.clone(), height, con) {
+            Ok(builder) => builder,
+            Err(e) => {
+                warn!("Error while preparing the preview tree: {:?}", e);
+                self.height = height;
+                return;
+            }
+        };
+        if let Some(mut tree) = builder.build(false, dam) {
+            if tree.options.needs_sum() {
+                tree.fetch_regular_file_sums();
+            }
+            self.tree = Some(tree);
+            self.height = height;
+        }
+    }
+
+    pub fn display(
+        &mut self,
+        w: &mut W,
+        _screen: Screen,
+        panel_skin: &PanelSkin,
+        area: &Area,
+        con: &AppContext,
+    ) -> Result<(), ProgramError> {
+        self.wanted_height = area.height as usize;
+        if let Some(tree) = &self.tree {
+            let dp = DisplayableTree {
+                app_state: None,
+                tree,
+                skin: &panel_skin.styles,
+                ext_colors: &con.ext_colors,
+                area: area.clone(),
+                in_app: true,
+                show_selection: false,
+                ascii: false,
+            };
+            dp.write_on(w)?;
+        } else {
+            // the tree isn't built yet
+            for y in area.top..area.top + area.height {
+                w.queue(cursor::MoveTo(area.left, y))?;
+                let mut cw = CropWriter::new(w, area.width as usize);
+                cw.fill(&panel_skin.styles.default, &SPACE_FILLING)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod dir_view_test {
+
+    use {
+        super::*,
+        crate::search::Searcher,
+        std::fs,
+    };
+
+    #[test]
+    fn check_dir_view_build() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        for i in 0..10 {
+            fs::write(root.join(format!("f{}", i)), "").unwrap();
+        }
+        let searcher = Searcher::new().unwrap();
+        let options = TreeOptions {
+            pattern: searcher.pattern("f1").unwrap(),
+            ..TreeOptions::default()
+        };
+        let mut dir_view = DirView::new(root.to_path_buf(), options);
+        // nothing is built before the height is known
+        assert!(!dir_view.needs_build());
+        dir_view.wanted_height = 5;
+        assert!(dir_view.needs_build());
+        dir_view.build_tree(&Dam::unlimited(), searcher.context());
+        assert!(!dir_view.needs_build());
+        // the pattern of the tree isn't applied to the preview
+        let tree = dir_view.tree.as_ref().unwrap();
+        assert!(tree.options.pattern.is_none());
+        assert_eq!(tree.lines.len(), 11);
+        // a resize requires another build
+        dir_view.wanted_height = 8;
+        assert!(dir_view.needs_build());
+        dir_view.build_tree(&Dam::unlimited(), searcher.context());
+        assert!(!dir_view.needs_build());
+        assert_eq!(dir_view.height, 8);
+    }
+}
@@ -1,9 +1,18 @@
+mod dir_view;
 mod preview;
+mod preview_positions;
 mod preview_state;
 mod zero_len_file_view;
 
+use {
+    crate::errors::ConfError,
+    std::str::FromStr,
+};
+
 pub use {
+    dir_view::DirView,
     preview::Preview,
+    preview_positions::{PreviewPosition, PreviewPositions},
     preview_state::PreviewState,
     zero_len_file_view::ZeroLenFileView,
 };
@@ -18,6 +27,44 @@ pub enum PreviewMode {
     /// it makes sens. Fails if the file isn't in UTF8
     Text,
 
+    /// show the content as text, without syntax coloring
+    Unstyled,
+
     /// show the content of the file as hex
     Hex,
+
+    /// show a small tree, for directories
+    Tree,
+}
+
+impl FromStr for PreviewMode {
+    type Err = ConfError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "image" => Ok(Self::Image),
+            "text" => Ok(Self::Text),
+            "unstyled" => Ok(Self::Unstyled),
+            "hex" | "binary" => Ok(Self::Hex),
+            _ => Err(ConfError::InvalidPreviewMode { mode: s.to_string() }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod preview_mode_test {
+
+    use super::*;
+
+    #[test]
+    fn check_preview_mode_parsing() {
+        assert_eq!("image".parse::<PreviewMode>().unwrap(), PreviewMode::Image);
+        assert_eq!("text".parse::<PreviewMode>().unwrap(), PreviewMode::Text);
+        assert_eq!("unstyled".parse::<PreviewMode>().unwrap(), PreviewMode::Unstyled);
+        assert_eq!("hex".parse::<PreviewMode>().unwrap(), PreviewMode::Hex);
+        assert_eq!("binary".parse::<PreviewMode>().unwrap(), PreviewMode::Hex);
+        assert_eq!("Text".parse::<PreviewMode>().unwrap(), PreviewMode::Text);
+        assert_eq!("HEX".parse::<PreviewMode>().unwrap(), PreviewMode::Hex);
+        assert!("tree".parse::<PreviewMode>().is_err());
+        assert!("".parse::<PreviewMode>().is_err());
+    }
 }
@@ -4,13 +4,14 @@ use {
         app::{AppContext, LineNumber},
         command::ScrollCommand,
         display::*,
-        errors::ProgramError,
+        errors::{ProgramError, TreeBuildError},
         hex::HexView,
         image::ImageView,
         pattern::InputPattern,
         skin::PanelSkin,
         syntactic::SyntacticView,
         task_sync::Dam,
+        tree::TreeOptions,
     },
     crossterm::{cursor, QueueableCommand},
     std::{
@@ -21,6 +22,7 @@ use {
 };
 
 pub enum Preview {
+    Dir(Box<DirView>),
     Image(ImageView),
     Syntactic(SyntacticView),
     Hex(HexView),
@@ -31,20 +33,26 @@ pub enum Preview {
 impl Preview {
     /// build a preview, never failing (but the preview can be Preview::IOError).
     /// If the prefered mode can't be applied, an other mode is chosen.
+    /// Directories are always previewed as trees.
     pub fn new(
         path: &Path,
         prefered_mode: Option<PreviewMode>,
+        tree_options: &TreeOptions,
         con: &AppContext,
     ) -> Self {
+        if path.is_dir() {
+            return Self::Dir(Box::new(DirView::new(path.to_path_buf(), tree_options.clone())));
+        }
         match prefered_mode {
             Some(PreviewMode::Hex) => Self::hex(path),
             Some(PreviewMode::Image) => Self::image(path),
-            Some(PreviewMode::Text) => Self::unfiltered_text(path, con),
-            None => {
+            Some(PreviewMode::Text) => Self::unfiltered_text(path, true, con),
+            Some(PreviewMode::Unstyled) => Self::unfiltered_text(path, false, con),
+            None | Some(PreviewMode::Tree) => {
                 // automatic behavior: image, text, hex
                 ImageView::new(path)
                     .map(Self::Image)
-                    .unwrap_or_else(|_| Self::unfiltered_text(path, con))
+                    .unwrap_or_else(|_| Self::unfiltered_text(path, true, con))
             }
         }
     }
@@ -53,18 +61,28 @@ impl Preview {
     pub fn with_mode(
         path: &Path,
         mode: PreviewMode,
+        tree_options: &TreeOptions,
         con: &AppContext,
     ) -> Result<Self, ProgramError> {
         match mode {
+            PreviewMode::Tree if path.is_dir() => {
+                Ok(Self::Dir(Box::new(DirView::new(path.to_path_buf(), tree_options.clone()))))
+            }
+            PreviewMode::Tree => {
+                Err(TreeBuildError::NotADirectory {
+                    path: path.to_string_lossy().to_string(),
+                }.into())
+            }
             PreviewMode::Hex => {
                 Ok(HexView::new(path.to_path_buf()).map(Self::Hex)?)
             }
             PreviewMode::Image => {
                 ImageView::new(path).map(Self::Image)
             }
-            PreviewMode::Text => {
+            PreviewMode::Text | PreviewMode::Unstyled => {
+                let with_style = mode == PreviewMode::Text;
                 Ok(
-                    SyntacticView::new(path, InputPattern::none(), &mut Dam::unlimited(), con)
+                    SyntacticView::new(path, InputPattern::none(), with_style, &mut Dam::unlimited(), con)
                         .transpose()
                         .expect("syntactic view without pattern shouldn't be none")
                         .map(Self::Syntactic)?,
@@ -86,9 +104,10 @@ impl Preview {
     /// there's a IO problem
     pub fn unfiltered_text(
         path: &Path,
+        with_style: bool,
         con: &AppContext,
     ) -> Self {
-        match SyntacticView::new(path, InputPattern::none(), &mut Dam::unlimited(), con) {
+        match SyntacticView::new(path, InputPattern::none(), with_style, &mut Dam::unlimited(), con) {
             Ok(Some(sv)) => Self::Syntactic(sv),
             Err(ProgramError::ZeroLenFile) => {
                 debug!("zero len file - check if system file");
@@ -109,8 +128,8 @@ impl Preview {
         con: &AppContext,
     ) -> Option<Self> {
         match self {
-            Self::Syntactic(_) => {
-                match SyntacticView::new(path, pattern, dam, con) {
+            Self::Syntactic(sv) => {
+                match SyntacticView::new(path, pattern, sv.with_style, dam, con) {
 
                     // normal finished loading
                     Ok(Some(sv)) => Some(Self::Syntactic(sv)),
@@ -141,7 +160,9 @@ impl Preview {
     /// return the preview_mode, or None if we're on IOError
     pub fn get_mode(&self) -> Option<PreviewMode> {
         match self {
+            Self::Dir(_) => Some(PreviewMode::Tree),
             Self::Image(_) => Some(PreviewMode::Image),
+            Self::Syntactic(sv) if !sv.with_style => Some(PreviewMode::Unstyled),
             Self::Syntactic(_) => Some(PreviewMode::Text),
             Self::ZeroLen(_) => Some(PreviewMode::Text),
             Self::Hex(_) => Some(PreviewMode::Hex),
@@ -164,6 +185,28 @@ impl Preview {
             _ => false,
         }
     }
+    /// scroll the view by dy lines without moving the
+    /// selection, unless it would leave the view
+    pub fn scroll_view(&mut self, dy: i32) -> bool {
+        match self {
+            Self::Syntactic(sv) => sv.scroll_view(dy),
+            Self::Hex(hv) => hv.try_scroll(ScrollCommand::Lines(dy)),
+            _ => false,
+        }
+    }
+    /// tell whether some long computation is needed before the
+    /// preview can be fully displayed
+    pub fn has_pending_task(&self) -> bool {
+        match self {
+            Self::Dir(dv) => dv.needs_build(),
+            _ => false,
+        }
+    }
+    pub fn do_pending_task(&mut self, dam: &Dam, con: &AppContext) {
+        if let Self::Dir(dv) = self {
+            dv.build_tree(dam, con);
+        }
+    }
     pub fn is_filterable(&self) -> bool {
         matches!(self, Self::Syntactic(_))
     }
@@ -186,6 +229,44 @@ impl Preview {
             _ => false,
         }
     }
+    /// go to the line of the given number, which, in a hex
+    /// view, is the one of the 16 bytes long lines
+    pub fn try_goto_line_number(&mut self, number: LineNumber) -> bool {
+        match self {
+            Self::Syntactic(sv) => sv.try_select_line_number(number),
+            Self::Hex(hv) if number > 0 && number <= hv.line_count() => {
+                hv.set_scroll(number - 1);
+                true
+            }
+            _ => false,
+        }
+    }
+    /// return the scroll and selection, so that they can be restored
+    pub fn position(&self) -> Option<PreviewPosition> {
+        match self {
+            Self::Syntactic(sv) => Some(PreviewPosition {
+                scroll: sv.get_scroll(),
+                selected_line: sv.get_selected_line_number(),
+            }),
+            Self::Hex(hv) => Some(PreviewPosition {
+                scroll: hv.get_scroll(),
+                selected_line: None,
+            }),
+            _ => None,
+        }
+    }
+    pub fn restore_position(&mut self, position: PreviewPosition) {
+        match self {
+            Self::Syntactic(sv) => {
+                if let Some(number) = position.selected_line {
+                    sv.try_select_line_number(number);
+                }
+                sv.set_scroll(position.scroll);
+            }
+            Self::Hex(hv) => hv.set_scroll(position.scroll),
+            _ => {}
+        }
+    }
     pub fn unselect(&mut self) {
         if let Self::Syntactic(sv) = self {
             sv.unselect();
@@ -229,6 +310,7 @@ impl Preview {
         con: &AppContext,
     ) -> Result<(), ProgramError> {
         match self {
+            Self::Dir(dv) => dv.display(w, screen, panel_skin, area, con),
             Self::Image(iv) => iv.display(w, screen, panel_skin, area, con),
             Self::Syntactic(sv) => sv.display(w, screen, panel_skin, area, con),
             Self::ZeroLen(zlv) => zlv.display(w, screen, panel_skin, area),
@@ -0,0 +1,66 @@
+use {
+    crate::app::LineNumber,
+    std::{
+        collections::VecDeque,
+        path::{Path, PathBuf},
+    },
+};
+
+/// how many positions are remembered
+const CAPACITY: usize = 100;
+
+/// where the user was in the preview of a file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewPosition {
+    pub scroll: usize,
+    pub selected_line: Option<LineNumber>,
+}
+
+/// the positions in the previews of the recently previewed files,
+/// so that going back to a file shows it as it was left.
+///
+/// The least recently used positions are forgotten first.
+#[derive(Debug, Default)]
+pub struct PreviewPositions {
+    entries: VecDeque<(PathBuf, PreviewPosition)>, // most recent first
+}
+
+impl PreviewPositions {
+    pub fn remember(&mut self, path: &Path, position: PreviewPosition) {
+        self.entries.retain(|(p, _)| p != path);
+        self.entries.push_front((path.to_path_buf(), position));
+        self.entries.truncate(CAPACITY);
+    }
+    pub fn get(&mut self, path: &Path) -> Option<PreviewPosition> {
+        let idx = self.entries.iter().position(|(p, _)| p == path)?;
+        let entry = self.entries.remove(idx)?;
+        let position = entry.1;
+        self.entries.push_front(entry);
+        Some(position)
+    }
+}
+
+#[cfg(test)]
+mod preview_positions_test {
+
+    use super::*;
+
+    fn pos(scroll: usize) -> PreviewPosition {
+        PreviewPosition { scroll, selected_line: None }
+    }
+
+    #[test]
+    fn test_lru() {
+        let mut positions = PreviewPositions::default();
+        for i in 0..CAPACITY {
+            positions.remember(&PathBuf::from(i.to_string()), pos(i));
+        }
+        // using "0" makes it the most recent one, so "1" is forgotten first
+        assert_eq!(positions.get(Path::new("0")), Some(pos(0)));
+        positions.remember(Path::new("new"), pos(1000));
+        assert_eq!(positions.get(Path::new("1")), None);
+        assert_eq!(positions.get(Path::new("0")), Some(pos(0)));
+        positions.remember(Path::new("0"), pos(3));
+        assert_eq!(positions.get(Path::new("0")), Some(pos(3)));
+    }
+}
@@ -1,5 +1,6 @@
 use {
     super::*,
+    ahash::AHashMap,
     crate::{
         app::*,
         command::{Command, ScrollCommand, TriggerType},
@@ -34,6 +35,7 @@ pub struct PreviewState {
     prefered_mode: Option<PreviewMode>,
     tree_options: TreeOptions,
     mode: Mode,
+    positions: PreviewPositions, // where we were in the previously previewed files
 }
 
 impl PreviewState {
@@ -45,7 +47,7 @@ impl PreviewState {
         con: &AppContext,
     ) -> PreviewState {
         let preview_area = Area::uninitialized(); // will be fixed at drawing time
-        let preview = Preview::new(&path, prefered_mode, con);
+        let preview = Preview::new(&path, mode_for(&path, prefered_mode, &con.preview_modes), &tree_options, con);
         PreviewState {
             preview_area,
             dirty: true,
@@ -57,6 +59,7 @@ impl PreviewState {
             prefered_mode,
             tree_options,
             mode: initial_mode(con),
+            positions: PreviewPositions::default(),
         }
     }
     fn mut_preview(&mut self) -> &mut Preview {
@@ -70,7 +73,7 @@ impl PreviewState {
         if self.preview.get_mode() == Some(mode) {
             return Ok(CmdResult::Keep);
         }
-        Ok(match Preview::with_mode(&self.path, mode, con) {
+        Ok(match Preview::with_mode(&self.path, mode, &self.tree_options, con) {
             Ok(preview) => {
                 self.preview = preview;
                 self.prefered_mode = Some(mode);
@@ -84,10 +87,20 @@ impl PreviewState {
         })
     }
 
+    /// remember the scroll and selection in the current file
+    fn remember_position(&mut self) {
+        if let Some(mut position) = self.preview.position() {
+            if let Some(fp) = &self.filtered_preview {
+                position.selected_line = fp.get_selected_line_number().or(position.selected_line);
+            }
+            self.positions.remember(&self.path, position);
+        }
+    }
+
     fn no_opt_selection(&self) -> Selection<'_> {
         Selection {
             path: &self.path,
-            stype: SelectionType::File,
+            stype: SelectionType::from(&self.path),
             is_exe: false, // not always true. It means :open_leave won't execute it
             line: self.preview.get_selected_line_number().unwrap_or(0),
         }
@@ -95,6 +108,21 @@ impl PreviewState {
 
 }
 
+/// the mode in which a file should be previewed: the one chosen by the
+/// user, if any, or else the one configured for its extension
+fn mode_for(
+    path: &Path,
+    prefered_mode: Option<PreviewMode>,
+    preview_modes: &AHashMap<String, PreviewMode>,
+) -> Option<PreviewMode> {
+    prefered_mode.or_else(|| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| preview_modes.get(&ext.to_lowercase()))
+            .copied()
+    })
+}
+
 impl PanelState for PreviewState {
 
     fn get_type(&self) -> PanelStateType {
@@ -112,6 +140,8 @@ impl PanelState for PreviewState {
     fn get_pending_task(&self) -> Option<&'static str> {
         if self.pending_pattern.is_some() {
             Some("searching")
+        } else if self.preview.has_pending_task() {
+            Some("building the tree")
         } else {
             None
         }
@@ -164,6 +194,8 @@ impl PanelState for PreviewState {
                     filtered_preview.try_select_line_number(number);
                 }
             }
+        } else if self.preview.has_pending_task() {
+            self.preview.do_pending_task(dam, con);
         }
     }
 
@@ -172,10 +204,17 @@ impl PanelState for PreviewState {
     }
 
     fn set_selected_path(&mut self, path: PathBuf, con: &AppContext) {
+        self.remember_position();
         if let Some(fp) = &self.filtered_preview {
             self.pending_pattern = fp.pattern();
         };
-        self.preview = Preview::new(&path, self.prefered_mode, con);
+        let mode = mode_for(&path, self.prefered_mode, &con.preview_modes);
+        self.preview = Preview::new(&path, mode, &self.tree_options, con);
+        if let Some(position) = self.positions.get(&path) {
+            self.preview.restore_position(position);
+            // so that the filtering starts from the restored selection
+            self.filtered_preview = None;
+        }
         self.path = path;
     }
 
@@ -345,6 +384,16 @@ impl PanelState for PreviewState {
                 self.mut_preview().move_selection(-count, false);
                 Ok(CmdResult::Keep)
             }
+            Internal::scroll_down => {
+                let count = get_arg(input_invocation, internal_exec, 1);
+                self.mut_preview().scroll_view(count);
+                Ok(CmdResult::Keep)
+            }
+            Internal::scroll_up => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.mut_preview().scroll_view(-count);
+                Ok(CmdResult::Keep)
+            }
             Internal::page_down => {
                 self.mut_preview().try_scroll(ScrollCommand::Pages(1));
                 Ok(CmdResult::Keep)
@@ -374,9 +423,40 @@ impl PanelState for PreviewState {
                 self.mut_preview().select_last();
                 Ok(CmdResult::Keep)
             }
+            Internal::preview_top => {
+                self.mut_preview().select_first();
+                Ok(CmdResult::Keep)
+            }
+            Internal::preview_bottom => {
+                self.mut_preview().select_last();
+                Ok(CmdResult::Keep)
+            }
+            Internal::preview_goto => {
+                let number = get_str_arg(input_invocation, internal_exec)
+                    .and_then(|arg| arg.parse::<LineNumber>().ok());
+                Ok(match number {
+                    Some(number) if self.mut_preview().try_goto_line_number(number) => {
+                        CmdResult::Keep
+                    }
+                    Some(number) => CmdResult::error(format!("no line {} in this preview", number)),
+                    None => CmdResult::error("a line number is needed, eg `:preview_goto 120`"),
+                })
+            }
             Internal::preview_image => self.set_mode(PreviewMode::Image, con),
             Internal::preview_text => self.set_mode(PreviewMode::Text, con),
             Internal::preview_binary => self.set_mode(PreviewMode::Hex, con),
+            Internal::preview_mode => match get_str_arg(input_invocation, internal_exec) {
+                Some(arg) => match arg.parse::<PreviewMode>() {
+                    Ok(mode) => self.set_mode(mode, con),
+                    Err(e) => Ok(CmdResult::error(e.to_string())),
+                },
+                None => {
+                    self.prefered_mode = None;
+                    let mode = mode_for(&self.path, None, &con.preview_modes);
+                    self.preview = Preview::new(&self.path, mode, &self.tree_options, con);
+                    Ok(CmdResult::Keep)
+                }
+            },
             _ => self.on_internal_generic(
                 w,
                 internal_exec,
@@ -401,3 +481,29 @@ impl PanelState for PreviewState {
     }
 
 }
+
+#[cfg(test)]
+mod preview_state_test {
+
+    use super::*;
+
+    #[test]
+    fn check_mode_for() {
+        let mut preview_modes = AHashMap::default();
+        preview_modes.insert("bin".to_string(), PreviewMode::Hex);
+        preview_modes.insert("svg".to_string(), PreviewMode::Text);
+        let mode = |path: &str, prefered_mode| {
+            mode_for(Path::new(path), prefered_mode, &preview_modes)
+        };
+        assert_eq!(mode("a/b.bin", None), Some(PreviewMode::Hex));
+        assert_eq!(mode("logo.svg", None), Some(PreviewMode::Text));
+        // the case of the extension doesn't matter
+        assert_eq!(mode("DUMP.BIN", None), Some(PreviewMode::Hex));
+        assert_eq!(mode("logo.Svg", None), Some(PreviewMode::Text));
+        assert_eq!(mode("a.txt", None), None);
+        assert_eq!(mode("bin", None), None);
+        // the mode chosen by the user has precedence
+        assert_eq!(mode("a.bin", Some(PreviewMode::Text)), Some(PreviewMode::Text));
+        assert_eq!(mode("a.txt", Some(PreviewMode::Image)), Some(PreviewMode::Image));
+    }
+}
@@ -3,20 +3,123 @@
 use {
     crate::{
         app::{AppContext, CmdResult},
-        display::{DisplayableTree, Screen},
-        errors::ProgramError,
+        display::{Col, DisplayableTree, Screen},
+        errors::{ConfError, ProgramError},
+        git,
         launchable::Launchable,
         skin::{ExtColorMap, PanelSkin, StyleMap},
+        task_sync::Dam,
         tree::Tree,
     },
+    clap::ArgMatches,
+    git2::Repository,
     pathdiff,
     std::{
         fs::OpenOptions,
         io::{self, Write},
         path::Path,
+        str::FromStr,
     },
 };
 
+/// the columns which can be chosen for an export
+static EXPORTABLE_COLS: &[Col] = &[Col::Git, Col::Size, Col::Date, Col::Permission, Col::Count];
+
+/// how a tree is written when printed, independently of the
+/// options of the interactive tree
+#[derive(Debug, Clone, Default)]
+pub struct ExportConfig {
+    /// the max depth of the written lines, the root having depth 0
+    pub max_depth: Option<u16>,
+
+    /// the optional columns to write, replacing the ones of the tree
+    pub cols: Option<Vec<Col>>,
+
+    /// whether only ASCII chars may be used for the branches and marks
+    pub ascii: bool,
+}
+
+impl ExportConfig {
+    pub fn from_launch_args(cli_args: &ArgMatches<'_>) -> Result<Self, ConfError> {
+        let max_depth = cli_args.value_of("export-depth")
+            .map(|s| s.parse().map_err(|_| ConfError::InvalidLaunchArg {
+                arg: "export-depth".to_string(),
+                value: s.to_string(),
+                expected: "a depth, eg 2".to_string(),
+            }))
+            .transpose()?;
+        let cols = cli_args.value_of("export-cols")
+            .map(parse_export_cols)
+            .transpose()?;
+        let ascii = cli_args.is_present("no-style");
+        Ok(Self { max_depth, cols, ascii })
+    }
+
+    /// build the tree to write from the displayed one, computing
+    /// the sizes, dates or git statuses it lacks
+    pub fn exported_tree(&self, tree: &Tree, con: &AppContext) -> Tree {
+        let mut tree = tree.clone();
+        if let Some(max_depth) = self.max_depth {
+            let lines: Vec<_> = tree.lines.iter()
+                .filter(|line| line.depth <= max_depth)
+                .cloned()
+                .collect();
+            tree.lines = lines.into_boxed_slice();
+            tree.selection = 0;
+            tree.scroll = 0;
+        }
+        if let Some(cols) = &self.cols {
+            let options = &mut tree.options;
+            options.show_sizes = cols.contains(&Col::Size);
+            options.show_dates = cols.contains(&Col::Date);
+            options.show_permissions = cols.contains(&Col::Permission);
+            options.show_counts = cols.contains(&Col::Count);
+            options.show_git_file_info = cols.contains(&Col::Git);
+            if options.needs_sum() {
+                tree.fetch_regular_file_sums();
+                while tree.has_dir_missing_sum() {
+                    tree.fetch_some_missing_dir_sum(&Dam::unlimited(), con);
+                }
+            }
+            if tree.options.show_git_file_info {
+                if !tree.has_line_git_statuses {
+                    if let Ok(repo) = Repository::discover(tree.root()) {
+                        let computer = git::LineStatusComputer::from(repo);
+                        for line in tree.lines.iter_mut() {
+                            line.git_status = computer.line_status(&line.path);
+                        }
+                        tree.has_line_git_statuses = true;
+                    }
+                }
+            } else {
+                tree.has_line_git_statuses = false;
+            }
+        }
+        tree
+    }
+}
+
+/// parse the columns given either as a string with one char per
+/// column (eg "sdg") or as comma separated names (eg "size,date,git")
+fn parse_export_cols(s: &str) -> Result<Vec<Col>, ConfError> {
+    let names: Vec<String> = if s.contains(',') {
+        s.split(',').map(|name| name.trim().to_string()).collect()
+    } else {
+        s.chars().map(String::from).collect()
+    };
+    let mut cols = Vec::new();
+    for name in names.iter().filter(|name| !name.is_empty()) {
+        let col = Col::from_str(name)?;
+        if !EXPORTABLE_COLS.contains(&col) {
+            return Err(ConfError::InvalidCols {
+                details: format!("column can't be chosen for an export : {}", name),
+            });
+        }
+        cols.push(col);
+    }
+    Ok(cols)
+}
+
 pub fn print_path(path: &Path, con: &AppContext) -> io::Result<CmdResult> {
     let path = path.to_string_lossy().to_string();
     Ok(
@@ -57,6 +160,7 @@ fn print_tree_to_file(
     screen: Screen,
     file_path: &str,
     ext_colors: &ExtColorMap,
+    ascii: bool,
 ) -> Result<CmdResult, ProgramError> {
     let no_style_skin = StyleMap::no_term();
     let dp = DisplayableTree::out_of_app(
@@ -65,6 +169,7 @@ fn print_tree_to_file(
         ext_colors,
         screen.width,
         (tree.lines.len() as u16).min(screen.height),
+        ascii,
     );
     let mut f = OpenOptions::new()
         .create(true)
@@ -78,11 +183,13 @@ pub fn print_tree(
     tree: &Tree,
     screen: Screen,
     panel_skin: &PanelSkin,
+    export: &ExportConfig,
     con: &AppContext,
 ) -> Result<CmdResult, ProgramError> {
+    let tree = &export.exported_tree(tree, con);
     if let Some(ref output_path) = con.launch_args.file_export_path {
         // an output path was provided, we write to it
-        print_tree_to_file(tree, screen, output_path, &con.ext_colors)
+        print_tree_to_file(tree, screen, output_path, &con.ext_colors, export.ascii)
     } else {
         // no output path provided. We write on stdout, but we must
         // do it after app closing to have the normal terminal
@@ -96,6 +203,21 @@ pub fn print_tree(
             screen,
             styles,
             con.ext_colors.clone(),
+            export.ascii,
         )))
     }
 }
+
+#[cfg(test)]
+mod print_test {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_export_cols() {
+        assert_eq!(parse_export_cols("sg").unwrap(), vec![Col::Size, Col::Git]);
+        assert_eq!(parse_export_cols("date, count").unwrap(), vec![Col::Date, Col::Count]);
+        assert!(parse_export_cols("sn").is_err()); // the name isn't optional
+        assert!(parse_export_cols("x").is_err());
+    }
+}
@@ -0,0 +1,9 @@
+//! The entry point for programs embedding broot's tree building and
+//! searches, without its terminal user interface.
+//!
+//! The `broot-core` crate of the workspace wraps this module in an API
+//! with its own types, and is the one other programs should depend on.
+
+mod searcher;
+
+pub use searcher::Searcher;
@@ -0,0 +1,214 @@
+use {
+    crate::{
+        app::AppContext,
+        cli::AppLaunchArgs,
+        command::CommandParts,
+        conf::Conf,
+        errors::{ConfError, PatternError, ProgramError, TreeBuildError},
+        file_sum::FileSum,
+        pattern::InputPattern,
+        print::ExportConfig,
+        task_sync::{CancellationToken, Dam},
+        tree::{Tree, TreeLine, TreeOptions},
+        tree_build::TreeBuilder,
+        verb::VerbStore,
+    },
+    clap::ArgMatches,
+    std::{
+        cmp::Reverse,
+        path::{Path, PathBuf},
+    },
+};
+
+/// builds trees and runs searches the way broot does, for
+/// another program.
+///
+/// A searcher holds the configuration which applies to all
+/// searches (search modes, special paths, score adjustments, etc.)
+/// and can be shared between threads.
+pub struct Searcher {
+    con: AppContext,
+}
+
+impl Searcher {
+
+    /// make a searcher with the default configuration, the
+    /// configuration files of the user being ignored
+    pub fn new() -> Result<Self, ConfError> {
+        Self::from_conf(&Conf::default())
+    }
+
+    /// make a searcher with the given configuration, for example
+    /// the one of the user, read with `Conf::from_default_location`
+    pub fn from_conf(conf: &Conf) -> Result<Self, ConfError> {
+        let launch_args = AppLaunchArgs {
+            root: PathBuf::from("."),
+            file_export_path: None,
+            cmd_export_path: None,
+            tree_options: TreeOptions::default(),
+            flags: ArgMatches::default(),
+            commands: None,
+            height: None,
+            no_style: true,
+            export_config: ExportConfig::default(),
+            pick: None,
+            dry_run: false,
+            must_show_selection_mark: false,
+
+            #[cfg(feature = "client-server")]
+            listen: None,
+        };
+        let con = AppContext::from(launch_args, VerbStore::default(), conf)?;
+        Ok(Self { con })
+    }
+
+    /// the context given to the tree builders, with the parsed
+    /// configuration
+    pub fn context(&self) -> &AppContext {
+        &self.con
+    }
+
+    /// parse a pattern written as in broot's input, for example
+    /// `mn` (fuzzy on names), `/\.rs$/` (regular expression) or
+    /// `c/TODO` (file contents).
+    ///
+    /// As in the input, the pattern ends at the first space or
+    /// colon which isn't escaped with a backslash.
+    pub fn pattern(&self, raw: &str) -> Result<InputPattern, PatternError> {
+        let parts = CommandParts::from(raw.to_string());
+        InputPattern::new(parts.raw_pattern, &parts.pattern, &self.con)
+    }
+
+    /// build the tree of the root as broot would display it
+    /// in a panel of the given height, with the pattern of the
+    /// options, if any.
+    ///
+    /// The search stops when the tree is filled with good enough
+    /// matches and the time budget of the options is exhausted.
+    ///
+    /// Return `Ok(None)` if the cancellation token was cancelled
+    pub fn tree(
+        &self,
+        root: &Path,
+        options: TreeOptions,
+        height: usize,
+        cancellation: CancellationToken,
+    ) -> Result<Option<Tree>, TreeBuildError> {
+        let builder = TreeBuilder::from(root.to_path_buf(), options, height, &self.con)?;
+        Ok(builder.build_with_cancellation(false, cancellation))
+    }
+
+    /// search the whole hierarchy under the root and return the
+    /// lines of the best matches of the pattern, best first.
+    ///
+    /// As they're kept in a tree, the lines of the parent directories
+    /// of the matches count in the limit, so that there may be fewer
+    /// than `limit` matches.
+    ///
+    /// Return `Ok(None)` if the cancellation token was cancelled
+    pub fn search(
+        &self,
+        root: &Path,
+        pattern: &str,
+        mut options: TreeOptions,
+        limit: usize,
+        cancellation: CancellationToken,
+    ) -> Result<Option<Vec<TreeLine>>, ProgramError> {
+        options.pattern = self.pattern(pattern)?;
+        let builder = TreeBuilder::from(root.to_path_buf(), options, limit, &self.con)?;
+        Ok(builder.build_with_cancellation(true, cancellation).map(|tree| {
+            let mut matches: Vec<TreeLine> = tree.lines
+                .into_vec()
+                .into_iter()
+                .skip(1)
+                .filter(|line| line.direct_match)
+                .collect();
+            matches.sort_by_key(|line| Reverse(line.score));
+            matches
+        }))
+    }
+
+    /// compute the sums (size, count, date) of all the lines of the
+    /// tree, which may be long for big directories
+    pub fn fetch_sums(&self, tree: &mut Tree) {
+        let dam = Dam::unlimited();
+        tree.fetch_regular_file_sums();
+        while tree.has_dir_missing_sum() {
+            tree.fetch_some_missing_dir_sum(&dam, &self.con);
+        }
+    }
+
+    /// compute the sum (size, count, date) of a directory
+    pub fn dir_sum(&self, path: &Path) -> Option<FileSum> {
+        FileSum::from_dir(path, &Dam::unlimited(), &self.con)
+    }
+}
+
+#[cfg(test)]
+mod searcher_test {
+
+    use {
+        super::*,
+        std::fs,
+        tempfile::TempDir,
+    };
+
+    fn make_hierarchy() -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for subpath in &["src/main.rs", "src/mainframe.txt", "doc/manual.md", "doc/other.md"] {
+            let path = dir.path().join(subpath);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "some content").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn check_search() {
+        let dir = make_hierarchy();
+        let searcher = Searcher::new().unwrap();
+        let matches = searcher
+            .search(dir.path(), "main", TreeOptions::default(), 100, CancellationToken::never())
+            .unwrap()
+            .unwrap();
+        let subpaths: Vec<&str> = matches.iter().map(|m| m.subpath.as_str()).collect();
+        assert_eq!(subpaths.len(), 2);
+        assert!(subpaths.contains(&"src/main.rs"));
+        assert!(subpaths.contains(&"src/mainframe.txt"));
+        assert!(matches[0].score >= matches[1].score);
+        let matches = searcher
+            .search(dir.path(), "/\\.md$/", TreeOptions::default(), 100, CancellationToken::never())
+            .unwrap()
+            .unwrap();
+        assert_eq!(matches.len(), 2);
+        let matches = searcher
+            .search(dir.path(), "c/content", TreeOptions::default(), 100, CancellationToken::never())
+            .unwrap()
+            .unwrap();
+        assert_eq!(matches.len(), 4);
+    }
+
+    #[test]
+    fn check_cancelled_search() {
+        let dir = make_hierarchy();
+        let searcher = Searcher::new().unwrap();
+        let cancellation = CancellationToken::never();
+        cancellation.cancel();
+        let matches = searcher
+            .search(dir.path(), "main", TreeOptions::default(), 100, cancellation)
+            .unwrap();
+        assert!(matches.is_none());
+    }
+
+    #[test]
+    fn check_searcher_is_sync() {
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<Searcher>();
+    }
+
+    #[test]
+    fn check_invalid_pattern() {
+        let searcher = Searcher::new().unwrap();
+        assert!(searcher.pattern("/[a/").is_err());
+    }
+}
@@ -16,28 +16,112 @@ pub struct AppSkin {
 
     /// the skin used in unfocused panels
     pub unfocused: PanelSkin,
+
+    /// the focused and unfocused skins of the preview panel,
+    /// when a preview_skin is configured
+    preview: Option<Box<(PanelSkin, PanelSkin)>>,
+
+    /// the background of the terminal, when it's known
+    pub background: Option<Background>,
+
+    /// the skin for the other background
+    alternate: Option<Box<AppSkin>>,
+}
+
+/// build the focused and unfocused panel skins from skin entries.
+///
+/// When dimming is configured, the unfocused skin is a dimmed variant
+/// of the focused one.
+fn panel_skins(
+    skin: &AHashMap<String, SkinEntry>,
+    conf: &Conf,
+    background: Option<Background>,
+) -> (PanelSkin, PanelSkin) {
+    let StyleMaps { focused, unfocused } = StyleMaps::create(skin);
+    let unfocused = match conf.unfocused_dimming {
+        Some(ratio) if ratio > 0.0 => {
+            focused.dimmed(ratio.min(1.0), background.unwrap_or(Background::Dark))
+        }
+        _ => unfocused,
+    };
+    (PanelSkin::new(focused), PanelSkin::new(unfocused))
 }
 
 impl AppSkin {
-    pub fn new(conf: &Conf, no_style: bool) -> Self {
+    /// build the skin. When the configuration has a light or dark skin, the
+    /// given background (or dark when it's unknown) selects the one to use
+    pub fn new(conf: &Conf, no_style: bool, background: Option<Background>) -> Self {
         if no_style {
             Self {
                 focused: PanelSkin::new(StyleMap::no_term()),
                 unfocused: PanelSkin::new(StyleMap::no_term()),
+                preview: None,
+                background: None,
+                alternate: None,
             }
+        } else if conf.light_skin.is_some() || conf.dark_skin.is_some() {
+            let background = background.unwrap_or(Background::Dark);
+            let mut skin = Self::for_background(conf, Some(background));
+            skin.alternate = Some(Box::new(
+                Self::for_background(conf, Some(background.other()))
+            ));
+            skin
         } else {
-            let def_skin;
-            let skin = if let Some(skin) = &conf.skin {
-                skin
-            } else {
-                def_skin = AHashMap::default();
-                &def_skin
-            };
-            let StyleMaps { focused, unfocused } = StyleMaps::create(skin);
-            Self {
-                focused: PanelSkin::new(focused),
-                unfocused: PanelSkin::new(unfocused),
+            Self::for_background(conf, background)
+        }
+    }
+
+    /// build the skin from the `skin` conf completed by the entries
+    /// of the skin specific to the background, if any
+    fn for_background(conf: &Conf, background: Option<Background>) -> Self {
+        let mut skin = conf.skin.clone().unwrap_or_default();
+        let specific_skin = match background {
+            Some(Background::Light) => conf.light_skin.as_ref(),
+            Some(Background::Dark) => conf.dark_skin.as_ref(),
+            None => None,
+        };
+        if let Some(specific_skin) = specific_skin {
+            for (k, v) in specific_skin {
+                skin.insert(k.clone(), v.clone());
+            }
+        }
+        let preview = conf.preview_skin.as_ref().map(|preview_skin| {
+            let mut skin = skin.clone();
+            for (k, v) in preview_skin {
+                skin.insert(k.clone(), v.clone());
+            }
+            Box::new(panel_skins(&skin, conf, background))
+        });
+        let (focused, unfocused) = panel_skins(&skin, conf, background);
+        Self {
+            focused,
+            unfocused,
+            preview,
+            background,
+            alternate: None,
+        }
+    }
+
+    /// return the skin to use for a panel
+    pub fn panel_skin(&self, active: bool, is_preview: bool) -> &PanelSkin {
+        match (&self.preview, is_preview, active) {
+            (Some(preview), true, true) => &preview.0,
+            (Some(preview), true, false) => &preview.1,
+            (_, _, true) => &self.focused,
+            (_, _, false) => &self.unfocused,
+        }
+    }
+
+    /// switch to the skin of the other background. Return false
+    /// when there's no such skin
+    pub fn toggle(&mut self) -> bool {
+        match self.alternate.take() {
+            Some(mut alternate) => {
+                std::mem::swap(self, &mut alternate);
+                self.alternate = Some(alternate);
+                true
             }
+            None => false,
         }
     }
 
@@ -0,0 +1,125 @@
+//! detection of the terminal's background, so that the
+//! light or dark skin can be chosen
+
+use std::env;
+
+/// the luminosity of the terminal's background
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Background {
+    Light,
+    Dark,
+}
+
+impl Background {
+    pub fn other(self) -> Self {
+        match self {
+            Self::Light => Self::Dark,
+            Self::Dark => Self::Light,
+        }
+    }
+}
+
+/// try to determine the background of the terminal, from, in order
+/// - the `BROOT_BACKGROUND` env variable ("light" or "dark")
+/// - the answer of the terminal to an OSC 11 query
+/// - the `COLORFGBG` env variable, set by some terminals
+///
+/// This must be called before the terminal events are listened to.
+pub fn detect() -> Option<Background> {
+    if let Ok(hint) = env::var("BROOT_BACKGROUND") {
+        match hint.to_lowercase().as_str() {
+            "light" => return Some(Background::Light),
+            "dark" => return Some(Background::Dark),
+            _ => warn!("unexpected BROOT_BACKGROUND: {:?}", hint),
+        }
+    }
+    #[cfg(unix)]
+    if let Some(background) = time!(query_terminal()) {
+        return Some(background);
+    }
+    env::var("COLORFGBG").ok().and_then(|s| from_colorfgbg(&s))
+}
+
+/// read a `COLORFGBG` value like "15;0" (or "15;default;0"), whose
+/// last part is the ANSI code of the background color
+fn from_colorfgbg(value: &str) -> Option<Background> {
+    match value.rsplit(';').next()?.parse::<u8>().ok()? {
+        0..=6 | 8 => Some(Background::Dark),
+        7 | 9..=15 => Some(Background::Light),
+        _ => None,
+    }
+}
+
+/// parse the answer to an OSC 11 query, which looks like
+/// `ESC]11;rgb:RRRR/GGGG/BBBB` followed by BEL or ST
+fn from_osc_answer(answer: &str) -> Option<Background> {
+    let rgb = answer.split("rgb:").nth(1)?;
+    let rgb = rgb.trim_end_matches(&['\x07', '\\', '\x1b'][..]);
+    let mut luminance = 0.0;
+    let mut nb_components = 0;
+    for (component, weight) in rgb.split('/').zip(&[0.2126, 0.7152, 0.0722]) {
+        let max = 16f64.powi(component.len() as i32) - 1.0;
+        let value = u16::from_str_radix(component, 16).ok()? as f64;
+        luminance += weight * value / max;
+        nb_components += 1;
+    }
+    if nb_components != 3 {
+        return None;
+    }
+    Some(if luminance > 0.5 { Background::Light } else { Background::Dark })
+}
+
+/// ask the terminal its background color, with an OSC 11 sequence.
+/// Terminals not supporting it don't answer, so we don't wait long.
+#[cfg(unix)]
+fn query_terminal() -> Option<Background> {
+    use {
+        crossterm::terminal,
+        libc::{poll, pollfd, read, POLLIN, STDIN_FILENO, STDOUT_FILENO},
+        std::io::{self, Write},
+    };
+    const TIMEOUT_MS: i32 = 200;
+    if unsafe { libc::isatty(STDIN_FILENO) == 0 || libc::isatty(STDOUT_FILENO) == 0 } {
+        return None;
+    }
+    terminal::enable_raw_mode().ok()?;
+    let mut answer = Vec::new();
+    let mut stdout = io::stdout();
+    if write!(stdout, "\x1b]11;?\x07").and_then(|_| stdout.flush()).is_ok() {
+        let mut fds = pollfd { fd: STDIN_FILENO, events: POLLIN, revents: 0 };
+        let mut buf = [0u8; 64];
+        // we read directly from the file descriptor, not to keep
+        // anything in a buffer which would be lost for the app
+        while unsafe { poll(&mut fds, 1, TIMEOUT_MS) } > 0 {
+            let n = unsafe { read(STDIN_FILENO, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+            if n <= 0 {
+                break;
+            }
+            answer.extend_from_slice(&buf[..n as usize]);
+            if answer.ends_with(b"\x07") || answer.ends_with(b"\x1b\\") {
+                break;
+            }
+        }
+    }
+    let _ = terminal::disable_raw_mode();
+    debug!("OSC 11 answer: {:?}", String::from_utf8_lossy(&answer));
+    from_osc_answer(&String::from_utf8_lossy(&answer))
+}
+
+#[cfg(test)]
+mod background_test {
+
+    use super::*;
+
+    #[test]
+    fn test_background_parsing() {
+        assert_eq!(from_osc_answer("\x1b]11;rgb:0000/0000/0000\x07"), Some(Background::Dark));
+        assert_eq!(from_osc_answer("\x1b]11;rgb:ffff/ffff/ffff\x1b\\"), Some(Background::Light));
+        assert_eq!(from_osc_answer("\x1b]11;rgb:fd/f6/e3\x07"), Some(Background::Light));
+        assert_eq!(from_osc_answer("\x1b]11;rgb:2828/2c2c/3434\x07"), Some(Background::Dark));
+        assert_eq!(from_osc_answer(""), None);
+        assert_eq!(from_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(from_colorfgbg("0;default;15"), Some(Background::Light));
+        assert_eq!(from_colorfgbg("default"), None);
+    }
+}
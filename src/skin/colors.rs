@@ -71,3 +71,42 @@ pub fn parse(s: &str) -> Result<Option<Color>, InvalidSkinError> {
     }
 }
 
+
+/// return the RGB components of a color, if it's not the terminal's default
+pub fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let ansi = match color {
+        Reset => { return None; }
+        Rgb { r, g, b } => { return Some((r, g, b)); }
+        AnsiValue(v) => v,
+        Black => 0,
+        DarkRed => 1,
+        DarkGreen => 2,
+        DarkYellow => 3,
+        DarkBlue => 4,
+        DarkMagenta => 5,
+        DarkCyan => 6,
+        Grey => 7,
+        DarkGrey => 8,
+        Red => 9,
+        Green => 10,
+        Yellow => 11,
+        Blue => 12,
+        Magenta => 13,
+        Cyan => 14,
+        White => 15,
+    };
+    Some(ansi_colours::rgb_from_ansi256(ansi))
+}
+
+/// move the color toward another one by the given ratio (between 0 and 1).
+///
+/// The result is an ANSI color so that it's rendered on all terminals.
+pub fn blend(color: Color, toward: Color, ratio: f32) -> Color {
+    match (to_rgb(color), to_rgb(toward)) {
+        (Some((r1, g1, b1)), Some((r2, g2, b2))) => {
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio).round() as u8;
+            AnsiValue(ansi_colours::ansi256_from_rgb((mix(r1, r2), mix(g1, g2), mix(b1, b2))))
+        }
+        _ => color,
+    }
+}
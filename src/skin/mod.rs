@@ -1,4 +1,5 @@
 mod app_skin;
+mod background;
 mod cli_mad_skin;
 pub mod colors;
 mod ext_colors;
@@ -11,6 +12,7 @@ mod status_mad_skin;
 
 pub use {
     app_skin::AppSkin,
+    background::{detect as detect_background, Background},
     cli_mad_skin::*,
     ext_colors::ExtColorMap,
     help_mad_skin::*,
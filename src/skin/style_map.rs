@@ -57,6 +57,18 @@ macro_rules! StyleMap {
                     $($name: CompoundStyle::default(),)*
                 }
             }
+            /// build a dimmed variant of the map, whose foreground colors are
+            /// moved toward the background ones by the given ratio.
+            ///
+            /// The terminal's colors are used when the background or foreground
+            /// of a style is the default one.
+            pub fn dimmed(&self, ratio: f32, background: Background) -> Self {
+                let mut map = self.clone();
+                $(
+                    dim_style(&mut map.$name, ratio, background);
+                )*
+                map
+            }
             /// ensures the "default" skin entry is used as base for all other
             /// entries (this processus is part of the skin initialization)
             fn diffuse_default(&mut self) {
@@ -123,6 +135,17 @@ macro_rules! StyleMap {
     }
 }
 
+/// move the foreground of the style toward its background
+fn dim_style(style: &mut CompoundStyle, ratio: f32, background: Background) {
+    let (terminal_fg, terminal_bg) = match background {
+        Background::Light => (AnsiValue(16), AnsiValue(231)),
+        Background::Dark => (AnsiValue(252), AnsiValue(16)),
+    };
+    let fg = style.get_fg().unwrap_or(terminal_fg);
+    let bg = style.get_bg().unwrap_or(terminal_bg);
+    style.set_fg(colors::blend(fg, bg, ratio));
+}
+
 impl StyleMap {
     pub fn queue_reset<W: Write>(&self, f: &mut W) -> Result<(), ProgramError> {
         if self.styled {
@@ -154,6 +177,7 @@ StyleMap! {
     count: ansi(138), gray(4), []
     dates: ansi(66), None, []
     sparse: ansi(214), None, []
+    tag: ansi(176), None, []
     content_extract: ansi(29), None, []
     content_match: ansi(34), None, []
     git_branch: ansi(178), None, []
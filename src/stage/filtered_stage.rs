@@ -41,6 +41,7 @@ impl FilteredStage {
                         subpath: &subpath,
                         name: &name,
                         regular_file,
+                        cancellation: None,
                     };
                     if let Some(score) = self.pattern.pattern.score_of(candidate) {
                         let is_best = match best_score {
@@ -61,6 +61,7 @@ pub struct Line {
 pub struct SyntacticView {
     pub path: PathBuf,
     pub pattern: InputPattern,
+    pub with_style: bool, // false when syntax coloring isn't wanted
     lines: Vec<Line>,
     scroll: usize,
     page_height: usize,
@@ -70,18 +71,21 @@ pub struct SyntacticView {
 
 impl SyntacticView {
 
-    /// return a prepared text view with syntax coloring if possible.
+    /// return a prepared text view with syntax coloring if possible
+    /// and wanted.
     /// May return Ok(None) only when a pattern is given and there
     /// was an event before the end of filtering.
     pub fn new(
         path: &Path,
         pattern: InputPattern,
+        with_style: bool,
         dam: &mut Dam,
         con: &AppContext,
     ) -> Result<Option<Self>, ProgramError> {
         let mut sv = Self {
             path: path.to_path_buf(),
             pattern,
+            with_style,
             lines: Vec::new(),
             scroll: 0,
             page_height: 0,
@@ -107,7 +111,7 @@ impl SyntacticView {
         if md.len() == 0 {
             return Err(ProgramError::ZeroLenFile);
         }
-        let with_style = md.len() < MAX_SIZE_FOR_STYLING;
+        let with_style = self.with_style && md.len() < MAX_SIZE_FOR_STYLING;
         let mut reader = BufReader::new(f);
         self.lines.clear();
         let mut line = String::new();
@@ -232,6 +236,13 @@ impl SyntacticView {
         }
     }
 
+    pub fn get_scroll(&self) -> usize {
+        self.scroll
+    }
+    pub fn set_scroll(&mut self, scroll: usize) {
+        self.scroll = scroll.min(self.lines.len().saturating_sub(1));
+    }
+
     pub fn try_select_line_number(&mut self, number: LineNumber) -> bool {
         // this could obviously be optimized
         for (idx, line) in self.lines.iter().enumerate() {
@@ -273,6 +284,22 @@ impl SyntacticView {
         self.scroll != old_scroll
     }
 
+    /// scroll the view by dy lines, the selection being moved
+    /// only when it would leave the view
+    pub fn scroll_view(&mut self, dy: i32) -> bool {
+        let old_scroll = self.scroll;
+        self.scroll = ScrollCommand::Lines(dy).apply(self.scroll, self.lines.len(), self.page_height);
+        if let Some(idx) = self.selection_idx {
+            if idx < self.scroll {
+                self.selection_idx = Some(self.scroll);
+            } else if self.page_height > 0 && idx >= self.scroll + self.page_height {
+                let last_visible = self.scroll + self.page_height - 1;
+                self.selection_idx = Some(last_visible.min(self.lines.len() - 1));
+            }
+        }
+        self.scroll != old_scroll
+    }
+
     pub fn display(
         &mut self,
         w: &mut W,
@@ -0,0 +1,66 @@
+//! Tags are labels the user attaches to paths with `:tag` and
+//! which persist across sessions in a file of the configuration
+//! directory.
+
+mod tag_db;
+
+pub use tag_db::TagDb;
+
+use {
+    crate::conf,
+    std::{
+        io,
+        path::{Path, PathBuf},
+        sync::RwLock,
+    },
+};
+
+lazy_static! {
+    static ref TAG_DB: RwLock<TagDb> = RwLock::new(TagDb::load(&db_path()));
+}
+
+/// return the path to the file where tags are stored
+pub fn db_path() -> PathBuf {
+    conf::dir().join("tags.txt")
+}
+
+/// tell whether the string can be used as a tag (tags are
+/// stored and displayed separated by commas)
+pub fn is_valid_tag(tag: &str) -> bool {
+    !tag.is_empty() && !tag.contains(|c: char| c == ',' || c.is_whitespace())
+}
+
+/// tell whether the path has the given tag
+pub fn has_tag(path: &Path, tag: &str) -> bool {
+    TAG_DB.read().unwrap().has_tag(path, tag)
+}
+
+/// tell whether the path has at least one tag
+pub fn is_tagged(path: &Path) -> bool {
+    TAG_DB.read().unwrap().tags(path).is_some()
+}
+
+/// return the tags of the path, comma separated, or None
+/// if the path has no tag
+pub fn tags_string(path: &Path) -> Option<String> {
+    TAG_DB.read().unwrap().tags(path).map(|tags| tags.join(","))
+}
+
+/// add the tag to all the given paths and save the db
+pub fn add_tag(paths: &[PathBuf], tag: &str) -> io::Result<()> {
+    let mut db = TAG_DB.write().unwrap();
+    for path in paths {
+        db.add(path.clone(), tag);
+    }
+    db.save(&db_path())
+}
+
+/// remove the tag (or all tags if `tag` is None) from all
+/// the given paths and save the db
+pub fn remove_tag(paths: &[PathBuf], tag: Option<&str>) -> io::Result<()> {
+    let mut db = TAG_DB.write().unwrap();
+    for path in paths {
+        db.remove(path, tag);
+    }
+    db.save(&db_path())
+}
@@ -0,0 +1,128 @@
+use {
+    ahash::AHashMap,
+    std::{
+        fs,
+        io,
+        path::{Path, PathBuf},
+    },
+};
+
+/// the tags of all tagged paths.
+///
+/// In the file, there's one line per (tag, path) couple, the tag
+/// being separated from the path by a tab. Tags can't contain a tab
+/// so the path may.
+#[derive(Debug, Clone, Default)]
+pub struct TagDb {
+    tags: AHashMap<PathBuf, Vec<String>>,
+}
+
+impl TagDb {
+    /// read the db from the given file. A missing file means
+    /// there's no tag yet, a badly formatted line is skipped
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    warn!("error while reading tags file {:?} : {:?}", path, e);
+                }
+                Self::default()
+            }
+        }
+    }
+    pub fn parse(content: &str) -> Self {
+        let mut db = Self::default();
+        for line in content.lines() {
+            match line.split_once('\t') {
+                Some((tag, path)) if !tag.is_empty() && !path.is_empty() => {
+                    db.add(PathBuf::from(path), tag);
+                }
+                _ => {
+                    warn!("invalid line in tags file: {:?}", line);
+                }
+            }
+        }
+        db
+    }
+    pub fn to_file_content(&self) -> String {
+        let mut paths: Vec<&PathBuf> = self.tags.keys().collect();
+        paths.sort();
+        let mut content = String::new();
+        for path in paths {
+            for tag in &self.tags[path] {
+                content.push_str(tag);
+                content.push('\t');
+                content.push_str(&path.to_string_lossy());
+                content.push('\n');
+            }
+        }
+        content
+    }
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_file_content())
+    }
+    /// return the tags of the path, None if there's none
+    pub fn tags(&self, path: &Path) -> Option<&[String]> {
+        self.tags.get(path).map(|tags| tags.as_slice())
+    }
+    pub fn has_tag(&self, path: &Path, tag: &str) -> bool {
+        self.tags.get(path).into_iter().flatten().any(|t| t == tag)
+    }
+    /// add the tag to the path, return false if it was already there
+    pub fn add(&mut self, path: PathBuf, tag: &str) -> bool {
+        let tags = self.tags.entry(path).or_default();
+        if tags.iter().any(|t| t == tag) {
+            false
+        } else {
+            tags.push(tag.to_string());
+            true
+        }
+    }
+    /// remove the tag from the path, or all its tags if `tag` is None
+    pub fn remove(&mut self, path: &Path, tag: Option<&str>) {
+        if let Some(tag) = tag {
+            if let Some(tags) = self.tags.get_mut(path) {
+                tags.retain(|t| t != tag);
+                if !tags.is_empty() {
+                    return;
+                }
+            }
+        }
+        self.tags.remove(path);
+    }
+}
+
+#[cfg(test)]
+mod tag_db_test {
+
+    use super::*;
+
+    #[test]
+    fn test_tag_db_round_trip() {
+        let mut db = TagDb::default();
+        db.add(PathBuf::from("/photos/cat.jpg"), "todo");
+        db.add(PathBuf::from("/photos/cat.jpg"), "best");
+        db.add(PathBuf::from("/dl/some\tfile"), "todo");
+        assert!(!db.add(PathBuf::from("/dl/some\tfile"), "todo"));
+        let db = TagDb::parse(&db.to_file_content());
+        assert!(db.has_tag(Path::new("/photos/cat.jpg"), "best"));
+        assert!(db.has_tag(Path::new("/dl/some\tfile"), "todo"));
+        assert!(!db.has_tag(Path::new("/dl/some\tfile"), "best"));
+        assert_eq!(db.tags(Path::new("/photos/cat.jpg")).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_tag_db_remove() {
+        let mut db = TagDb::parse("todo\t/a\nbest\t/a\ntodo\t/b\n");
+        db.remove(Path::new("/a"), Some("todo"));
+        assert_eq!(db.tags(Path::new("/a")), Some(&["best".to_string()][..]));
+        db.remove(Path::new("/a"), Some("best"));
+        assert!(db.tags(Path::new("/a")).is_none());
+        db.remove(Path::new("/b"), None);
+        assert!(db.tags(Path::new("/b")).is_none());
+    }
+}
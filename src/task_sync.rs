@@ -1,7 +1,13 @@
 use {
+    crate::event::Event,
     crossbeam::channel::{self, bounded, Receiver},
-    std::thread,
-    termimad::Event,
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread,
+    },
 };
 
 pub enum Either<A, B> {
@@ -51,16 +57,14 @@ impl Dam {
         Self::from(channel::never())
     }
 
-    /// provide an observer which can be used for periodic
-    /// check a task can be used.
-    /// The observer can safely be moved to another thread
-    /// but Be careful not to use it
-    /// after the event listener started again. In any case
-    /// using try_compute should be prefered for immediate
-    /// return to the ui thread.
-
-    pub fn observer(&self) -> DamObserver {
-        DamObserver::from(self)
+    /// provide a new cancellation token, to be shared by all
+    /// the workers of a computation.
+    /// The token can safely be moved to other threads
+    /// but be careful not to use it after the event listener
+    /// started again. In any case using try_compute should be
+    /// prefered for immediate return to the ui thread.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        CancellationToken::from(self)
     }
 
     /// launch the computation on a new thread and return
@@ -159,20 +163,47 @@ impl Dam {
     }
 }
 
-pub struct DamObserver {
+/// A cheap to clone token telling the workers of a computation
+/// they must stop, checked cooperatively from inside the work
+/// (for example between two chunks of a file being searched).
+///
+/// The token is cancelled either explicitly or when an event
+/// comes into the dam it was made from. Once cancelled, it stays
+/// so, and checking it is just an atomic load.
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
     receiver: Receiver<Event>,
 }
-impl DamObserver {
+impl CancellationToken {
     pub fn from(dam: &Dam) -> Self {
         Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
             receiver: dam.receiver.clone(),
         }
     }
+    /// a token which is never cancelled by events
+    pub fn never() -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            receiver: channel::never(),
+        }
+    }
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
     /// be careful that this can be used as a thread
     /// stop condition only before the event receiver
     /// start being active to avoid a race condition.
-    pub fn has_event(&self) -> bool {
-        !self.receiver.is_empty()
+    pub fn is_cancelled(&self) -> bool {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return true;
+        }
+        if !self.receiver.is_empty() {
+            self.cancel();
+            return true;
+        }
+        false
     }
 }
 
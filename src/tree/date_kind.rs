@@ -0,0 +1,43 @@
+use {
+    crate::errors::ConfError,
+    std::str::FromStr,
+};
+
+/// Which of the timestamps of a file is displayed in the date
+/// column and used when sorting by date
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateKind {
+    Modified, // last modification of the content
+    Changed,  // last change of the content or of the metadata (unix ctime)
+    Created,  // birth time, where the system and file system support it
+}
+
+impl DateKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Modified => "modified",
+            Self::Changed => "changed",
+            Self::Created => "created",
+        }
+    }
+    /// the kind following this one, for cycling through the kinds
+    pub fn next(self) -> Self {
+        match self {
+            Self::Modified => Self::Changed,
+            Self::Changed => Self::Created,
+            Self::Created => Self::Modified,
+        }
+    }
+}
+
+impl FromStr for DateKind {
+    type Err = ConfError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "modified" | "mtime" => Ok(Self::Modified),
+            "changed" | "ctime" => Ok(Self::Changed),
+            "created" | "birth" | "btime" => Ok(Self::Created),
+            _ => Err(ConfError::InvalidDateKind { kind: s.to_string() }),
+        }
+    }
+}
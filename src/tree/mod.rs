@@ -1,4 +1,5 @@
 
+mod date_kind;
 mod sort;
 mod tree;
 mod tree_line;
@@ -6,9 +7,10 @@ mod tree_line_type;
 mod tree_options;
 
 pub use {
+    date_kind::DateKind,
     sort::Sort,
     tree::Tree,
     tree_line::TreeLine,
     tree_line_type::TreeLineType,
-    tree_options::TreeOptions,
+    tree_options::{parse_flags, TreeOptions},
 };
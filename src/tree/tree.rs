@@ -3,7 +3,7 @@ use {
     crate::{
         app::AppContext,
         errors,
-        file_sum::FileSum,
+        file_sum::{self, FileSum},
         git::TreeGitStatus,
         task_sync::ComputationResult,
         task_sync::Dam,
@@ -16,6 +16,9 @@ use {
     },
 };
 
+/// the maximal number of ancestors pinned at the top of a scrolled tree
+const MAX_STICKY_LINES: usize = 5;
+
 /// The tree which may be displayed, with onle line per visible line of the panel.
 ///
 /// In the tree structure, every "node" is just a line, there's
@@ -24,11 +27,17 @@ use {
 pub struct Tree {
     pub lines: Box<[TreeLine]>,
     pub selection: usize, // there's always a selection (starts with root, which is 0)
+    pub drag_start: Option<usize>, // the line where the current mouse drag started
     pub options: TreeOptions,
     pub scroll: i32, // the number of lines at the top hidden because of scrolling
+    pub h_scroll: usize, // the number of chars hidden at the left of the names
     pub nb_gitignored: u32, // number of times a gitignore pattern excluded a file
     pub total_search: bool, // whether the search was made on all children
+    pub budget_exhausted: bool, // whether the search was stopped by its time budget
+    pub result_capped: bool, // whether the search was stopped by the result cap
     pub git_status: ComputationResult<TreeGitStatus>,
+    pub has_line_git_statuses: bool, // whether the git statuses of the lines were computed
+    pub unlisted_matches: Vec<PathBuf>, // matches not displayed, best first
 }
 
 impl Tree {
@@ -54,6 +63,7 @@ impl Tree {
                        // we save the old selection to try restore it
         let selected_path = self.selected_line().path.to_path_buf();
         mem::swap(&mut self.lines, &mut tree.lines);
+        mem::swap(&mut self.unlisted_matches, &mut tree.unlisted_matches);
         self.scroll = 0;
         if !self.try_select_path(&selected_path) {
             if self.selection >= self.lines.len() {
@@ -108,14 +118,21 @@ impl Tree {
                 if parent_index != last_parent_index {
                     // the line at end_index is the last listed child of the line at parent_index
                     let unlisted = self.lines[parent_index].unlisted;
-                    if unlisted > 0 && self.lines[end_index].nb_kept_children == 0 {
+                    if unlisted > 0 && self.lines[end_index].nb_kept_children == 0
+                        && !self.options.revealed_matches.contains(&self.lines[end_index].path)
+                    {
                         if best_index == end_index {
                             //debug!("Avoiding to prune the line with best score");
                         } else {
                             //debug!("turning {:?} into Pruning", self.lines[end_index].path);
                             self.lines[end_index].line_type = TreeLineType::Pruning;
                             self.lines[end_index].unlisted = unlisted + 1;
-                            self.lines[end_index].name = format!("{} unlisted", unlisted + 1);
+                            self.lines[end_index].not_loaded = self.lines[parent_index].not_loaded;
+                            self.lines[end_index].name = if self.lines[end_index].not_loaded > 0 {
+                                format!("… {} more, use :load_children to load them", unlisted + 1)
+                            } else {
+                                format!("{} unlisted", unlisted + 1)
+                            };
                             self.lines[parent_index].unlisted = 0;
                         }
                     }
@@ -147,16 +164,12 @@ impl Tree {
     pub fn move_selection(&mut self, dy: i32, page_height: i32, cycle: bool) {
         // FIXME may not work well if dy is too big
         let l = self.lines.len() as i32;
-        loop {
-            if !cycle {
-                let s = dy + (self.selection as i32);
-                if s < 0 || s >= l {
-                    break;
-                }
-            }
+        if cycle {
             self.selection = (self.selection + (l + dy) as usize) % self.lines.len();
-            if self.lines[self.selection].is_selectable() {
-                break;
+        } else {
+            let s = dy + (self.selection as i32);
+            if s >= 0 && s < l {
+                self.selection = s as usize;
             }
         }
         // we adjust the scroll
@@ -180,36 +193,130 @@ impl Tree {
         }
     }
 
+    /// scroll the names horizontally by dx chars, without going
+    /// past the end of the longest one
+    pub fn h_scroll_by(&mut self, dx: i32) {
+        let max = self.lines[1..]
+            .iter()
+            .map(|line| line.subpath.chars().count().max(line.name.chars().count()))
+            .max()
+            .unwrap_or(0);
+        self.h_scroll = (self.h_scroll as i32 + dx).clamp(0, max as i32) as usize;
+    }
+
+    /// scroll the view by dy lines, the selection being moved
+    /// only when it would leave the view
+    pub fn scroll_view(&mut self, dy: i32, page_height: i32) {
+        let l = self.lines.len() as i32;
+        self.scroll = (self.scroll + dy).min(l - page_height).max(0);
+        let sel = self.selection as i32;
+        if sel > 0 && sel <= self.scroll {
+            // the root line is always visible
+            self.selection = self.scroll as usize + 1;
+        } else if sel >= self.scroll + page_height {
+            self.selection = (self.scroll + page_height - 1) as usize;
+        }
+    }
+
     pub fn try_scroll(&mut self, dy: i32, page_height: i32) {
         self.scroll = (self.scroll + dy).max(0).min(self.lines.len() as i32 - 5);
         self.select_visible_line(page_height);
     }
 
-    /// try to select a line (works if y+scroll falls on a selectable line)
-    pub fn try_select_y(&mut self, y: i32) -> bool {
+    /// return the index of the line displayed at row y (which
+    /// may be a sticky line)
+    pub fn line_index_at_y(&self, y: i32) -> Option<usize> {
+        if y > 0 {
+            if let Some(&idx) = self.sticky_lines().get(y as usize - 1) {
+                return Some(idx);
+            }
+        }
         let y = y + self.scroll;
         if y >= 0 && y < self.lines.len() as i32 {
-            let y = y as usize;
-            if self.lines[y].is_selectable() {
-                self.selection = y;
-                return true;
+            Some(y as usize)
+        } else {
+            None
+        }
+    }
+    /// tell whether the line is between the start of the current
+    /// mouse drag and the selection
+    pub fn is_in_drag_range(&self, line_index: usize) -> bool {
+        match self.drag_start {
+            Some(start) => {
+                start.min(self.selection) <= line_index && line_index <= start.max(self.selection)
             }
+            None => false,
+        }
+    }
+    /// try to select a line (works if y+scroll falls on a line, or
+    /// if y is the row of a sticky line)
+    pub fn try_select_y(&mut self, y: i32) -> bool {
+        match self.line_index_at_y(y) {
+            Some(idx) => {
+                self.selection = idx;
+                true
+            }
+            None => false,
         }
-        false
     }
-    /// fix the selection so that it's a selectable visible line
+    /// fix the selection so that it's a visible line
     fn select_visible_line(&mut self, page_height: i32) {
         let sel = self.selection as i32;
         if sel < self.scroll || sel >= self.scroll + page_height {
-            self.selection = self.scroll as usize;
-            let l = self.lines.len();
-            loop {
-                self.selection = (self.selection + ((l as i32) + 1) as usize) % l;
-                if self.lines[self.selection].is_selectable() {
-                    break;
-                }
+            self.selection = (self.scroll as usize + 1) % self.lines.len();
+        }
+    }
+
+    /// return the indexes of the ancestors (below the root) of the
+    /// line at idx, the shallowest first
+    fn ancestors(&self, idx: usize) -> Vec<usize> {
+        let mut ancestors = Vec::new();
+        let mut depth = self.lines[idx].depth;
+        for i in (1..idx).rev() {
+            if depth <= 1 {
+                break;
+            }
+            // the lines being sorted, the parent is the closest
+            // previous line which is less deep
+            if self.lines[i].depth < depth {
+                ancestors.push(i);
+                depth = self.lines[i].depth;
             }
         }
+        ancestors.reverse();
+        ancestors
+    }
+
+    /// return the indexes of the lines to pin at the top of the tree,
+    /// over the first visible ones, when it's scrolled: the ancestors
+    /// of the first line which isn't covered, so that it's clear
+    /// which directory it belongs to.
+    ///
+    /// The selected line is never covered.
+    pub fn sticky_lines(&self) -> Vec<usize> {
+        let mut sticky = Vec::new();
+        if self.scroll <= 0 {
+            return sticky;
+        }
+        let scroll = self.scroll as usize;
+        while sticky.len() < MAX_STICKY_LINES {
+            let first = scroll + 1 + sticky.len();
+            if first >= self.lines.len() {
+                break;
+            }
+            let ancestors = self.ancestors(first);
+            if ancestors.len() > sticky.len() {
+                // one more line is covered, whose ancestors may differ
+                sticky = ancestors[..=sticky.len()].to_vec();
+            } else {
+                sticky = ancestors;
+                break;
+            }
+        }
+        if self.selection > scroll {
+            sticky.truncate(self.selection - scroll - 1);
+        }
+        sticky
     }
 
     pub fn make_selection_visible(&mut self, page_height: i32) {
@@ -231,7 +338,7 @@ impl Tree {
     pub fn try_select_best_match(&mut self) {
         let mut best_score = 0;
         for (idx, line) in self.lines.iter().enumerate() {
-            if !line.is_selectable() {
+            if line.is_pruning() {
                 continue;
             }
             if best_score > line.score {
@@ -250,7 +357,7 @@ impl Tree {
     /// return true when we could select the given path
     pub fn try_select_path(&mut self, path: &Path) -> bool {
         for (idx, line) in self.lines.iter().enumerate() {
-            if !line.is_selectable() {
+            if line.is_pruning() {
                 continue;
             }
             if path == line.path {
@@ -261,33 +368,21 @@ impl Tree {
         false
     }
     pub fn try_select_first(&mut self) -> bool {
-        for idx in 0..self.lines.len() {
-            let line = &self.lines[idx];
-            if line.is_selectable() {
-                self.selection = idx;
-                self.scroll = 0;
-                return true;
-            }
-        }
-        false
+        self.selection = 0;
+        self.scroll = 0;
+        true
     }
     pub fn try_select_last(&mut self, page_height: i32) -> bool {
-        for idx in (0..self.lines.len()).rev() {
-            let line = &self.lines[idx];
-            if line.is_selectable() {
-                self.selection = idx;
-                self.make_selection_visible(page_height);
-                return true;
-            }
-        }
-        false
+        self.selection = self.lines.len() - 1;
+        self.make_selection_visible(page_height);
+        true
     }
     pub fn try_select_previous_same_depth(&mut self) -> bool {
         let depth = self.lines[self.selection].depth;
         for di in (0..self.lines.len()).rev() {
             let idx = (self.selection + di) % self.lines.len();
             let line = &self.lines[idx];
-            if !line.is_selectable() || line.depth != depth {
+            if line.is_pruning() || line.depth != depth {
                 continue;
             }
             self.selection = idx;
@@ -300,7 +395,7 @@ impl Tree {
         for di in 0..self.lines.len() {
             let idx = (self.selection + di + 1) % self.lines.len();
             let line = &self.lines[idx];
-            if !line.is_selectable() || line.depth != depth {
+            if line.is_pruning() || line.depth != depth {
                 continue;
             }
             self.selection = idx;
@@ -308,11 +403,18 @@ impl Tree {
         }
         false
     }
+    /// return the paths of the lines matching the pattern
+    pub fn matching_paths(&self) -> Vec<PathBuf> {
+        self.lines.iter()
+            .filter(|line| !line.is_pruning() && line.direct_match && line.score > 0)
+            .map(|line| line.path.clone())
+            .collect()
+    }
     pub fn try_select_previous_match(&mut self) -> bool {
         for di in (0..self.lines.len()).rev() {
             let idx = (self.selection + di) % self.lines.len();
             let line = &self.lines[idx];
-            if !line.is_selectable() {
+            if line.is_pruning() {
                 continue;
             }
             if !line.direct_match {
@@ -325,11 +427,71 @@ impl Tree {
         }
         false
     }
+    /// tell whether a displayed match precedes the selection
+    pub fn has_match_before_selection(&self) -> bool {
+        self.lines.iter()
+            .take(self.selection)
+            .any(|line| !line.is_pruning() && line.direct_match && line.score > 0)
+    }
+    /// tell whether a displayed match follows the selection
+    pub fn has_match_after_selection(&self) -> bool {
+        self.lines[self.selection + 1..].iter()
+            .any(|line| !line.is_pruning() && line.direct_match && line.score > 0)
+    }
+    /// remove from the matches which didn't fit the best one, or
+    /// the worst one when going backward, and return it
+    pub fn take_unlisted_match(&mut self, backward: bool) -> Option<PathBuf> {
+        if backward {
+            self.unlisted_matches.pop()
+        } else if self.unlisted_matches.is_empty() {
+            None
+        } else {
+            Some(self.unlisted_matches.remove(0))
+        }
+    }
+    /// rebuild the tree so that it displays a match which didn't fit,
+    /// in addition to the displayed ones, and select it.
+    ///
+    /// Return false if the build was interrupted, the tree being unchanged
+    pub fn reveal_match(
+        &mut self,
+        path: &Path,
+        page_height: usize,
+        con: &AppContext,
+        dam: &Dam,
+    ) -> Result<bool, errors::TreeBuildError> {
+        let mut options = self.options.clone();
+        options.revealed_matches = self.lines.iter()
+            .filter(|line| !line.is_pruning() && line.direct_match && line.score > 0)
+            .map(|line| line.path.clone())
+            .collect();
+        options.revealed_matches.push(path.to_path_buf());
+        let builder = TreeBuilder::from(
+            self.root().to_path_buf(),
+            options,
+            page_height,
+            con,
+        )?;
+        let tree = match builder.build(self.total_search, dam) {
+            Some(tree) => tree,
+            None => return Ok(false),
+        };
+        let (scroll, h_scroll) = (self.scroll, self.h_scroll);
+        *self = tree;
+        self.scroll = scroll;
+        self.h_scroll = h_scroll;
+        if !self.try_select_path(path) {
+            // the file may have been removed since the first search
+            self.options.revealed_matches.retain(|p| p != path);
+        }
+        self.make_selection_visible(page_height as i32);
+        Ok(true)
+    }
     pub fn try_select_next_match(&mut self) -> bool {
         for di in 0..self.lines.len() {
             let idx = (self.selection + di + 1) % self.lines.len();
             let line = &self.lines[idx];
-            if !line.is_selectable() {
+            if line.is_pruning() {
                 continue;
             }
             if !line.direct_match {
@@ -351,6 +513,15 @@ impl Tree {
                 .any(|line| line.line_type == TreeLineType::Dir && line.sum.is_none())
     }
 
+    pub fn has_dir_missing_ignored_size(&self) -> bool {
+        self.options.show_sizes
+            && self.options.show_ignored_sizes
+            && self
+                .lines
+                .iter()
+                .any(|line| line.line_type == TreeLineType::Dir && line.ignored_size.is_none())
+    }
+
     pub fn is_missing_git_status_computation(&self) -> bool {
         self.git_status.is_not_computed()
     }
@@ -385,6 +556,21 @@ impl Tree {
         }
     }
 
+    /// compute the size of the gitignored content of one directory
+    ///
+    /// To compute it for all of them, this should be called until
+    ///  has_dir_missing_ignored_size returns false
+    pub fn fetch_some_missing_ignored_size(&mut self, dam: &Dam, con: &AppContext) {
+        // as for sums, the root directory is computed last, to benefit
+        // from the cached sizes of its children
+        for i in (0..self.lines.len()).rev() {
+            if self.lines[i].ignored_size.is_none() && self.lines[i].line_type == TreeLineType::Dir {
+                self.lines[i].ignored_size = file_sum::ignored_size(&self.lines[i].path, dam, con);
+                return;
+            }
+        }
+    }
+
     /// Sort files according to the sort option
     ///
     /// (does nothing if it's None)
@@ -405,9 +591,10 @@ impl Tree {
             }
             Sort::Date => {
                 let selected_path = self.selected_line().path.to_path_buf();
+                let kind = self.options.date_kind;
                 self.lines[1..].sort_by(|a, b| {
-                    let adate = a.sum.map_or(0, |s| s.to_seconds());
-                    let bdate = b.sum.map_or(0, |s| s.to_seconds());
+                    let adate = a.sum.map_or(0, |s| s.to_seconds(kind));
+                    let bdate = b.sum.map_or(0, |s| s.to_seconds(kind));
                     bdate.cmp(&adate)
                 });
                 self.try_select_path(&selected_path);
@@ -446,3 +633,118 @@ impl Tree {
     }
 }
 
+#[cfg(test)]
+mod tree_test {
+
+    use {
+        super::*,
+        crate::{
+            search::Searcher,
+            task_sync::CancellationToken,
+        },
+        std::fs,
+    };
+
+    /// build the tree of
+    ///   a/b/c/f1 .. a/b/c/f5
+    ///   z.txt
+    /// whose lines are root, a, b, c, f1 .. f5, z.txt
+    fn make_tree(root: &Path) -> Tree {
+        let c = root.join("a/b/c");
+        fs::create_dir_all(&c).unwrap();
+        for i in 1..=5 {
+            fs::write(c.join(format!("f{}", i)), "").unwrap();
+        }
+        fs::write(root.join("z.txt"), "").unwrap();
+        let tree = Searcher::new().unwrap()
+            .tree(root, TreeOptions::default(), 100, CancellationToken::never())
+            .unwrap()
+            .unwrap();
+        assert_eq!(tree.lines.len(), 10);
+        assert_eq!(tree.lines[4].path, root.join("a/b/c/f1"));
+        tree
+    }
+
+    #[test]
+    fn check_ancestors() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let tree = make_tree(temp_dir.path());
+        assert_eq!(tree.ancestors(1), Vec::<usize>::new());
+        assert_eq!(tree.ancestors(2), vec![1]);
+        assert_eq!(tree.ancestors(3), vec![1, 2]);
+        assert_eq!(tree.ancestors(4), vec![1, 2, 3]);
+        assert_eq!(tree.ancestors(8), vec![1, 2, 3]);
+        assert_eq!(tree.ancestors(9), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn check_sticky_lines() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut tree = make_tree(temp_dir.path());
+        tree.selection = 9;
+        // nothing is pinned when the tree isn't scrolled
+        assert_eq!(tree.sticky_lines(), Vec::<usize>::new());
+        // the lines 3 to 5 are covered by the ancestors of the line 6
+        tree.scroll = 2;
+        assert_eq!(tree.sticky_lines(), vec![1, 2, 3]);
+        // the first not covered line is z.txt, which has no ancestor
+        tree.scroll = 7;
+        assert_eq!(tree.sticky_lines(), Vec::<usize>::new());
+        // the selected line isn't covered
+        tree.scroll = 2;
+        tree.selection = 4;
+        assert_eq!(tree.sticky_lines(), vec![1]);
+        tree.selection = 3;
+        assert_eq!(tree.sticky_lines(), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn check_select_sticky_line() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut tree = make_tree(temp_dir.path());
+        tree.selection = 9;
+        tree.scroll = 2;
+        // the second row shows the pinned line of b
+        assert!(tree.try_select_y(2));
+        assert_eq!(tree.selection, 2);
+        // the fourth one, the first not covered line
+        tree.selection = 9;
+        assert!(tree.try_select_y(4));
+        assert_eq!(tree.selection, 6);
+    }
+
+    #[test]
+    fn check_pruning_lines() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let dir = root.join("dir");
+        fs::create_dir_all(&dir).unwrap();
+        for i in 0..20 {
+            fs::write(dir.join(format!("f{:02}", i)), "").unwrap();
+        }
+        let searcher = Searcher::new().unwrap();
+        let mut tree = searcher
+            .tree(root, TreeOptions::default(), 8, CancellationToken::never())
+            .unwrap()
+            .unwrap();
+        let last = tree.lines.len() - 1;
+        assert!(tree.lines[last].is_pruning());
+        // the pruning line can be selected, but isn't a match
+        assert!(tree.try_select_last(8));
+        assert_eq!(tree.selection, last);
+        tree.move_selection(1, 8, true);
+        assert_eq!(tree.selection, 0);
+        assert!(tree.try_select_y(last as i32));
+        assert_eq!(tree.selection, last);
+        assert!(!tree.matching_paths().contains(&tree.lines[last].path));
+        // once the directory is expanded, all its children are listed
+        let mut options = TreeOptions::default();
+        options.expanded_dirs.push(dir.clone());
+        let tree = searcher
+            .tree(root, options, 8, CancellationToken::never())
+            .unwrap()
+            .unwrap();
+        assert_eq!(tree.lines.len(), 22);
+        assert!(!tree.lines.iter().any(TreeLine::is_pruning));
+    }
+}
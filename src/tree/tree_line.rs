@@ -31,9 +31,13 @@ pub struct TreeLine {
     pub has_error: bool,
     pub nb_kept_children: usize,
     pub unlisted: usize, // number of not listed children (Dir) or brothers (Pruning)
+    pub not_loaded: usize, // number of children (Dir) or brothers (Pruning) not even read
+    pub nb_hidden: usize,     // number of children excluded because they're hidden
+    pub nb_gitignored: usize, // number of children excluded by a gitignore rule
     pub score: i32,      // 0 if there's no pattern
     pub direct_match: bool,
     pub sum: Option<FileSum>, // None when not measured
+    pub ignored_size: Option<u64>, // size of the gitignored content, None when not measured
     pub metadata: fs::Metadata,
     pub git_status: Option<LineGitStatus>,
 }
@@ -54,8 +58,11 @@ impl TreeLine {
             .map(|e| e.as_str())
     }
 
-    pub fn is_selectable(&self) -> bool {
-        !matches!(&self.line_type, TreeLineType::Pruning)
+    /// tell whether the line stands for the unlisted children of its
+    /// directory (it can be selected, to list them, but verbs don't
+    /// apply to it)
+    pub fn is_pruning(&self) -> bool {
+        matches!(&self.line_type, TreeLineType::Pruning)
     }
     pub fn is_dir(&self) -> bool {
         match &self.line_type {
@@ -89,7 +96,7 @@ impl TreeLine {
                     SelectionType::File
                 }
             }
-            Pruning => SelectionType::Any, // should not happen today
+            Pruning => SelectionType::Any, // it's not a path
         }
     }
     pub fn as_selection(&self) -> Selection<'_> {
@@ -1,25 +1,39 @@
 use {
-    super::Sort,
+    super::{DateKind, Sort},
     crate::{
         cli::clap_args,
-        conf::Conf,
+        conf::{Conf, LocalConf, LocalConfBase},
         display::{Cols, DEFAULT_COLS},
         errors::ConfError,
+        path_index::PathIndex,
         pattern::*,
     },
     clap::ArgMatches,
-    std::convert::TryFrom,
+    std::{
+        convert::TryFrom,
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::Duration,
+    },
 };
 
+/// If a search found enough results to fill the screen but didn't scan
+/// everything, it searches a little more in case it finds better matches
+/// but not longer than this default budget.
+const DEFAULT_SEARCH_TIME_BUDGET: Duration = Duration::from_millis(900);
+
 /// Options defining how the tree should be build and|or displayed
 #[derive(Debug, Clone)]
 pub struct TreeOptions {
     pub show_selection_mark: bool, // whether to have a triangle left of selected line
+    pub show_filtered_counts: bool, // whether to show the numbers of hidden and gitignored children
     pub show_hidden: bool, // whether files whose name starts with a dot should be shown
     pub only_folders: bool, // whether to hide normal files and links
     pub show_counts: bool, // whether to show the number of files (> 1 only for dirs)
-    pub show_dates: bool,  // whether to show the last modified date
+    pub show_dates: bool,  // whether to show the date (see date_kind)
+    pub date_kind: DateKind, // which date is shown and used for sorting
     pub show_sizes: bool,  // whether to show sizes of files and dirs
+    pub show_ignored_sizes: bool, // whether to show the gitignored part of the sizes of dirs
     pub show_git_file_info: bool,
     pub show_root_fs: bool, // show information relative to the fs of the root
     pub trim_root: bool,    // whether to cut out direct children of root
@@ -30,6 +44,15 @@ pub struct TreeOptions {
     pub date_time_format: &'static str,
     pub sort: Sort,
     pub cols_order: Cols, // order of columns
+    pub uncapped_dirs: Vec<PathBuf>, // dirs whose children are all loaded, whatever max_loaded_children
+    pub expanded_dirs: Vec<PathBuf>, // dirs whose children are all listed, whatever the screen height
+    pub collapsed_dirs: Vec<PathBuf>, // dirs whose children aren't listed (when there's no pattern)
+    pub revealed_matches: Vec<PathBuf>, // matches kept by the search even when they don't fit the screen
+    pub path_list: Option<Arc<PathIndex>>, // the only paths to display (see --paths-from)
+    pub local_conf: Option<Arc<LocalConf>>, // the .broot.toml files applying to the root
+    pub local_conf_base: Option<LocalConfBase>, // the options as they were before local_conf applied
+    pub search_time_budget: Duration, // how long a search goes on once it can fill the screen
+    pub search_result_cap: Option<usize>, // number of matches after which a search stops
 }
 
 impl TreeOptions {
@@ -37,11 +60,14 @@ impl TreeOptions {
     pub fn without_pattern(&self) -> Self {
         TreeOptions {
             show_selection_mark: self.show_selection_mark,
+            show_filtered_counts: self.show_filtered_counts,
             show_hidden: self.show_hidden,
             only_folders: self.only_folders,
             show_counts: self.show_counts,
             show_dates: self.show_dates,
+            date_kind: self.date_kind,
             show_sizes: self.show_sizes,
+            show_ignored_sizes: self.show_ignored_sizes,
             show_permissions: self.show_permissions,
             respect_git_ignore: self.respect_git_ignore,
             filter_by_git_status: self.filter_by_git_status,
@@ -52,6 +78,30 @@ impl TreeOptions {
             date_time_format: self.date_time_format,
             sort: self.sort,
             cols_order: self.cols_order,
+            uncapped_dirs: self.uncapped_dirs.clone(),
+            expanded_dirs: self.expanded_dirs.clone(),
+            collapsed_dirs: self.collapsed_dirs.clone(),
+            revealed_matches: Vec::new(),
+            path_list: self.path_list.clone(),
+            local_conf: self.local_conf.clone(),
+            local_conf_base: self.local_conf_base,
+            search_time_budget: self.search_time_budget,
+            search_result_cap: self.search_result_cap,
+        }
+    }
+    /// make all the children of the directory listed, even
+    /// if the tree becomes higher than the screen
+    pub fn expand_dir(&mut self, dir: &Path) {
+        self.collapsed_dirs.retain(|d| d != dir);
+        if !self.expanded_dirs.iter().any(|d| d == dir) {
+            self.expanded_dirs.push(dir.to_path_buf());
+        }
+    }
+    /// make the children of the directory not listed
+    pub fn collapse_dir(&mut self, dir: &Path) {
+        self.expanded_dirs.retain(|d| d != dir);
+        if !self.collapsed_dirs.iter().any(|d| d == dir) {
+            self.collapsed_dirs.push(dir.to_path_buf());
         }
     }
     /// counts must be computed, either for sorting or just for display
@@ -70,21 +120,28 @@ impl TreeOptions {
         self.needs_counts() || self.needs_dates() || self.needs_sizes()
     }
     /// this method does not exist, you saw nothing
-    /// (at least don't call it other than with the config, once)
+    /// (at least don't call it other than when reading the config)
     pub fn set_date_time_format(&mut self, format: String) {
         self.date_time_format = Box::leak(format.into_boxed_str());
     }
     /// change tree options according to configuration
     pub fn apply_config(&mut self, config: &Conf) -> Result<(), ConfError> {
         if let Some(default_flags) = &config.default_flags {
-            let clap_app = clap_args::clap_app().setting(clap::AppSettings::NoBinaryName);
-            let flags_args = format!("-{}", default_flags);
-            let conf_matches = clap_app.get_matches_from(vec![&flags_args]);
-            self.apply_launch_args(&conf_matches);
+            let conf_matches = parse_flags(default_flags)?;
+            self.apply_launch_args(&conf_matches)?;
         }
         if let Some(b) = &config.show_selection_mark {
             self.show_selection_mark = *b;
         }
+        if let Some(b) = &config.show_filtered_counts {
+            self.show_filtered_counts = *b;
+        }
+        if let Some(ms) = config.search_time_budget {
+            self.search_time_budget = Duration::from_millis(ms);
+        }
+        if config.search_result_cap.is_some() {
+            self.search_result_cap = config.search_result_cap;
+        }
         if let Some(format) = &config.date_time_format {
             self.set_date_time_format(format.clone());
         }
@@ -96,14 +153,59 @@ impl TreeOptions {
             .unwrap_or(DEFAULT_COLS);
         Ok(())
     }
+    /// reset the options which can be set by launch flags to the
+    /// ones of `initial`
+    fn reset_flag_options(&mut self, initial: &TreeOptions) {
+        self.show_hidden = initial.show_hidden;
+        self.only_folders = initial.only_folders;
+        self.show_counts = initial.show_counts;
+        self.show_dates = initial.show_dates;
+        self.date_kind = initial.date_kind;
+        self.show_sizes = initial.show_sizes;
+        self.show_ignored_sizes = initial.show_ignored_sizes;
+        self.show_git_file_info = initial.show_git_file_info;
+        self.show_root_fs = initial.show_root_fs;
+        self.trim_root = initial.trim_root;
+        self.show_permissions = initial.show_permissions;
+        self.respect_git_ignore = initial.respect_git_ignore;
+        self.filter_by_git_status = initial.filter_by_git_status;
+        self.sort = initial.sort;
+        self.search_time_budget = initial.search_time_budget;
+        self.search_result_cap = initial.search_result_cap;
+    }
+    /// reset the options which can be set by launch flags to the
+    /// ones of `initial`, then apply the flags of a profile
+    pub fn apply_profile(&mut self, initial: &TreeOptions, profile: &ArgMatches<'_>) {
+        self.reset_flag_options(initial);
+        // the values of the profile flags were checked by parse_flags
+        let _ = self.apply_launch_args(profile);
+    }
+    /// reset the options coming from the configuration or from the
+    /// launch flags to the ones of `initial`, the pattern and the
+    /// state of the directories being kept
+    pub fn apply_initial(&mut self, initial: &TreeOptions) {
+        self.reset_flag_options(initial);
+        self.show_selection_mark = initial.show_selection_mark;
+        self.show_filtered_counts = initial.show_filtered_counts;
+        self.date_time_format = initial.date_time_format;
+        self.cols_order = initial.cols_order;
+        // the local configuration still applies over the initial options
+        if let Some(local_conf) = self.local_conf.clone() {
+            self.local_conf_base = Some(local_conf.apply_to(self));
+        }
+    }
     /// change tree options according to broot launch arguments
-    pub fn apply_launch_args(&mut self, cli_args: &ArgMatches<'_>) {
+    pub fn apply_launch_args(&mut self, cli_args: &ArgMatches<'_>) -> Result<(), ConfError> {
         if cli_args.is_present("sizes") {
             self.show_sizes = true;
             self.show_root_fs = true;
         } else if cli_args.is_present("no-sizes") {
             self.show_sizes = false;
         }
+        if cli_args.is_present("ignored-sizes") {
+            self.show_ignored_sizes = true;
+            self.show_sizes = true;
+        }
         if cli_args.is_present("whale-spotting") {
             self.show_hidden = true;
             self.respect_git_ignore = false;
@@ -156,6 +258,9 @@ impl TreeOptions {
             self.sort = Sort::Date;
             self.show_dates = true;
         }
+        if let Some(kind) = cli_args.value_of("date-kind") {
+            self.date_kind = kind.parse()?;
+        }
         if cli_args.is_present("sort-by-size") {
             self.sort = Sort::Size;
             self.show_sizes = true;
@@ -163,23 +268,77 @@ impl TreeOptions {
         if cli_args.is_present("no-sort") {
             self.sort = Sort::None;
         }
+        if let Some(ms) = cli_args.value_of("search-time-budget") {
+            let ms = ms.parse().map_err(|_| ConfError::InvalidLaunchArg {
+                arg: "search-time-budget".to_string(),
+                value: ms.to_string(),
+                expected: "a duration in milliseconds".to_string(),
+            })?;
+            self.search_time_budget = Duration::from_millis(ms);
+        }
+        if let Some(cap) = cli_args.value_of("search-result-cap") {
+            let cap = match cap.parse() {
+                Ok(cap) if cap > 0 => cap,
+                _ => {
+                    return Err(ConfError::InvalidLaunchArg {
+                        arg: "search-result-cap".to_string(),
+                        value: cap.to_string(),
+                        expected: "a number of matches greater than 0".to_string(),
+                    });
+                }
+            };
+            self.search_result_cap = Some(cap);
+        }
         if cli_args.is_present("trim-root") {
             self.trim_root = true;
         } else if cli_args.is_present("no-trim-root") {
             self.trim_root = false;
         }
+        Ok(())
     }
 }
 
+/// parse flags given in configuration, either in the short form
+/// of `default_flags` (eg "gh") or as launch arguments (eg "-g --sizes")
+pub fn parse_flags(flags: &str) -> Result<ArgMatches<'static>, ConfError> {
+    let flags = flags.trim();
+    let args: Vec<String> = if flags.starts_with('-') {
+        flags.split_whitespace().map(|s| s.to_string()).collect()
+    } else if flags.is_empty() {
+        Vec::new()
+    } else {
+        vec![format!("-{}", flags)]
+    };
+    let matches = clap_args::clap_app()
+        .setting(clap::AppSettings::NoBinaryName)
+        .get_matches_from_safe(args)
+        .map_err(|e| ConfError::InvalidFlags {
+            flags: flags.to_string(),
+            details: e.message
+                .lines()
+                .next()
+                .unwrap_or("")
+                .trim_start_matches("error: ")
+                .to_string(),
+        })?;
+    // the values of the flags are checked now, so that they
+    // can't fail when applied later
+    TreeOptions::default().apply_launch_args(&matches)?;
+    Ok(matches)
+}
+
 impl Default for TreeOptions {
     fn default() -> Self {
         Self {
             show_selection_mark: false,
+            show_filtered_counts: false,
             show_hidden: false,
             only_folders: false,
             show_counts: false,
             show_dates: false,
+            date_kind: DateKind::Modified,
             show_sizes: false,
+            show_ignored_sizes: false,
             show_git_file_info: false,
             show_root_fs: false,
             trim_root: false,
@@ -190,6 +349,71 @@ impl Default for TreeOptions {
             date_time_format: "%Y/%m/%d %R",
             sort: Sort::None,
             cols_order: DEFAULT_COLS,
+            uncapped_dirs: Vec::new(),
+            expanded_dirs: Vec::new(),
+            collapsed_dirs: Vec::new(),
+            revealed_matches: Vec::new(),
+            path_list: None,
+            local_conf: None,
+            local_conf_base: None,
+            search_time_budget: DEFAULT_SEARCH_TIME_BUDGET,
+            search_result_cap: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tree_options_test {
+
+    use super::*;
+
+    #[test]
+    fn check_profile_parsing() {
+        let initial = TreeOptions {
+            show_hidden: true,
+            ..TreeOptions::default()
+        };
+        let mut options = initial.clone();
+        options.apply_profile(&initial, &parse_flags("-s --sort-by-size").unwrap());
+        assert!(options.show_sizes);
+        assert_eq!(options.sort, Sort::Size);
+        // applying another profile doesn't keep the flags of the previous one
+        options.apply_profile(&initial, &parse_flags("gH").unwrap());
+        assert!(!options.show_sizes);
+        assert!(!options.show_hidden);
+        assert!(options.show_git_file_info);
+        assert_eq!(options.sort, Sort::None);
+        assert!(parse_flags("Z").is_err());
+    }
+
+    #[test]
+    fn check_launch_arg_values() {
+        let mut options = TreeOptions::default();
+        options.apply_launch_args(
+            &parse_flags("--search-time-budget 300 --search-result-cap 50").unwrap(),
+        ).unwrap();
+        assert_eq!(options.search_time_budget, Duration::from_millis(300));
+        assert_eq!(options.search_result_cap, Some(50));
+        assert!(parse_flags("--search-time-budget soon").is_err());
+        assert!(parse_flags("--search-result-cap 0").is_err());
+        assert!(parse_flags("--search-result-cap many").is_err());
+    }
+
+    #[test]
+    fn check_apply_initial() {
+        let conf = Conf {
+            default_flags: Some("h".to_string()),
+            show_selection_mark: Some(true),
+            ..Conf::default()
+        };
+        let mut initial = TreeOptions::default();
+        initial.apply_config(&conf).unwrap();
+        let mut options = TreeOptions::default();
+        options.expand_dir(Path::new("/a"));
+        options.apply_initial(&initial);
+        assert!(options.show_hidden);
+        assert!(options.show_selection_mark);
+        // the state of the directories is kept
+        assert_eq!(options.expanded_dirs, vec![PathBuf::from("/a")]);
+    }
+}
@@ -8,7 +8,7 @@ use {
         tree::*,
     },
     id_arena::Arena,
-    std::{fs, path::PathBuf, result::Result},
+    std::{fs, path::PathBuf, result::Result, time::SystemTime},
 };
 
 /// like a tree line, but with the info needed during the build
@@ -22,6 +22,7 @@ pub struct BLine {
     pub file_type: fs::FileType,
     pub children: Option<Vec<BId>>, // sorted and filtered
     pub next_child_idx: usize,      // index for iteration, among the children
+    pub nb_not_loaded: usize,       // children left unread because of max_loaded_children
     pub has_error: bool,
     pub has_match: bool,
     pub direct_match: bool,
@@ -29,6 +30,10 @@ pub struct BLine {
     pub nb_kept_children: i32, // used during the trimming step
     pub git_ignore_chain: GitIgnoreChain,
     pub special_handling: SpecialHandling,
+    pub depth_limit: Option<u16>, // directories at this depth aren't entered (see MaxDepth)
+    pub nb_hidden: usize,     // children excluded because they're hidden
+    pub nb_gitignored: usize, // children excluded by a gitignore rule
+    pub mtime: Option<SystemTime>, // only read for directories, when it orders a search
 }
 
 impl BLine {
@@ -53,6 +58,7 @@ impl BLine {
                 subpath: String::new(),
                 children: None,
                 next_child_idx: 0,
+                nb_not_loaded: 0,
                 file_type,
                 has_error: false,
                 has_match: true,
@@ -61,6 +67,10 @@ impl BLine {
                 nb_kept_children: 0,
                 git_ignore_chain,
                 special_handling: SpecialHandling::None,
+                depth_limit: None,
+                nb_hidden: 0,
+                nb_gitignored: 0,
+                mtime: None,
             }))
         } else {
             Err(TreeBuildError::FileNotFound {
@@ -69,7 +79,13 @@ impl BLine {
         }
     }
     /// tell whether we should list the childs of the present line
-    pub fn can_enter(&self) -> bool {
+    pub fn can_enter(&self, searching: bool) -> bool {
+        if matches!(self.depth_limit, Some(limit) if self.depth >= limit) {
+            return false;
+        }
+        if searching && self.special_handling == SpecialHandling::NoRecurse {
+            return false;
+        }
         if self.file_type.is_dir() && self.special_handling != SpecialHandling::NoEnter {
             return true;
         }
@@ -101,7 +117,7 @@ impl BLine {
         let line_type = TreeLineType::new(&self.path, &self.file_type);
         let unlisted = if let Some(children) = &self.children {
             // number of not listed children
-            children.len() - self.next_child_idx
+            children.len() - self.next_child_idx + self.nb_not_loaded
         } else {
             0
         };
@@ -132,9 +148,13 @@ impl BLine {
             has_error,
             nb_kept_children: self.nb_kept_children as usize,
             unlisted,
+            not_loaded: self.nb_not_loaded,
+            nb_hidden: self.nb_hidden,
+            nb_gitignored: self.nb_gitignored,
             score: self.score,
             direct_match: self.direct_match,
             sum: None,
+            ignored_size: None,
             metadata,
             git_status: None,
         })
@@ -0,0 +1,48 @@
+use {
+    std::{
+        sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        time::{Duration, Instant},
+    },
+};
+
+/// a duration which can be incremented from several threads
+#[derive(Debug, Default)]
+pub struct Timing {
+    nanos: AtomicU64,
+}
+
+impl Timing {
+    pub fn add_since(&self, start: Instant) {
+        self.nanos.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+    }
+    pub fn get(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed))
+    }
+}
+
+/// Durations and counts measured during a tree build.
+///
+/// They're measured only when a report is given to the builder
+/// (eg with `--bench`) as measuring has a cost. Durations of steps
+/// done in parallel are summed over all threads, so they may be
+/// greater than the total.
+#[derive(Debug, Default)]
+pub struct BuildReport {
+    pub from_index: AtomicBool,
+    pub nb_dirs_read: AtomicUsize,
+    pub nb_entries: AtomicUsize,
+    pub dir_reading: Timing,
+    pub scoring: Timing,
+    pub git_ignore: Timing,
+    pub gathering: Timing,
+    pub trimming: Timing,
+    pub making_lines: Timing,
+    pub total: Timing,
+}
+
+impl BuildReport {
+    pub fn count_dir(&self, nb_entries: usize) {
+        self.nb_dirs_read.fetch_add(1, Ordering::Relaxed);
+        self.nb_entries.fetch_add(nb_entries, Ordering::Relaxed);
+    }
+}
@@ -2,26 +2,30 @@ use {
     super::{
         bid::{BId, SortableBId},
         bline::BLine,
+        build_report::{BuildReport, Timing},
+        search_priority,
     },
     crate::{
         app::AppContext,
         errors::TreeBuildError,
         git::{GitIgnoreChain, GitIgnorer, LineStatusComputer},
         pattern::Candidate,
-        path::{SpecialHandling, SpecialPathList},
-        task_sync::ComputationResult,
-        task_sync::Dam,
+        path::{ScoreAdjustmentList, SpecialHandling, SpecialPathList},
+        path_index::{self, PathIndex},
+        task_sync::{CancellationToken, ComputationResult, Dam},
         tree::*,
     },
+    ahash::AHashMap,
     git2::Repository,
     id_arena::Arena,
     rayon::prelude::*,
     std::{
         collections::{BinaryHeap, VecDeque},
         fs,
-        path::PathBuf,
+        path::{Path, PathBuf},
         result::Result,
-        time::{Duration, Instant},
+        sync::{atomic::Ordering, Arc},
+        time::Instant,
     },
 };
 
@@ -43,10 +47,12 @@ impl OsStrWin for OsStr {
         self.to_str().map(|s| s.as_bytes()).unwrap_or(INVALID_UTF8)
     }
 }
-/// If a search found enough results to fill the screen but didn't scan
-/// everything, we search a little more in case we find better matches
-/// but not after the NOT_LONG duration.
-static NOT_LONG: Duration = Duration::from_millis(900);
+/// why an entry of a directory isn't made a line of the tree
+enum Exclusion {
+    Hidden,     // its name starts with a dot and hidden files aren't shown
+    GitIgnored, // a gitignore rule excludes it
+    Other,      // not matching the pattern or the filters, or unreadable
+}
 
 /// The TreeBuilder builds a Tree according to options (including an optional search pattern)
 /// Instead of the final TreeLine, the builder uses an internal structure: BLine.
@@ -57,6 +63,8 @@ pub struct TreeBuilder<'c> {
     pub options: TreeOptions,
     targeted_size: usize, // the number of lines we should fill (height of the screen)
     nb_gitignored: u32,   // number of times a gitignore pattern excluded a file
+    budget_exhausted: bool, // whether the search was stopped by the time budget
+    result_capped: bool, // whether the search was stopped by the result cap
     blines: Arena<BLine>,
     root_id: BId,
     total_search: bool,
@@ -64,6 +72,10 @@ pub struct TreeBuilder<'c> {
     line_status_computer: Option<LineStatusComputer>,
     con: &'c AppContext,
     trim_root: bool,
+    cancellation: CancellationToken, // shared with the workers of the build
+    report: Option<Arc<BuildReport>>, // where to record timings, if required
+    trimmed_matches: Vec<SortableBId>, // the matches removed to fit the screen
+    listing_index: Option<(Arc<PathIndex>, String)>, // where children are read, when not on disk
 }
 impl<'c> TreeBuilder<'c> {
 
@@ -93,6 +105,8 @@ impl<'c> TreeBuilder<'c> {
             options,
             targeted_size,
             nb_gitignored: 0,
+            budget_exhausted: false,
+            result_capped: false,
             blines,
             root_id,
             total_search: true, // we'll set it to false if we don't look at all children
@@ -100,22 +114,81 @@ impl<'c> TreeBuilder<'c> {
             line_status_computer,
             con,
             trim_root,
+            cancellation: CancellationToken::never(),
+            report: None,
+            trimmed_matches: Vec::new(),
+            listing_index: None,
         })
     }
 
-    /// return a bline if the dir_entry directly matches the options and there's no error
+    /// require the durations of the build steps to be
+    /// measured and recorded in the given report
+    pub fn with_report(mut self, report: Arc<BuildReport>) -> Self {
+        self.report = Some(report);
+        self
+    }
+
+    /// start measuring a step, if a report was required
+    fn timer(&self) -> Option<Instant> {
+        self.report.as_ref().map(|_| Instant::now())
+    }
+
+    /// add the time elapsed since the start of the timer
+    /// to the given timing of the report
+    fn record(&self, timer: Option<Instant>, timing: fn(&BuildReport) -> &Timing) {
+        if let (Some(report), Some(start)) = (&self.report, timer) {
+            timing(report).add_since(start);
+        }
+    }
+
+    /// return the special handling of the path, the special paths
+    /// of the local configuration having precedence
+    fn special_handling(&self, path: &Path) -> SpecialHandling {
+        if let Some(local_conf) = &self.options.local_conf {
+            let handling = local_conf.special_paths.as_slice().find(path);
+            if handling != SpecialHandling::None {
+                return handling;
+            }
+        }
+        self.con.special_paths.find(path)
+    }
+
+    /// return the depth at which directories stop being entered,
+    /// because of a MaxDepth special handling of the line or of
+    /// one of its ancestors
+    fn depth_limit(
+        &self,
+        parent_id: BId,
+        depth: u16,
+        special_handling: SpecialHandling,
+    ) -> Option<u16> {
+        let parent_limit = self.blines[parent_id].depth_limit;
+        match special_handling {
+            SpecialHandling::MaxDepth(max) => {
+                let limit = depth + max;
+                Some(parent_limit.map_or(limit, |pl| pl.min(limit)))
+            }
+            _ => parent_limit,
+        }
+    }
+
+    /// return a bline if the dir_entry directly matches the options and there's no error,
+    /// or the reason why it's excluded
     fn make_line(
         &self,
         parent_id: BId,
         e: &fs::DirEntry,
         depth: u16,
-    ) -> Option<BLine> {
+    ) -> Result<BLine, Exclusion> {
+        if self.cancellation.is_cancelled() {
+            return Err(Exclusion::Other);
+        }
         let name = e.file_name();
         if name.is_empty() {
-            return None;
+            return Err(Exclusion::Other);
         }
         if !self.options.show_hidden && name.as_bytes()[0] == b'.' {
-            return None;
+            return Err(Exclusion::Hidden);
         }
         let name = name.to_string_lossy();
         let mut has_match = true;
@@ -124,7 +197,7 @@ impl<'c> TreeBuilder<'c> {
         let file_type = match e.file_type() {
             Ok(ft) => ft,
             Err(_) => {
-                return None;
+                return Err(Exclusion::Other);
             }
         };
         let parent_subpath = &self.blines[parent_id].subpath;
@@ -138,15 +211,18 @@ impl<'c> TreeBuilder<'c> {
             subpath: &subpath,
             path: &path,
             regular_file: file_type.is_file(),
+            cancellation: Some(&self.cancellation),
         };
+        let timer = self.timer();
         let direct_match = if let Some(pattern_score) = self.options.pattern.pattern.score_of(candidate) {
             // we dope direct matchs to compensate for depth doping of parent folders
-            score += pattern_score + 10;
+            score += pattern_score + 10 + self.con.score_adjustments.as_slice().adjustment(&subpath);
             true
         } else {
             has_match = false;
             false
         };
+        self.record(timer, |r| &r.scoring);
         let name = name.to_string();
         if has_match && self.options.filter_by_git_status {
             if let Some(line_status_computer) = &self.line_status_computer {
@@ -157,26 +233,35 @@ impl<'c> TreeBuilder<'c> {
         }
         if file_type.is_file() || file_type.is_symlink() {
             if !has_match {
-                return None;
+                return Err(Exclusion::Other);
             }
             if self.options.only_folders {
-                return None;
+                return Err(Exclusion::Other);
             }
         }
-        let special_handling = self.con.special_paths.find(&path);
+        let special_handling = self.special_handling(&path);
         if special_handling == SpecialHandling::Hide {
-            return None;
+            return Err(Exclusion::Other);
         }
+        // the modification date of directories is read here, in parallel
+        // with the other entries, rather than when sorting the next level
+        let mtime = if file_type.is_dir() && self.prioritizes_dirs() {
+            e.metadata().and_then(|md| md.modified()).ok()
+        } else {
+            None
+        };
         if self.options.respect_git_ignore {
+            let timer = self.timer();
             let parent_chain = &self.blines[parent_id].git_ignore_chain;
-            if !self
+            let accepted = self
                 .git_ignorer
-                .accepts(parent_chain, &path, &name, file_type.is_dir())
-            {
-                return None;
+                .accepts(parent_chain, &path, &name, file_type.is_dir());
+            self.record(timer, |r| &r.git_ignore);
+            if !accepted {
+                return Err(Exclusion::GitIgnored);
             }
         };
-        Some(BLine {
+        Ok(BLine {
             parent_id: Some(parent_id),
             path,
             depth,
@@ -185,6 +270,7 @@ impl<'c> TreeBuilder<'c> {
             file_type,
             children: None,
             next_child_idx: 0,
+            nb_not_loaded: 0,
             has_error: false,
             has_match,
             direct_match,
@@ -192,29 +278,80 @@ impl<'c> TreeBuilder<'c> {
             nb_kept_children: 0,
             git_ignore_chain: GitIgnoreChain::default(),
             special_handling,
+            depth_limit: self.depth_limit(parent_id, depth, special_handling),
+            nb_hidden: 0,
+            nb_gitignored: 0,
+            mtime,
         })
     }
 
     /// returns true when there are direct matches among children
     fn load_children(&mut self, bid: BId) -> bool {
+        if let Some((index, base)) = self.listing_index.clone() {
+            return self.load_listed_children(bid, &index, &base);
+        }
         let mut has_child_match = false;
+        let timer = self.timer();
         match fs::read_dir(&self.blines[bid].path) {
-            Ok(entries) => {
+            Ok(mut entries) => {
                 let mut children: Vec<BId> = Vec::new();
                 let child_depth = self.blines[bid].depth + 1;
-                let entries: Vec<fs::DirEntry> = entries.filter_map(Result::ok).collect();
-                let lines: Vec<BLine> = entries
+                // a search must see all the children, or it would miss matches
+                let max_children = if self.options.pattern.is_some()
+                    || self.options.uncapped_dirs.contains(&self.blines[bid].path)
+                {
+                    usize::MAX
+                } else {
+                    self.con.max_loaded_children
+                };
+                let loaded: Vec<fs::DirEntry> = entries
+                    .by_ref()
+                    .filter_map(Result::ok)
+                    .take(max_children)
+                    .collect();
+                // in pathological directories (eg with millions of children)
+                // the remaining entries are only counted, not kept
+                let nb_not_loaded = entries.filter_map(Result::ok).count();
+                if nb_not_loaded > 0 {
+                    debug!("{} children of {:?} not loaded", nb_not_loaded, &self.blines[bid].path);
+                }
+                self.blines[bid].nb_not_loaded = nb_not_loaded;
+                self.record(timer, |r| &r.dir_reading);
+                if let Some(report) = &self.report {
+                    report.count_dir(loaded.len() + nb_not_loaded);
+                }
+                let entries = loaded;
+                let lines: Vec<Result<BLine, Exclusion>> = entries
                     .par_iter()
-                    .filter_map(|e| self.make_line(bid, e, child_depth))
+                    .map(|e| self.make_line(bid, e, child_depth))
                     .collect();
+                let mut nb_hidden = 0;
+                let mut nb_gitignored = 0;
+                let lines = lines.into_iter().filter_map(|line| match line {
+                    Ok(bl) => Some(bl),
+                    Err(Exclusion::Hidden) => {
+                        nb_hidden += 1;
+                        None
+                    }
+                    Err(Exclusion::GitIgnored) => {
+                        nb_gitignored += 1;
+                        None
+                    }
+                    Err(Exclusion::Other) => None,
+                }).collect::<Vec<BLine>>();
+                self.blines[bid].nb_hidden = nb_hidden;
+                self.blines[bid].nb_gitignored = nb_gitignored;
+                self.nb_gitignored += nb_gitignored as u32;
                 for mut bl in lines {
                     if self.options.respect_git_ignore {
+                        let timer = self.timer();
                         let parent_chain = &self.blines[bid].git_ignore_chain;
                         bl.git_ignore_chain = if bl.file_type.is_dir() {
                             self.git_ignorer.deeper_chain(parent_chain, &bl.path)
                         } else {
                             parent_chain.clone()
                         };
+                        self.record(timer, |r| &r.git_ignore);
                     }
                     if bl.has_match {
                         self.blines[bid].has_match = true;
@@ -239,6 +376,52 @@ impl<'c> TreeBuilder<'c> {
         has_child_match
     }
 
+    /// load the children of a directory from the path list (see --paths-from)
+    /// instead of the disk, so that only the listed paths are displayed
+    fn load_listed_children(&mut self, bid: BId, index: &PathIndex, base: &str) -> bool {
+        let subpath = self.blines[bid].subpath.clone();
+        let index_subpath = if subpath.is_empty() {
+            base.to_string()
+        } else {
+            path_index::join_subpath(base, &subpath)
+        };
+        let entries: Vec<(String, bool)> = match index.dir(&index_subpath) {
+            Some(dir) => dir.dirs.iter().map(|name| (name.clone(), true))
+                .chain(dir.files.iter().map(|name| (name.clone(), false)))
+                .collect(),
+            None => Vec::new(),
+        };
+        let mut children: Vec<BId> = Vec::new();
+        let mut nb_hidden = 0;
+        for (name, is_dir) in entries {
+            if !self.options.show_hidden && name.starts_with('.') {
+                nb_hidden += 1;
+                continue;
+            }
+            if self.options.only_folders && !is_dir {
+                continue;
+            }
+            let child_subpath = path_index::join_subpath(&subpath, &name);
+            if let Some(child_id) = self.make_indexed_line(bid, &child_subpath, &name) {
+                children.push(child_id);
+            }
+        }
+        children.sort_by(|&a, &b| {
+            self.blines[a]
+                .name
+                .to_lowercase()
+                .cmp(&self.blines[b].name.to_lowercase())
+        });
+        let has_child_match = !children.is_empty();
+        let bline = &mut self.blines[bid];
+        bline.nb_hidden = nb_hidden;
+        bline.children = Some(children);
+        if has_child_match {
+            bline.has_match = true;
+        }
+        has_child_match
+    }
+
     /// return the next child.
     /// load_children must have been called before on parent_id
     fn next_child(&mut self, parent_id: BId) -> Option<BId> {
@@ -256,38 +439,51 @@ impl<'c> TreeBuilder<'c> {
         }
     }
 
+    /// tell whether the directories of a level are explored in the
+    /// order given by the search priority heuristic
+    fn prioritizes_dirs(&self) -> bool {
+        self.con.search_priority_heuristic && self.options.pattern.is_some()
+    }
+
+    /// the number of lines after which a search which isn't total may stop
+    fn optimal_size(&self) -> usize {
+        match self.options.search_result_cap {
+            Some(cap) => cap,
+            None if self.options.pattern.pattern.has_real_scores() => 10 * self.targeted_size,
+            None => self.targeted_size,
+        }
+    }
+
     /// first step of the build: we explore the directories and gather lines.
     /// If there's no search pattern we stop when we have enough lines to fill the screen.
     /// If there's a pattern, we try to gather more lines that will be sorted afterwards.
-    fn gather_lines(&mut self, total_search: bool, dam: &Dam) -> Option<Vec<BId>> {
+    fn gather_lines(&mut self, total_search: bool) -> Option<Vec<BId>> {
         let start = Instant::now();
         let mut out_blines: Vec<BId> = Vec::new(); // the blines we want to display
-        let optimal_size = if self.options.pattern.pattern.has_real_scores() {
-            10 * self.targeted_size
-        } else {
-            self.targeted_size
-        };
+        let optimal_size = self.optimal_size();
         out_blines.push(self.root_id);
         let mut nb_lines_ok = 1; // in out_blines
+        let mut nb_revealed = 0; // revealed matches already in out_blines
         let mut open_dirs: VecDeque<BId> = VecDeque::new();
         let mut next_level_dirs: Vec<BId> = Vec::new();
         self.load_children(self.root_id);
         open_dirs.push_back(self.root_id);
 
-
-        let timer = std::time::Instant::now();
-        let limit = std::env::var("BrootSearchLimit")
-            .map(|limit| Some(Duration::from_millis(limit.parse().ok()?)))
-            .ok()
-            .flatten();
-
         loop {
-            if !total_search && (
-                (nb_lines_ok > optimal_size)
-                || (nb_lines_ok >= self.targeted_size && start.elapsed() > NOT_LONG)
-            ) {
-                self.total_search = false;
-                break;
+            if !total_search {
+                // the search goes on until the revealed matches are found
+                // again, but not past the time budget
+                let revealing = nb_revealed < self.options.revealed_matches.len();
+                if nb_lines_ok > optimal_size && !revealing {
+                    self.total_search = false;
+                    self.result_capped = self.options.search_result_cap.is_some();
+                    break;
+                }
+                if nb_lines_ok >= self.targeted_size && start.elapsed() > self.options.search_time_budget {
+                    self.total_search = false;
+                    self.budget_exhausted = true;
+                    break;
+                }
             }
             if let Some(open_dir_id) = open_dirs.pop_front() {
                 if let Some(child_id) = self.next_child(open_dir_id) {
@@ -295,8 +491,11 @@ impl<'c> TreeBuilder<'c> {
                     let child = &self.blines[child_id];
                     if child.has_match {
                         nb_lines_ok += 1;
+                        if self.is_revealed(child_id) {
+                            nb_revealed += 1;
+                        }
                     }
-                    if child.can_enter() {
+                    if child.can_enter(self.options.pattern.is_some()) && !self.is_collapsed(child_id) {
                         next_level_dirs.push(child_id);
                     }
                     out_blines.push(child_id);
@@ -311,14 +510,11 @@ impl<'c> TreeBuilder<'c> {
                     // except there's nothing deeper
                     break;
                 }
-                if let Some(limit) = limit {
-                    if timer.elapsed() > limit {
-                        // too much time has passed
-                        break;
-                    }
+                if !total_search && self.prioritizes_dirs() {
+                    search_priority::sort_dirs(&self.blines, &mut next_level_dirs);
                 }
                 for next_level_dir_id in &next_level_dirs {
-                    if dam.has_event() {
+                    if self.cancellation.is_cancelled() {
                         info!("task expired (core build - inner loop)");
                         return None;
                     }
@@ -351,15 +547,283 @@ impl<'c> TreeBuilder<'c> {
                 out_blines.push(child_id);
             }
         }
+        if self.cancellation.is_cancelled() {
+            // some lines may have been wrongly rejected
+            info!("task expired (core build)");
+            return None;
+        }
+        Some(out_blines)
+    }
+
+    /// return the path index to use for the search and the subpath
+    /// of the root in this index, if there's a fresh one.
+    ///
+    /// When a path list was given (with --paths-from) and covers the
+    /// root, it's used even without pattern as it's the tree to display.
+    fn usable_index(&self) -> Option<(Arc<PathIndex>, String)> {
+        if let Some(path_list) = &self.options.path_list {
+            let subpath = path_list.subpath_of(&self.blines[self.root_id].path);
+            if let Some(subpath) = subpath.filter(|subpath| path_list.dir(subpath).is_some()) {
+                return Some((Arc::clone(path_list), subpath));
+            }
+        }
+        if self.options.pattern.is_none() || self.options.sort.is_some() {
+            return None;
+        }
+        path_index::fresh_index_for(&self.blines[self.root_id].path)
+    }
+
+    /// first step of the build when a path index covers the root:
+    /// the matching paths are found in the index, then only them and
+    /// their ancestors are checked on disk (and filtered according to
+    /// special paths and gitignore rules)
+    fn gather_lines_from_index(
+        &mut self,
+        index: &PathIndex,
+        base: &str,
+        total_search: bool,
+    ) -> Option<Vec<BId>> {
+        let root_path = self.blines[self.root_id].path.clone();
+        let optimal_size = self.optimal_size();
+        let mut matches: Vec<(i32, String)> = Vec::new(); // score, subpath
+        let mut open_dirs: Vec<(String, u16)> = vec![(String::new(), 0)];
+        let mut nb_seen = 0;
+        while let Some((dir_subpath, dir_depth)) = open_dirs.pop() {
+            let index_subpath = if dir_subpath.is_empty() {
+                base.to_string()
+            } else {
+                path_index::join_subpath(base, &dir_subpath)
+            };
+            let dir = match index.dir(&index_subpath) {
+                Some(dir) => dir,
+                None => continue,
+            };
+            let depth = dir_depth + 1;
+            let entries = dir.dirs.iter().map(|name| (name, true))
+                .chain(dir.files.iter().map(|name| (name, false)));
+            for (name, is_dir) in entries {
+                nb_seen += 1;
+                if nb_seen % 5000 == 0 && self.cancellation.is_cancelled() {
+                    info!("task expired (index search)");
+                    return None;
+                }
+                if !self.options.show_hidden && name.starts_with('.') {
+                    continue;
+                }
+                let subpath = path_index::join_subpath(&dir_subpath, name);
+                if is_dir {
+                    open_dirs.push((subpath.clone(), depth));
+                } else if self.options.only_folders {
+                    continue;
+                }
+                let path = root_path.join(&subpath);
+                let candidate = Candidate {
+                    name,
+                    subpath: &subpath,
+                    path: &path,
+                    regular_file: !is_dir,
+                    cancellation: Some(&self.cancellation),
+                };
+                if let Some(pattern_score) = self.options.pattern.pattern.score_of(candidate) {
+                    let score = 10000 - i32::from(depth) + pattern_score + 10
+                        + self.con.score_adjustments.as_slice().adjustment(&subpath);
+                    matches.push((score, subpath));
+                }
+            }
+        }
+        if self.cancellation.is_cancelled() {
+            info!("task expired (index search)");
+            return None;
+        }
+        debug!("{} matches among {} indexed paths", matches.len(), nb_seen);
+        if !total_search && matches.len() > optimal_size {
+            // as in a disk search, only the best matches are kept
+            matches.select_nth_unstable_by(optimal_size, |a, b| b.0.cmp(&a.0));
+            matches.truncate(optimal_size);
+            self.total_search = false;
+            self.result_capped = self.options.search_result_cap.is_some();
+        }
+        let mut out_blines: Vec<BId> = vec![self.root_id];
+        let mut ids: AHashMap<String, Option<BId>> = AHashMap::default();
+        ids.insert(String::new(), Some(self.root_id));
+        self.blines[self.root_id].children = Some(Vec::new());
+        for (score, subpath) in matches {
+            if self.options.filter_by_git_status {
+                if let Some(line_status_computer) = &self.line_status_computer {
+                    if !line_status_computer.is_interesting(&root_path.join(&subpath)) {
+                        continue;
+                    }
+                }
+            }
+            if let Some(id) = self.indexed_line(&subpath, &mut ids, &mut out_blines) {
+                let bline = &mut self.blines[id];
+                bline.direct_match = true;
+                bline.score = score;
+            }
+        }
         Some(out_blines)
     }
 
+    /// return the id of the bline for the subpath found in the index,
+    /// creating it and its ancestors if needed. Return None when the
+    /// path doesn't exist anymore or must not be displayed.
+    fn indexed_line(
+        &mut self,
+        subpath: &str,
+        ids: &mut AHashMap<String, Option<BId>>,
+        out_blines: &mut Vec<BId>,
+    ) -> Option<BId> {
+        if let Some(id) = ids.get(subpath) {
+            return *id;
+        }
+        let (parent_subpath, name) = match subpath.rfind('/') {
+            Some(idx) => (&subpath[..idx], &subpath[idx + 1..]),
+            None => ("", subpath),
+        };
+        let parent_id = self
+            .indexed_line(parent_subpath, ids, out_blines)
+            .filter(|&parent_id| self.blines[parent_id].can_enter(self.options.pattern.is_some()));
+        let id = parent_id.and_then(|parent_id| self.make_indexed_line(parent_id, subpath, name));
+        if let (Some(parent_id), Some(id)) = (parent_id, id) {
+            // the index gives all children: there's no unlisted one
+            // except the ones removed during trimming
+            if self.blines[id].file_type.is_dir() {
+                self.blines[id].children = Some(Vec::new());
+            }
+            let parent = &mut self.blines[parent_id];
+            if let Some(children) = parent.children.as_mut() {
+                children.push(id);
+                parent.next_child_idx = children.len();
+            }
+            out_blines.push(id);
+        }
+        ids.insert(subpath.to_string(), id);
+        id
+    }
+
+    fn make_indexed_line(
+        &mut self,
+        parent_id: BId,
+        subpath: &str,
+        name: &str,
+    ) -> Option<BId> {
+        let path = self.blines[parent_id].path.join(name);
+        let file_type = fs::symlink_metadata(&path).ok()?.file_type();
+        let special_handling = self.special_handling(&path);
+        if special_handling == SpecialHandling::Hide {
+            return None;
+        }
+        let mut git_ignore_chain = GitIgnoreChain::default();
+        if self.options.respect_git_ignore {
+            let parent_chain = &self.blines[parent_id].git_ignore_chain;
+            if !self.git_ignorer.accepts(parent_chain, &path, name, file_type.is_dir()) {
+                return None;
+            }
+            git_ignore_chain = if file_type.is_dir() {
+                self.git_ignorer.deeper_chain(parent_chain, &path)
+            } else {
+                parent_chain.clone()
+            };
+        }
+        let depth = self.blines[parent_id].depth + 1;
+        let depth_limit = self.depth_limit(parent_id, depth, special_handling);
+        let id = self.blines.alloc(BLine {
+            parent_id: Some(parent_id),
+            path,
+            depth,
+            subpath: subpath.to_string(),
+            name: name.to_string(),
+            file_type,
+            children: None,
+            next_child_idx: 0,
+            nb_not_loaded: 0,
+            has_error: false,
+            has_match: true,
+            direct_match: false,
+            score: 10000 - i32::from(depth),
+            nb_kept_children: 0,
+            git_ignore_chain,
+            special_handling,
+            depth_limit,
+            nb_hidden: 0,
+            nb_gitignored: 0,
+            mtime: None,
+        });
+        Some(id)
+    }
+
+    /// add the unlisted children of the directories the user expanded,
+    /// even if the tree becomes higher than the screen
+    fn list_expanded_children(&mut self, out_blines: &mut Vec<BId>) {
+        if self.options.expanded_dirs.is_empty() {
+            return;
+        }
+        // the added lines are checked too, as they may be expanded
+        let mut nb_added = 0;
+        let mut idx = 0;
+        while idx < out_blines.len() {
+            let dir_id = out_blines[idx];
+            idx += 1;
+            if !self.is_expanded(dir_id) {
+                continue;
+            }
+            if self.blines[dir_id].children.is_none() {
+                if !self.blines[dir_id].can_enter(false) {
+                    continue;
+                }
+                self.load_children(dir_id);
+            }
+            while let Some(child_id) = self.next_child(dir_id) {
+                if self.blines[child_id].has_match {
+                    nb_added += 1;
+                }
+                out_blines.push(child_id);
+            }
+        }
+        if nb_added > 0 {
+            // the lines kept by the trimming must include the added ones
+            self.targeted_size += nb_added;
+            if self.options.pattern.is_none() {
+                // without pattern, the gathering goes one line past the
+                // screen, to make a pruning line. As this line may have
+                // been listed now, it mustn't be trimmed elsewhere
+                self.targeted_size += 1;
+            }
+        }
+    }
+
+    /// tell whether the user asked for all the children of the
+    /// line to be listed
+    fn is_expanded(&self, id: BId) -> bool {
+        self.options.expanded_dirs.contains(&self.blines[id].path)
+    }
+
+    /// tell whether the line is a match which must be kept in the
+    /// tree, because the user navigated to it
+    fn is_revealed(&self, id: BId) -> bool {
+        self.blines[id].has_match
+            && self.options.revealed_matches.contains(&self.blines[id].path)
+    }
+
+    /// tell whether the user asked for the children of the line
+    /// not to be listed. Collapsed directories are still searched
+    fn is_collapsed(&self, id: BId) -> bool {
+        self.options.pattern.is_none()
+            && self.options.collapsed_dirs.contains(&self.blines[id].path)
+    }
+
     /// Post search trimming
     /// When there's a pattern, gathering normally brings many more lines than
     ///  strictly necessary to fill the screen.
     /// This function keeps only the best ones while taking care of not
     ///  removing a parent before its children.
     fn trim_excess(&mut self, out_blines: &[BId]) {
+        if self.options.pattern.is_some() && !self.options.revealed_matches.is_empty() {
+            // when the user navigated to matches which didn't fit, the
+            // tree is made of the revealed matches only, so that the
+            // displayed lines don't change from one revelation to the next
+            self.targeted_size = 0;
+        }
         let mut count = 1;
         for id in out_blines[1..].iter() {
             if self.blines[*id].has_match {
@@ -374,6 +838,8 @@ impl<'c> TreeBuilder<'c> {
         for id in out_blines[1..].iter() {
             let bline = &self.blines[*id];
             if bline.has_match && bline.nb_kept_children == 0 && (bline.depth > 1 || self.trim_root)
+                && !matches!(bline.parent_id, Some(pid) if self.is_expanded(pid))
+                && !self.is_revealed(*id)
             {
                 //debug!("in list: {:?} score: {}",  &bline.path, bline.score);
                 remove_queue.push(SortableBId {
@@ -385,6 +851,12 @@ impl<'c> TreeBuilder<'c> {
         while count > self.targeted_size {
             if let Some(sli) = remove_queue.pop() {
                 self.blines[sli.id].has_match = false;
+                if self.blines[sli.id].direct_match && sli.score > 0 {
+                    self.trimmed_matches.push(SortableBId {
+                        id: sli.id,
+                        score: sli.score,
+                    });
+                }
                 let parent_id = self.blines[sli.id].parent_id.unwrap();
                 let mut parent = &mut self.blines[parent_id];
                 parent.nb_kept_children -= 1;
@@ -397,12 +869,31 @@ impl<'c> TreeBuilder<'c> {
                 }
                 count -= 1;
             } else {
-                debug!("trimming prematurely interrupted");
+                if self.options.revealed_matches.is_empty() {
+                    debug!("trimming prematurely interrupted");
+                }
                 break;
             }
         }
     }
 
+    /// remember the directories containing the matches, so
+    /// that the next searches explore them first
+    fn remember_matched_dirs(&self, out_blines: &[BId]) {
+        let mut dirs = Vec::new();
+        for &id in out_blines {
+            if !(self.blines[id].has_match && self.blines[id].direct_match) {
+                continue;
+            }
+            let mut parent_id = self.blines[id].parent_id;
+            while let Some(id) = parent_id.filter(|&id| id != self.root_id) {
+                dirs.push(self.blines[id].path.as_path());
+                parent_id = self.blines[id].parent_id;
+            }
+        }
+        search_priority::remember_matches(&self.options.pattern.raw, dirs.into_iter());
+    }
+
     /// make a tree from the builder's specific structure
     fn take(mut self, out_blines: &[BId]) -> Tree {
         let mut lines: Vec<TreeLine> = Vec::new();
@@ -426,17 +917,38 @@ impl<'c> TreeBuilder<'c> {
         let mut tree = Tree {
             lines: lines.into_boxed_slice(),
             selection: 0,
+            drag_start: None,
             options: self.options.clone(),
             scroll: 0,
+            h_scroll: 0,
             nb_gitignored: self.nb_gitignored,
             total_search: self.total_search,
+            budget_exhausted: self.budget_exhausted,
+            result_capped: self.result_capped,
             git_status: ComputationResult::None,
+            has_line_git_statuses: false,
+            unlisted_matches: Vec::new(),
         };
         tree.after_lines_changed();
+        if self.options.pattern.is_some() {
+            // the matches which were trimmed, or turned into pruning lines,
+            // can still be reached by match navigation
+            let mut unlisted: Vec<(i32, PathBuf)> = self.trimmed_matches.iter()
+                .map(|sli| (sli.score, self.blines[sli.id].path.clone()))
+                .collect();
+            for line in tree.lines.iter() {
+                if line.is_pruning() && line.direct_match && line.score > 0 {
+                    unlisted.push((line.score, line.path.clone()));
+                }
+            }
+            unlisted.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            tree.unlisted_matches = unlisted.into_iter().map(|(_, path)| path).collect();
+        }
         if let Some(computer) = self.line_status_computer {
             // tree git status is slow to compute, we just mark it should be
             // done (later on)
             tree.git_status = ComputationResult::NotComputed;
+            tree.has_line_git_statuses = true;
             // it would make no sense to keep only files having a git status and
             // not display that type
             for mut line in tree.lines.iter_mut() {
@@ -450,13 +962,201 @@ impl<'c> TreeBuilder<'c> {
     ///
     /// Return None if the lifetime expires before end of computation
     /// (usually because the user hit a key)
-    pub fn build(mut self, total_search: bool, dam: &Dam) -> Option<Tree> {
-        match self.gather_lines(total_search, dam) {
-            Some(out_blines) => {
+    pub fn build(self, total_search: bool, dam: &Dam) -> Option<Tree> {
+        let cancellation = dam.cancellation_token();
+        self.build_with_cancellation(total_search, cancellation)
+    }
+
+    /// build a tree, stopping when the token is cancelled, which
+    /// lets programs without a dam of terminal events interrupt
+    /// the build from another thread.
+    /// Can be called only once per builder.
+    ///
+    /// Return None if the build was cancelled
+    pub fn build_with_cancellation(
+        mut self,
+        total_search: bool,
+        cancellation: CancellationToken,
+    ) -> Option<Tree> {
+        let total_timer = self.timer();
+        self.cancellation = cancellation;
+        let timer = self.timer();
+        let out_blines = match self.usable_index() {
+            Some(listing_index) if self.options.pattern.is_none() => {
+                // without pattern, the path list is explored as the disk
+                // would be, so that the tree is the one of a normal run
+                self.listing_index = Some(listing_index);
+                self.gather_lines(total_search)
+            }
+            Some((index, base)) => {
+                debug!("searching with the path index of {:?}", &index.root);
+                if let Some(report) = &self.report {
+                    report.from_index.store(true, Ordering::Relaxed);
+                }
+                self.gather_lines_from_index(&index, &base, total_search)
+            }
+            None => self.gather_lines(total_search),
+        };
+        self.record(timer, |r| &r.gathering);
+        match out_blines {
+            Some(mut out_blines) => {
+                self.list_expanded_children(&mut out_blines);
+                let timer = self.timer();
                 self.trim_excess(&out_blines);
-                Some(self.take(&out_blines))
+                self.record(timer, |r| &r.trimming);
+                if self.con.search_priority_heuristic && self.options.pattern.is_some() {
+                    self.remember_matched_dirs(&out_blines);
+                }
+                let report = self.report.clone();
+                let timer = self.timer();
+                let tree = self.take(&out_blines);
+                if let (Some(report), Some(start), Some(total_start)) = (report, timer, total_timer) {
+                    report.making_lines.add_since(start);
+                    report.total.add_since(total_start);
+                }
+                Some(tree)
             }
             None => None, // interrupted
         }
     }
 }
+
+#[cfg(test)]
+mod builder_test {
+
+    use {
+        super::*,
+        crate::search::Searcher,
+    };
+
+    fn build(root: &Path, options: TreeOptions, height: usize, searcher: &Searcher) -> Tree {
+        TreeBuilder::from(root.to_path_buf(), options, height, searcher.context())
+            .unwrap()
+            .build(true, &Dam::unlimited())
+            .unwrap()
+    }
+
+    fn displayed_matches(tree: &Tree) -> Vec<PathBuf> {
+        tree.lines[1..].iter()
+            .filter(|line| !line.is_pruning() && line.direct_match && line.score > 0)
+            .map(|line| line.path.clone())
+            .collect()
+    }
+
+    #[test]
+    fn check_trimmed_and_revealed_matches() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let mut all_matches = Vec::new();
+        for i in 0..12 {
+            let dir = root.join(format!("dir{:02}", i));
+            fs::create_dir_all(&dir).unwrap();
+            let file = dir.join(format!("match{:02}.txt", i));
+            fs::write(&file, "").unwrap();
+            all_matches.push(file);
+        }
+        let searcher = Searcher::new().unwrap();
+        let mut options = TreeOptions {
+            pattern: searcher.pattern("match").unwrap(),
+            ..TreeOptions::default()
+        };
+        let tree = build(root, options.clone(), 8, &searcher);
+        // the matches which didn't fit are kept, to be revealed
+        let displayed = displayed_matches(&tree);
+        assert!(!displayed.is_empty());
+        assert!(!tree.unlisted_matches.is_empty());
+        assert!(tree.unlisted_matches.iter().all(|path| !displayed.contains(path)));
+        let mut found: Vec<PathBuf> = displayed.iter()
+            .chain(tree.unlisted_matches.iter())
+            .cloned()
+            .collect();
+        found.sort();
+        assert_eq!(found, all_matches);
+        // once revealed, a match is displayed with the already displayed ones
+        let revealed = tree.unlisted_matches[0].clone();
+        options.revealed_matches = displayed.clone();
+        options.revealed_matches.push(revealed.clone());
+        let tree = build(root, options.clone(), 8, &searcher);
+        let mut expected = options.revealed_matches.clone();
+        expected.sort();
+        assert_eq!(displayed_matches(&tree), expected);
+        assert!(!tree.unlisted_matches.contains(&revealed));
+        assert_eq!(tree.unlisted_matches.len(), all_matches.len() - expected.len());
+        // without pattern, the revealed matches don't change the tree
+        let options = options.without_pattern();
+        assert!(options.revealed_matches.is_empty());
+        let mut with_revealed = options.clone();
+        with_revealed.revealed_matches = vec![revealed];
+        assert_eq!(
+            build(root, with_revealed, 8, &searcher).lines.len(),
+            build(root, options, 8, &searcher).lines.len(),
+        );
+    }
+
+    #[test]
+    fn check_path_list_without_pattern() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        // (the root's children aren't trimmed, so the files are deeper)
+        let dir = root.join("dir");
+        fs::create_dir(&dir).unwrap();
+        let mut listed = Vec::new();
+        for i in 0..30 {
+            let file = dir.join(format!("f{:02}", i));
+            fs::write(&file, "").unwrap();
+            if i % 2 == 0 {
+                listed.push(file);
+            }
+        }
+        let searcher = Searcher::new().unwrap();
+        let options = TreeOptions {
+            path_list: Some(Arc::new(PathIndex::from_paths(root.to_path_buf(), &listed))),
+            ..TreeOptions::default()
+        };
+        let tree = build(root, options, 8, &searcher);
+        let displayed: Vec<PathBuf> = tree.lines[2..].iter()
+            .filter(|line| !line.is_pruning())
+            .map(|line| line.path.clone())
+            .collect();
+        // only listed paths are displayed, and the other listed ones
+        // are counted as unlisted
+        assert!(!displayed.is_empty());
+        assert!(displayed.len() < listed.len());
+        assert!(displayed.iter().all(|path| listed.contains(path)));
+        assert!(displayed.windows(2).all(|w| w[0] < w[1]));
+        let unlisted: usize = tree.lines.iter().map(|line| line.unlisted).sum();
+        assert_eq!(displayed.len() + unlisted, listed.len());
+    }
+
+    #[test]
+    fn check_capped_path_list_search() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let mut listed = Vec::new();
+        for i in 0..30 {
+            let file = root.join(format!("match{:02}", i));
+            fs::write(&file, "").unwrap();
+            listed.push(file);
+        }
+        let searcher = Searcher::new().unwrap();
+        let options = TreeOptions {
+            pattern: searcher.pattern("match").unwrap(),
+            path_list: Some(Arc::new(PathIndex::from_paths(root.to_path_buf(), &listed))),
+            search_result_cap: Some(10),
+            ..TreeOptions::default()
+        };
+        let build = |total_search| {
+            TreeBuilder::from(root.to_path_buf(), options.clone(), 8, searcher.context())
+                .unwrap()
+                .build(total_search, &Dam::unlimited())
+                .unwrap()
+        };
+        // the cap applies to a search in the index as to a disk search
+        let tree = build(false);
+        assert!(!tree.total_search);
+        assert!(tree.result_capped);
+        let tree = build(true);
+        assert!(tree.total_search);
+        assert!(!tree.result_capped);
+    }
+}
@@ -1,5 +1,10 @@
 mod bid;
 mod bline;
+mod build_report;
 mod builder;
+mod search_priority;
 
-pub use builder::TreeBuilder;
+pub use {
+    build_report::{BuildReport, Timing},
+    builder::TreeBuilder,
+};
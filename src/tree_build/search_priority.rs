@@ -0,0 +1,127 @@
+//! Choose the order in which the directories of a level are explored
+//! during a search, so that the best candidates are found sooner.
+
+use {
+    super::{bid::BId, bline::BLine},
+    ahash::AHashMap,
+    id_arena::Arena,
+    std::{
+        cmp::Reverse,
+        path::{Path, PathBuf},
+        sync::Mutex,
+    },
+};
+
+/// number of searches whose matches are remembered
+const REMEMBERED_SEARCHES: usize = 10;
+
+/// the directories where matches were found during the session,
+/// with the number of the last search which found them
+#[derive(Default)]
+struct MatchedDirs {
+    searches_count: usize,
+    last_pattern: String,
+    dirs: AHashMap<PathBuf, usize>,
+}
+
+impl MatchedDirs {
+    fn remember<'p>(&mut self, pattern: &str, dirs: impl Iterator<Item = &'p Path>) {
+        // while the user types (or erases) a pattern, all the intermediate
+        // searches count as only one, so that they don't evict the history
+        let same_search = self.searches_count > 0
+            && (pattern.starts_with(&self.last_pattern) || self.last_pattern.starts_with(pattern));
+        if !same_search {
+            self.searches_count += 1;
+        }
+        self.last_pattern = pattern.to_string();
+        let search = self.searches_count;
+        for dir in dirs {
+            self.dirs.insert(dir.to_path_buf(), search);
+        }
+        self.dirs.retain(|_, s| *s + REMEMBERED_SEARCHES > search);
+    }
+    fn contains(&self, dir: &Path) -> bool {
+        self.dirs.contains_key(dir)
+    }
+}
+
+lazy_static! {
+    static ref MATCHED_DIRS_MUTEX: Mutex<MatchedDirs> = Mutex::new(MatchedDirs::default());
+}
+
+/// remember the directories containing the matches of a completed
+/// search, so that they're explored first in the next searches
+pub fn remember_matches<'p>(pattern: &str, dirs: impl Iterator<Item = &'p Path>) {
+    MATCHED_DIRS_MUTEX.lock().unwrap().remember(pattern, dirs);
+}
+
+/// sort the directories so that the ones where matches were
+/// previously found come first, then the recently modified ones
+pub fn sort_dirs(blines: &Arena<BLine>, dirs: &mut [BId]) {
+    let matched_dirs = MATCHED_DIRS_MUTEX.lock().unwrap();
+    dirs.sort_by_key(|&bid| {
+        let bline = &blines[bid];
+        (Reverse(matched_dirs.contains(&bline.path)), Reverse(bline.mtime))
+    });
+}
+
+#[cfg(test)]
+mod search_priority_test {
+
+    use super::*;
+
+    #[test]
+    fn check_typing_doesnt_evict_history() {
+        let mut matched_dirs = MatchedDirs::default();
+        let old_dir = Path::new("/old");
+        matched_dirs.remember("old", std::iter::once(old_dir));
+        for pattern in &["a", "ab", "abc", "abcd", "abcde", "abcdef", "abcde", "abcd"] {
+            for _ in 0..REMEMBERED_SEARCHES {
+                matched_dirs.remember(pattern, std::iter::once(Path::new("/new")));
+            }
+        }
+        assert!(matched_dirs.contains(old_dir));
+        assert_eq!(matched_dirs.searches_count, 2);
+    }
+
+    #[test]
+    fn check_old_searches_are_forgotten() {
+        let mut matched_dirs = MatchedDirs::default();
+        let first_dir = Path::new("/first");
+        matched_dirs.remember("first", std::iter::once(first_dir));
+        for i in 1..REMEMBERED_SEARCHES {
+            matched_dirs.remember(&format!("{}", i), std::iter::empty());
+        }
+        assert!(matched_dirs.contains(first_dir));
+        matched_dirs.remember("last", std::iter::empty());
+        assert!(!matched_dirs.contains(first_dir));
+    }
+
+    #[test]
+    fn check_dirs_order() {
+        use {
+            crate::{git::GitIgnoreChain, tree::TreeOptions},
+            std::time::{Duration, SystemTime},
+        };
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut blines = Arena::new();
+        let mut dirs = Vec::new();
+        for (name, age) in &[("old", 100), ("new", 10), ("matched", 1000)] {
+            let path = temp_dir.path().join(name);
+            std::fs::create_dir(&path).unwrap();
+            let bid = BLine::from_root(
+                &mut blines,
+                path,
+                GitIgnoreChain::default(),
+                &TreeOptions::default(),
+            ).unwrap();
+            blines[bid].mtime = Some(SystemTime::now() - Duration::from_secs(*age));
+            dirs.push(bid);
+        }
+        let matched = blines[dirs[2]].path.clone();
+        remember_matches("check_dirs_order", std::iter::once(matched.as_path()));
+        sort_dirs(&blines, &mut dirs);
+        let names: Vec<&str> = dirs.iter().map(|&bid| blines[bid].name.as_str()).collect();
+        assert_eq!(names, vec!["matched", "new", "old"]);
+    }
+}
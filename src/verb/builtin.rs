@@ -52,6 +52,8 @@ pub fn builtin_verbs() -> Vec<Verb> {
     use super::{ExternalExecutionMode::*, Internal::*};
     vec![
         internal(back),
+        internal(build_index),
+        internal(cd_now),
 
         // those two operations are mapped on ALT-ENTER, one
         // for directories and the other one for the other files
@@ -72,9 +74,13 @@ pub fn builtin_verbs() -> Vec<Verb> {
         internal(open_preview),
         internal(close_preview),
         internal(toggle_preview),
+        internal(preview_top),
+        internal(preview_bottom),
+        internal(preview_goto),
         internal(preview_image),
         internal(preview_text),
         internal(preview_binary),
+        internal(preview_mode),
         internal(close_panel_ok),
         internal(close_panel_cancel)
             .with_key(BACK_TAB)
@@ -85,11 +91,20 @@ pub fn builtin_verbs() -> Vec<Verb> {
             StayInBroot,
         )
             .with_shortcut("cp"),
+        internal(clean)
+            .with_confirmation(Confirmation::Always),
+        internal(cleanable),
         #[cfg(feature = "clipboard")]
         internal(copy_line)
             .with_alt_key('c'),
         #[cfg(feature = "clipboard")]
         internal(copy_path),
+        #[cfg(feature = "clipboard")]
+        internal(copy_relative_path),
+        #[cfg(feature = "clipboard")]
+        internal(copy_name),
+        #[cfg(feature = "clipboard")]
+        internal(copy_file_content),
         external(
             "copy_to_panel",
             "cp -r {file} {other-panel-directory}",
@@ -110,6 +125,8 @@ pub fn builtin_verbs() -> Vec<Verb> {
         #[cfg(feature="clipboard")]
         internal(input_paste)
             .with_control_key('v'),
+        internal(keys),
+        internal(layout),
         internal(line_down)
             .with_key(DOWN)
             .with_char_key('j'),
@@ -136,12 +153,18 @@ pub fn builtin_verbs() -> Vec<Verb> {
             .with_shortcut("mvp"),
         internal_bang(start_end_panel)
             .with_control_key('p'),
+        internal(date_kind),
+        internal(expand_unlisted),
+        internal(load_children)
+            .with_control_key('x'),
         // the char keys for mode_input are handled differently as they're not
         // consumed by the command
         internal(mode_input)
             .with_char_key(' ')
             .with_char_key(':')
             .with_char_key('/'),
+        internal(new_dir).with_shortcut("nd"),
+        internal(new_file).with_shortcut("nf"),
         internal(next_match)
             .with_key(TAB),
         internal(no_sort)
@@ -160,6 +183,7 @@ pub fn builtin_verbs() -> Vec<Verb> {
         internal(page_up)
             .with_control_key('u')
             .with_key(PAGE_UP),
+        internal(palette),
         internal(panel_left)
             .with_key(KeyEvent {
                 code: KeyCode::Left,
@@ -170,14 +194,28 @@ pub fn builtin_verbs() -> Vec<Verb> {
                 code: KeyCode::Right,
                 modifiers: KeyModifiers::CONTROL,
             }),
+        internal(panel_grow),
+        internal(panel_shrink),
+        internal(panel_zoom),
         internal(print_path).with_shortcut("pp"),
         internal(print_relative_path).with_shortcut("prp"),
         internal(print_tree).with_shortcut("pt"),
+        internal(profile),
         internal(quit)
             .with_control_key('c')
             .with_control_key('q')
             .with_shortcut("q"),
+        internal(rebind),
         internal(refresh).with_key(F5),
+        internal(reload_config),
+        internal(register_paste),
+        internal(register_yank),
+        internal(repeat)
+            .with_char_key('.'),
+        internal(scroll_down),
+        internal(scroll_up),
+        internal(scroll_left),
+        internal(scroll_right),
         internal(select_first).with_key(HOME),
         internal(select_last).with_key(END),
         internal(clear_stage).with_shortcut("cls"),
@@ -187,26 +225,34 @@ pub fn builtin_verbs() -> Vec<Verb> {
             .with_char_key('-'),
         internal(toggle_stage)
             .with_control_key('g'),
+        internal(stage_all).with_shortcut("sa"),
         internal(open_staging_area).with_shortcut("osa"),
         internal(close_staging_area).with_shortcut("csa"),
         internal(toggle_staging_area).with_shortcut("tsa"),
+        internal(tag),
+        internal(untag),
         internal(sort_by_count).with_shortcut("sc"),
         internal(sort_by_date).with_shortcut("sd"),
         internal(sort_by_size).with_shortcut("ss"),
         external("rm", "rm -rf {file}", StayInBroot),
         internal(toggle_counts).with_shortcut("counts"),
         internal(toggle_dates).with_shortcut("dates"),
+        internal(toggle_expand),
         internal(toggle_files).with_shortcut("files"),
         internal(toggle_git_ignore).with_shortcut("gi"),
         internal(toggle_git_file_info).with_shortcut("gf"),
         internal(toggle_git_status).with_shortcut("gs"),
         internal(toggle_root_fs).with_shortcut("rfs"),
         internal(toggle_hidden).with_shortcut("h"),
+        internal(toggle_ignored_sizes).with_shortcut("isizes"),
         #[cfg(unix)]
         internal(toggle_perm).with_shortcut("perm"),
         internal(toggle_sizes).with_shortcut("sizes"),
+        internal(toggle_skin).with_shortcut("skin"),
         internal(toggle_trim_root),
         internal(total_search).with_control_key('s'),
         internal(up_tree).with_shortcut("up"),
+        internal(why_ignored),
+        internal(worktrees).with_shortcut("wt"),
     ]
 }
@@ -0,0 +1,75 @@
+use crate::errors::ConfError;
+
+/// when the execution of a verb must be confirmed by the user
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Confirmation {
+    /// the verb is executed at once
+    Never,
+
+    /// the user is always asked before execution
+    Always,
+
+    /// the user is asked when the verb applies to several paths
+    /// (usually the staged ones)
+    WhenMultiple,
+}
+
+impl Confirmation {
+    pub fn from_conf(s: Option<&str>) -> Result<Self, ConfError> {
+        match s {
+            None | Some("never") => Ok(Self::Never),
+            Some("always") => Ok(Self::Always),
+            Some("when-multiple") | Some("when_multiple") => Ok(Self::WhenMultiple),
+            Some(s) => Err(ConfError::InvalidVerbConf {
+                details: format!(
+                    "{:?} isn't a valid value of confirm, expected always, never or when-multiple",
+                    s,
+                ),
+            }),
+        }
+    }
+
+    /// tell whether the user must confirm the execution of a
+    /// verb applying to `nb_paths` paths
+    pub fn is_required(self, nb_paths: usize) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Always => true,
+            Self::WhenMultiple => nb_paths > 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod confirmation_test {
+
+    use super::*;
+
+    #[test]
+    fn check_confirmation_from_conf() {
+        assert_eq!(Confirmation::from_conf(None).unwrap(), Confirmation::Never);
+        assert_eq!(Confirmation::from_conf(Some("never")).unwrap(), Confirmation::Never);
+        assert_eq!(Confirmation::from_conf(Some("always")).unwrap(), Confirmation::Always);
+        assert_eq!(
+            Confirmation::from_conf(Some("when-multiple")).unwrap(),
+            Confirmation::WhenMultiple,
+        );
+        assert_eq!(
+            Confirmation::from_conf(Some("when_multiple")).unwrap(),
+            Confirmation::WhenMultiple,
+        );
+        assert!(Confirmation::from_conf(Some("sometimes")).is_err());
+        assert!(Confirmation::from_conf(Some("")).is_err());
+    }
+
+    #[test]
+    fn check_confirmation_is_required() {
+        for nb_paths in 0..3 {
+            assert!(!Confirmation::Never.is_required(nb_paths));
+            assert!(Confirmation::Always.is_required(nb_paths));
+        }
+        assert!(!Confirmation::WhenMultiple.is_required(0));
+        assert!(!Confirmation::WhenMultiple.is_required(1));
+        assert!(Confirmation::WhenMultiple.is_required(2));
+    }
+}
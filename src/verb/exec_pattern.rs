@@ -35,6 +35,23 @@ impl ExecPattern {
             Self::Array(v) => v.iter().any(|s| str_has_other_panel_group(s)),
         }
     }
+    pub fn has_multi_path_group(&self) -> bool {
+        match self {
+            Self::String(s) => str_has_multi_path_group(s),
+            Self::Array(v) => v.iter().any(|s| str_has_multi_path_group(s)),
+        }
+    }
+    /// tell whether the pattern contains the given group, eg "{files}"
+    pub fn has_group(&self, group: &str) -> bool {
+        match self {
+            Self::String(s) => s.contains(group),
+            Self::Array(v) => v.iter().any(|s| s.contains(group)),
+        }
+    }
+    /// tell whether the paths must be given NUL separated on stdin
+    pub fn has_stdin_group(&self) -> bool {
+        self.has_group("{files-0}")
+    }
     pub fn as_internal_pattern(&self) -> Option<&str> {
         match self {
             Self::String(s) => {
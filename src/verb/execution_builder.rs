@@ -1,8 +1,9 @@
 use {
     super::*,
     crate::{
-        app::{Selection, SelInfo, SelectionType},
+        app::{LineNumber, Selection, SelInfo, SelectionType},
         path,
+        pattern::Pattern,
     },
     ahash::AHashMap,
     regex::Captures,
@@ -21,6 +22,17 @@ pub struct ExecutionStringBuilder<'b> {
 
     /// parsed arguments
     invocation_values: Option<AHashMap<String, String>>,
+
+    /// the paths replacing {files} and {files-0}: the staged
+    /// ones, or the selected one when the stage is empty
+    files: Vec<PathBuf>,
+
+    /// the paths replacing {results}: the current matches
+    results: Vec<PathBuf>,
+
+    /// the pattern of the current search when it's on file contents,
+    /// giving {line} when no line is selected
+    content_pattern: Option<&'b Pattern>,
 }
 
 impl<'b> ExecutionStringBuilder<'b> {
@@ -31,6 +43,9 @@ impl<'b> ExecutionStringBuilder<'b> {
             sel_info,
             other_file: None,
             invocation_values: None,
+            files: Vec::new(),
+            results: Vec::new(),
+            content_pattern: None,
         }
     }
     pub fn from_invocation(
@@ -47,8 +62,79 @@ impl<'b> ExecutionStringBuilder<'b> {
             sel_info,
             other_file: other_file.as_ref(),
             invocation_values,
+            files: Vec::new(),
+            results: Vec::new(),
+            content_pattern: None,
         }
     }
+    pub fn with_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.files = files;
+        self
+    }
+    pub fn with_results(mut self, results: Vec<PathBuf>) -> Self {
+        self.results = results;
+        self
+    }
+    pub fn with_content_pattern(mut self, content_pattern: Option<&'b Pattern>) -> Self {
+        self.content_pattern = content_pattern;
+        self
+    }
+    pub fn has_files(&self) -> bool {
+        !self.files.is_empty()
+    }
+    pub fn has_results(&self) -> bool {
+        !self.results.is_empty()
+    }
+    /// return what must be written on the stdin of the launched
+    /// process: the NUL terminated paths when the pattern has {files-0}
+    pub fn stdin_input(&self, exec_pattern: &ExecPattern) -> Option<Vec<u8>> {
+        if !exec_pattern.has_stdin_group() {
+            return None;
+        }
+        let mut input = Vec::new();
+        for path in &self.files {
+            input.extend_from_slice(path_to_string(path).as_bytes());
+            input.push(0);
+        }
+        Some(input)
+    }
+    /// replace the groups standing for several paths.
+    ///
+    /// A token made only of {files} or {results} becomes as many tokens
+    /// as there are paths, while in a bigger token (eg a command given
+    /// to `sh -c`) the paths are quoted and separated with spaces.
+    /// {files-0} is removed as those paths are written on stdin.
+    fn expand_multi_path_groups(&self, exec_pattern: &ExecPattern) -> ExecPattern {
+        if !exec_pattern.has_multi_path_group() {
+            return exec_pattern.clone();
+        }
+        // the groups whose paths are unknown (eg when building the
+        // description of the verb) are left as is
+        let mut tokens = Vec::new();
+        for token in exec_pattern.clone().into_array() {
+            match token.as_str() {
+                "{files-0}" if self.has_files() => {}
+                "{files}" if self.has_files() => {
+                    tokens.extend(self.files.iter().map(path_to_string));
+                }
+                "{results}" if self.has_results() => {
+                    tokens.extend(self.results.iter().map(path_to_string));
+                }
+                _ => {
+                    let token = GROUP.replace_all(&token, |ec: &Captures<'_>| {
+                        match ec.get(1).unwrap().as_str() {
+                            "files" if self.has_files() => join_quoted(&self.files),
+                            "results" if self.has_results() => join_quoted(&self.results),
+                            "files-0" if self.has_files() => String::new(),
+                            _ => ec[0].to_string(),
+                        }
+                    });
+                    tokens.push(token.to_string());
+                }
+            }
+        }
+        ExecPattern::Array(tokens)
+    }
     fn get_raw_capture_replacement(&self, ec: &Captures<'_>) -> Option<String> {
         match self.sel_info {
             SelInfo::None => self.get_raw_sel_capture_replacement(ec, None),
@@ -74,6 +160,19 @@ impl<'b> ExecutionStringBuilder<'b> {
             }
         }
     }
+    /// return the selected line or, when the file was found by its
+    /// content, the line of the first match (searched only now, as
+    /// few verbs need it)
+    fn line_of(&self, sel: Selection<'_>) -> LineNumber {
+        if sel.line == 0 && sel.stype == SelectionType::File {
+            if let Some(content_match) = self.content_pattern
+                .and_then(|pattern| pattern.search_content(sel.path, 1))
+            {
+                return content_match.line;
+            }
+        }
+        sel.line
+    }
     fn get_raw_sel_capture_replacement(
         &self,
         ec: &Captures<'_>,
@@ -81,7 +180,7 @@ impl<'b> ExecutionStringBuilder<'b> {
     ) -> Option<String> {
         let name = ec.get(1).unwrap().as_str();
         match name {
-            "line" => sel.map(|s| s.line.to_string()),
+            "line" => sel.map(|s| self.line_of(s).to_string()),
             "file" => sel.map(|s| s.path).map(path_to_string),
             "directory" => sel.map(|s| path::closest_dir(s.path)).map(path_to_string),
             "parent" => sel.and_then(|s| s.path.parent()).map(path_to_string),
@@ -137,7 +236,7 @@ impl<'b> ExecutionStringBuilder<'b> {
         &self,
         exec_pattern: &ExecPattern,
     ) -> String {
-        exec_pattern
+        self.expand_multi_path_groups(exec_pattern)
             .apply(&|s| {
                 GROUP.replace_all(
                     s,
@@ -155,7 +254,7 @@ impl<'b> ExecutionStringBuilder<'b> {
         exec_pattern: &ExecPattern,
         sel: Option<Selection<'_>>,
     ) -> String {
-        exec_pattern
+        self.expand_multi_path_groups(exec_pattern)
             .apply(&|s| {
                 GROUP.replace_all(
                     s,
@@ -171,7 +270,7 @@ impl<'b> ExecutionStringBuilder<'b> {
         &self,
         exec_pattern: &ExecPattern,
     ) -> Vec<String> {
-        exec_pattern
+        self.expand_multi_path_groups(exec_pattern)
             .apply(&|s| {
                 GROUP.replace_all(
                     s,
@@ -188,7 +287,7 @@ impl<'b> ExecutionStringBuilder<'b> {
         exec_pattern: &ExecPattern,
         sel: Option<Selection<'_>>,
     ) -> Vec<String> {
-        exec_pattern
+        self.expand_multi_path_groups(exec_pattern)
             .apply(&|s| {
                 GROUP.replace_all(
                     s,
@@ -272,8 +371,106 @@ mod execution_builder_test {
             vec!["xterm", "-e", "kak /path/to/file"],
         );
     }
+
+    #[test]
+    fn test_build_multi_path_execution() {
+        let builder = ExecutionStringBuilder::from_sel_info(SelInfo::None)
+            .with_files(vec![PathBuf::from("/a/b"), PathBuf::from("/a/it's c")])
+            .with_results(vec![PathBuf::from("/r")]);
+        assert_eq!(
+            builder.exec_token(&ExecPattern::from_string("tar czf out.tgz {files}")),
+            vo!["tar", "czf", "out.tgz", "/a/b", "/a/it's c"],
+        );
+        assert_eq!(
+            builder.exec_token(&ExecPattern::from_string("sh -c \"wc -l {files} {results}\"")),
+            vo!["sh", "-c", "wc -l /a/b '/a/it'\\''s c' /r"],
+        );
+        let pattern = ExecPattern::from_string("xargs -0 {files-0} ls");
+        assert_eq!(builder.exec_token(&pattern), vo!["xargs", "-0", "ls"]);
+        assert_eq!(
+            builder.stdin_input(&pattern),
+            Some(b"/a/b\0/a/it's c\0".to_vec()),
+        );
+    }
+
+    #[test]
+    fn test_line_of_content_match() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("file.txt");
+        std::fs::write(&path, "first line\nsecond line\nthird broot line\n").unwrap();
+        let searcher = crate::search::Searcher::new().unwrap();
+        let pattern = searcher.pattern("c/broot").unwrap();
+        let sel = Selection {
+            path: &path,
+            line: 0,
+            stype: SelectionType::File,
+            is_exe: false,
+        };
+        let exec_pattern = ExecPattern::from_string("vi +{line} {file}");
+        let builder = ExecutionStringBuilder::from_sel_info(SelInfo::One(sel));
+        assert_eq!(builder.exec_token(&exec_pattern)[1], "+0");
+        // with a content search, {line} is the line of the first match
+        let builder = ExecutionStringBuilder::from_sel_info(SelInfo::One(sel))
+            .with_content_pattern(Some(&pattern.pattern));
+        assert_eq!(builder.exec_token(&exec_pattern)[1], "+3");
+    }
+
+    #[test]
+    fn test_yank_paste_round_trip() {
+        let parser = InvocationParser::new("copy {newpath}").unwrap();
+        for path in &["/tmp/a b", "/tmp/it's", "/tmp/plain"] {
+            // what register_yank stores, then register_paste types
+            let yanked = join_quoted(&[PathBuf::from(path)]);
+            let invocation = VerbInvocation::from(format!("copy {}", yanked).as_str());
+            let values = parser.parse(invocation.args.as_ref().unwrap()).unwrap();
+            assert_eq!(values["newpath"], *path);
+        }
+        assert_eq!(shell_unquote("'/a b' '/c'"), None);
+    }
 }
 
 fn path_to_string<P: AsRef<Path>>(path: P) -> String {
     path.as_ref().to_string_lossy().to_string()
 }
+
+/// quote the string, if necessary, so that a POSIX shell reads
+/// it as one word
+pub fn shell_quote(s: &str) -> String {
+    let safe = !s.is_empty() && s.chars().all(|c| {
+        c.is_alphanumeric() || matches!(c, '/' | '.' | '-' | '_' | ',' | ':' | '@' | '%' | '+' | '=')
+    });
+    if safe {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+/// return the word, unquoted, if the string is exactly one shell
+/// word (as written by shell_quote)
+pub fn shell_unquote(s: &str) -> Option<String> {
+    let mut word = String::new();
+    let mut chars = s.chars();
+    let mut in_quotes = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => in_quotes = !in_quotes,
+            '\\' if !in_quotes => word.push(chars.next()?),
+            c if c.is_whitespace() && !in_quotes => return None,
+            c => word.push(c),
+        }
+    }
+    if in_quotes {
+        None
+    } else {
+        Some(word)
+    }
+}
+
+/// quote the paths and join them with spaces
+pub fn join_quoted(paths: &[PathBuf]) -> String {
+    paths.iter()
+        .map(|path| shell_quote(&path.to_string_lossy()))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
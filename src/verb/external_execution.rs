@@ -29,6 +29,9 @@ pub struct ExternalExecution {
     /// * {other-panel-file}
     /// * {other-panel-directory}
     /// * {other-panel-parent}
+    /// * {files} (the staged paths, or the selected one)
+    /// * {files-0} (same paths, but NUL separated on stdin)
+    /// * {results} (the paths of all current matches)
     pub exec_pattern: ExecPattern,
 
     /// how the external process must be launched
@@ -67,6 +70,9 @@ impl ExternalExecution {
         builder: ExecutionStringBuilder<'_>,
         con: &AppContext,
     ) -> Result<CmdResult, ProgramError> {
+        if let Some(error) = self.missing_paths_error(&builder) {
+            return Ok(CmdResult::error(error));
+        }
         match self.exec_mode {
             ExternalExecutionMode::FromParentShell => self.cmd_result_exec_from_parent_shell(
                 builder,
@@ -84,6 +90,53 @@ impl ExternalExecution {
         }
     }
 
+    /// the commands which would be executed, one per staged path
+    /// when the verb must be run on each of them
+    pub fn dry_run_commands(
+        &self,
+        builder: &ExecutionStringBuilder<'_>,
+    ) -> Vec<String> {
+        match &builder.sel_info {
+            SelInfo::More(stage) if self.runs_per_path(builder) => stage.paths().iter()
+                .map(|path| {
+                    let sel = Selection {
+                        path,
+                        line: 0,
+                        stype: SelectionType::from(path),
+                        is_exe: false,
+                    };
+                    builder.sel_shell_exec_string(&self.exec_pattern, Some(sel))
+                })
+                .collect(),
+            _ => vec![builder.shell_exec_string(&self.exec_pattern)],
+        }
+    }
+
+    /// return an error message when a group standing for several
+    /// paths would be replaced by nothing
+    fn missing_paths_error(
+        &self,
+        builder: &ExecutionStringBuilder<'_>,
+    ) -> Option<&'static str> {
+        let needs_files = self.exec_pattern.has_group("{files}")
+            || self.exec_pattern.has_stdin_group();
+        let needs_results = self.exec_pattern.has_group("{results}");
+        if needs_files && !builder.has_files() {
+            Some("no staged or selected path")
+        } else if needs_results && !builder.has_results() {
+            Some("no search result")
+        } else {
+            None
+        }
+    }
+
+    /// tell whether the verb must be executed once per selected path
+    /// (it's executed only once when the pattern has a group standing
+    /// for several paths, like {files})
+    fn runs_per_path(&self, builder: &ExecutionStringBuilder<'_>) -> bool {
+        builder.sel_info.count_paths() > 1 && !self.exec_pattern.has_multi_path_group()
+    }
+
     /// build the cmd result as an executable which will be called
     /// from the parent shell (meaning broot must quit)
     fn cmd_result_exec_from_parent_shell(
@@ -91,11 +144,16 @@ impl ExternalExecution {
         builder: ExecutionStringBuilder<'_>,
         con: &AppContext,
     ) -> Result<CmdResult, ProgramError> {
-        if builder.sel_info.count_paths() > 1 {
+        if self.runs_per_path(&builder) {
             return Ok(CmdResult::error(
                 "only verbs returning to broot on end can be executed on a multi-selection"
             ));
         }
+        if self.exec_pattern.has_stdin_group() {
+            return Ok(CmdResult::error(
+                "{files-0} can't be used in verbs executed from the parent shell"
+            ));
+        }
         if let Some(ref export_path) = con.launch_args.cmd_export_path {
             // Broot was probably launched as br.
             // the whole command is exported in the passed file
@@ -127,11 +185,12 @@ impl ExternalExecution {
         builder: ExecutionStringBuilder<'_>,
         con: &AppContext,
     ) -> Result<CmdResult, ProgramError> {
-        if builder.sel_info.count_paths() > 1 {
+        if self.runs_per_path(&builder) {
             return Ok(CmdResult::error(
                 "only verbs returning to broot on end can be executed on a multi-selection"
             ));
         }
+        let input = builder.stdin_input(&self.exec_pattern);
         let launchable = Launchable::program(
             builder.exec_token(&self.exec_pattern),
             builder.sel_info
@@ -139,7 +198,7 @@ impl ExternalExecution {
                 .filter(|_| self.set_working_dir)
                 .map(|sel| path::closest_dir(sel.path)),
             con,
-        )?;
+        )?.with_input(input);
         Ok(CmdResult::from(launchable))
     }
 
@@ -152,23 +211,7 @@ impl ExternalExecution {
         con: &AppContext,
     ) -> Result<CmdResult, ProgramError> {
         match &builder.sel_info {
-            SelInfo::None | SelInfo::One(_) => {
-                // zero or one selection -> only one execution
-                let launchable = Launchable::program(
-                    builder.exec_token(&self.exec_pattern),
-                    builder.sel_info
-                        .one_sel()
-                        .filter(|_| self.set_working_dir)
-                        .map(|sel| path::closest_dir(sel.path)),
-                    con,
-                )?;
-                info!("Executing not leaving, launchable {:?}", launchable);
-                if let Err(e) = launchable.execute(Some(w)) {
-                    warn!("launchable failed : {:?}", e);
-                    return Ok(CmdResult::error(e.to_string()));
-                }
-            }
-            SelInfo::More(stage) => {
+            SelInfo::More(stage) if self.runs_per_path(&builder) => {
                 // multiselection -> we must execute on all paths
                 let sels = stage.paths().iter()
                     .map(|path| Selection {
@@ -193,6 +236,24 @@ impl ExternalExecution {
                     }
                 }
             }
+            _ => {
+                // zero or one selection, or a pattern taking all
+                // the paths at once -> only one execution
+                let input = builder.stdin_input(&self.exec_pattern);
+                let launchable = Launchable::program(
+                    builder.exec_token(&self.exec_pattern),
+                    builder.sel_info
+                        .one_sel()
+                        .filter(|_| self.set_working_dir)
+                        .map(|sel| path::closest_dir(sel.path)),
+                    con,
+                )?.with_input(input);
+                info!("Executing not leaving, launchable {:?}", launchable);
+                if let Err(e) = launchable.execute(Some(w)) {
+                    warn!("launchable failed : {:?}", e);
+                    return Ok(CmdResult::error(e.to_string()));
+                }
+            }
         }
         Ok(CmdResult::RefreshState { clear_cache: true })
     }
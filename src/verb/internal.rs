@@ -54,10 +54,19 @@ macro_rules! Internals {
 //  name: "description" needs_a_path
 Internals! {
     back: "revert to the previous state (mapped to *esc*)" false,
+    build_index: "build or update the path index of the current root, for instant searches" false,
     close_panel_ok: "close the panel, validating the selected path" false,
     close_panel_cancel: "close the panel, not using the selected path" false,
+    cd_now: "quit broot and cd to the selected directory, or to the parent of the selected file" true,
+    clean: "delete the staged directories of the :cleanable list, after confirmation" false,
+    cleanable: "list the build artifacts and caches under the root, biggest first, to delete them" false,
     copy_line: "copy selected line (in tree or preview)" true,
     copy_path: "copy path to system clipboard" true,
+    copy_relative_path: "copy path relative to the tree root to system clipboard" true,
+    copy_name: "copy file name to system clipboard" true,
+    copy_file_content: "copy the content of a small text file to system clipboard" true,
+    date_kind: "choose the date which is shown and used for sorting (modified, changed or created), eg `:date_kind created`" false,
+    expand_unlisted: "list in place the unlisted children of the selected directory (or of the directory of the selection or of the selected pruning line)" false,
     filesystems: "list mounted filesystems" false,
     focus: "display the directory (mapped to *enter*)" true,
     help: "display broot's help" false,
@@ -72,38 +81,61 @@ Internals! {
     input_go_word_left: "move the cursor one word to the left" false,
     input_go_word_right: "move the cursor one word to the right" false,
     input_paste: "paste the clipboard content into the input" false,
+    keys: "show the key bindings, in the verbs table of the help" false,
+    layout: "set the relative widths of the panels, eg `:layout 70/30`" false,
     line_down: "move one line down" false,
     line_up: "move one line up" false,
     line_down_no_cycle: "move one line down" false,
     line_up_no_cycle: "move one line up" false,
+    load_children: "load all the children of the selected directory, even past the configured max" false,
     open_stay: "open file or directory according to OS (stay in broot)" true,
     open_stay_filter: "display the directory, keeping the current pattern" true,
     open_leave: "open file or directory according to OS (quit broot)" true,
     mode_input: "enter the input mode" false,
     mode_command: "enter the command mode" false,
+    new_dir: "create a new directory" true,
+    new_file: "create a new file, maybe from a template" true,
     next_match: "select the next match" false,
     next_same_depth: "select the next file at the same depth" false,
     no_sort: "don't sort" false,
     page_down: "scroll one page down" false,
     page_up: "scroll one page up" false,
+    palette: "open the list of the verbs and recent commands, to search one and execute it" false,
     parent: "move to the parent directory" false,
     panel_left: "focus panel on left" false,
     panel_right: "focus panel on right" false,
+    panel_grow: "make the current panel wider" false,
+    panel_shrink: "make the current panel narrower" false,
+    panel_zoom: "give the whole width to the current panel, or restore the other panels" false,
     previous_match: "select the previous match" false,
     previous_same_depth: "select the previous file at the same depth" false,
     open_preview: "open the preview panel" true,
     close_preview: "close the preview panel" false,
     toggle_preview: "open/close the preview panel" false,
+    preview_top: "go to the start of the previewed file" false,
+    preview_bottom: "go to the end of the previewed file" false,
+    preview_goto: "go to a line of the previewed file, eg `:preview_goto 120`" false,
     preview_image: "preview the selection as image" true,
     preview_text: "preview the selection as text" true,
     preview_binary: "preview the selection as binary" true,
+    preview_mode: "preview the selection in a mode (image, text, unstyled or hex), or in its default one" true,
     print_path: "print path and leaves broot" true,
     print_relative_path: "print relative path and leaves broot" true,
     print_tree: "print tree and leaves broot" true,
+    profile: "apply a set of flags defined in the configuration, eg `:profile review`" false,
     start_end_panel: "either open or close an additional panel" true,
     quit: "quit Broot" false,
+    rebind: "bind a key to a verb and save it in the configuration, eg `:rebind back ctrl-b`" false,
     refresh: "refresh tree and clear size cache" false,
+    reload_config: "read again the configuration files (done automatically when they change)" false,
     //restore_pattern: "restore a pattern which was just removed" false,
+    register_paste: "paste the content of a register in the input, eg `:register_paste a`" false,
+    register_yank: "put the selected path in a register, eg `:register_yank a`" true,
+    repeat: "repeat the last verb which ran a command or modified files" false,
+    scroll_down: "scroll the view down, keeping the selection in view, eg `:scroll_down 3`" false,
+    scroll_up: "scroll the view up, keeping the selection in view, eg `:scroll_up 3`" false,
+    scroll_left: "scroll the names of the tree to the left, eg `:scroll_left 20`" false,
+    scroll_right: "scroll the names of the tree to the right, revealing their end, eg `:scroll_right 20`" false,
     select_first: "select the first item" false,
     select_last: "select the last item" false,
     sort_by_count: "sort by count" false,
@@ -111,44 +143,74 @@ Internals! {
     sort_by_size: "sort by size" false,
     clear_stage: "empty the staging area" false,
     stage: "add selection to staging area" true,
+    stage_all: "add all the listed paths (eg the search results) to the staging area" false,
     unstage: "remove selection from staging area" true,
     open_staging_area: "open the staging area" false,
     close_staging_area: "close the staging area panel" false,
     toggle_staging_area: "open/close the staging area panel" false,
     toggle_stage: "add or remove selection to staging area" true,
+    tag: "attach a tag to the selection" true,
+    untag: "remove a tag (or all tags) from the selection" true,
     toggle_counts: "toggle showing number of files in directories" false,
     toggle_dates: "toggle showing last modified dates" false,
+    toggle_expand: "collapse the selected directory, or list all its children" true,
     toggle_files: "toggle showing files (or just folders)" false,
     toggle_git_ignore: "toggle use of .gitignore" false,
     toggle_git_file_info: "toggle display of git file information" false,
     toggle_git_status: "toggle showing only files relevant for git status" false,
     toggle_root_fs: "toggle showing filesystem info on top" false,
     toggle_hidden: "toggle showing hidden files" false,
+    toggle_ignored_sizes: "toggle showing which part of the sizes of directories is gitignored" false,
     toggle_perm: "toggle showing file permissions" false,
     toggle_sizes: "toggle showing sizes" false,
+    toggle_skin: "switch between the light and the dark skins" false,
     toggle_trim_root: "toggle removing nodes at first level too" false,
     total_search: "search again but on all children" false,
     up_tree: "focus the parent of the current root" true,
+    why_ignored: "tell which gitignore rule excludes the selected path" true,
+    worktrees: "list the worktrees of the git repository" true,
 }
 
 impl Internal {
     pub fn invocation_pattern(self) -> &'static str {
         match self {
             Internal::focus => r"focus (?P<path>.*)?",
+            Internal::date_kind => r"date_kind (?P<kind>\w*)?",
             Internal::line_down => r"line_down (?P<count>\d*)?",
             Internal::line_up => r"line_up (?P<count>\d*)?",
             Internal::line_down_no_cycle => r"line_down_no_cycle (?P<count>\d*)?",
             Internal::line_up_no_cycle => r"line_up_no_cycle (?P<count>\d*)?",
+            Internal::scroll_down => r"scroll_down (?P<count>\d*)?",
+            Internal::scroll_up => r"scroll_up (?P<count>\d*)?",
+            Internal::scroll_left => r"scroll_left (?P<count>\d*)?",
+            Internal::scroll_right => r"scroll_right (?P<count>\d*)?",
+            Internal::new_dir => r"new_dir (?P<subpath>.*)?",
+            Internal::new_file => r"new_file (?P<subpath>.*)?",
+            Internal::preview_mode => r"preview_mode (?P<mode>\w*)?",
+            Internal::profile => r"profile (?P<name>\S*)?",
+            Internal::tag => r"tag (?P<tag>.*)?",
+            Internal::untag => r"untag (?P<tag>.*)?",
             _ => self.name(),
         }
     }
     pub fn exec_pattern(self) -> &'static str {
         match self {
             Internal::focus => r"focus {path}",
+            Internal::date_kind => r"date_kind {kind}",
             Internal::line_down => r"line_down {count}",
             Internal::line_up => r"line_up {count}",
             Internal::line_down_no_cycle => r"line_down_no_cycle {count}",
             Internal::line_up_no_cycle => r"line_up_no_cycle {count}",
+            Internal::scroll_down => r"scroll_down {count}",
+            Internal::scroll_up => r"scroll_up {count}",
+            Internal::scroll_left => r"scroll_left {count}",
+            Internal::scroll_right => r"scroll_right {count}",
+            Internal::new_dir => r"new_dir {subpath}",
+            Internal::new_file => r"new_file {subpath}",
+            Internal::preview_mode => r"preview_mode {mode}",
+            Internal::profile => r"profile {name}",
+            Internal::tag => r"tag {tag}",
+            Internal::untag => r"untag {tag}",
             _ => self.name(),
         }
     }
@@ -0,0 +1,69 @@
+//! utility functions to help handle the `:new_file` and `:new_dir` internals
+
+use {
+    crate::{
+        app::AppContext,
+        path::{self, PathAnchor},
+    },
+    std::{
+        fs,
+        io,
+        path::{Path, PathBuf},
+    },
+};
+
+/// return the template to use for a new file, if any: the file of the
+/// templates directory whose name is the extension of the new file
+pub fn template_for(path: &Path, con: &AppContext) -> Option<PathBuf> {
+    let templates_dir = con.templates_dir.as_ref()?;
+    let extension = path.extension()?.to_str()?;
+    let template = templates_dir.join(extension);
+    if template.is_file() {
+        Some(template)
+    } else {
+        None
+    }
+}
+
+/// create a file, failing if it already exists, with the content
+/// of the relevant template if there's one.
+///
+/// The name is interpreted relative to the selected directory
+/// (or the parent of the selected file).
+pub fn create_file(
+    selected_path: &Path,
+    name: &str,
+    con: &AppContext,
+) -> io::Result<PathBuf> {
+    let path = path::path_from(selected_path, PathAnchor::Unspecified, name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = match template_for(&path, con) {
+        Some(template) => {
+            debug!("using template {:?}", &template);
+            fs::read(template)?
+        }
+        None => Vec::new(),
+    };
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)?;
+    io::Write::write_all(&mut file, &content)?;
+    Ok(path)
+}
+
+/// create a directory (and its missing parents), failing if it
+/// already exists.
+pub fn create_dir(
+    selected_path: &Path,
+    name: &str,
+) -> io::Result<PathBuf> {
+    let path = path::path_from(selected_path, PathAnchor::Unspecified, name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::create_dir(&path)?;
+    Ok(path)
+}
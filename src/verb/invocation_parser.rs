@@ -115,7 +115,14 @@ impl InvocationParser {
                 if let Some(input_cap) = r.captures(&args) {
                     for name in r.capture_names().flatten() {
                         if let Some(c) = input_cap.name(name) {
-                            map.insert(name.to_string(), c.as_str().to_string());
+                            // a quoted value (eg a pasted path) is unquoted
+                            let value = c.as_str();
+                            let value = if value.starts_with('\'') {
+                                shell_unquote(value).unwrap_or_else(|| value.to_string())
+                            } else {
+                                value.to_string()
+                            };
+                            map.insert(name.to_string(), value);
                         }
                     }
                 }
@@ -1,4 +1,5 @@
 mod builtin;
+mod confirmation;
 mod exec_pattern;
 mod execution_builder;
 mod external_execution;
@@ -6,7 +7,9 @@ mod external_execution_mode;
 mod internal;
 mod internal_execution;
 pub mod internal_focus;
+pub mod internal_new;
 mod invocation_parser;
+mod opener;
 mod sequence_execution;
 mod verb;
 mod verb_description;
@@ -15,13 +18,15 @@ mod verb_invocation;
 mod verb_store;
 
 pub use {
+    confirmation::Confirmation,
     exec_pattern::*,
-    execution_builder::ExecutionStringBuilder,
+    execution_builder::{join_quoted, shell_quote, shell_unquote, ExecutionStringBuilder},
     external_execution::ExternalExecution,
     external_execution_mode::ExternalExecutionMode,
     internal::Internal,
     internal_execution::InternalExecution,
     invocation_parser::InvocationParser,
+    opener::Opener,
     sequence_execution::SequenceExecution,
     verb::Verb,
     verb_description::VerbDescription,
@@ -41,7 +46,7 @@ pub fn str_has_selection_group(s: &str) -> bool {
     for group in GROUP.find_iter(s) {
         if matches!(
             group.as_str(),
-            "{file}" | "{parent}" | "{directory}"
+            "{file}" | "{parent}" | "{directory}" | "{files}" | "{files-0}"
         ){
                 return true;
         }
@@ -57,3 +62,13 @@ pub fn str_has_other_panel_group(s: &str) -> bool {
     false
 }
 
+/// tell whether the string contains a group standing for
+/// several paths ({files}, {files-0} or {results})
+pub fn str_has_multi_path_group(s: &str) -> bool {
+    for group in GROUP.find_iter(s) {
+        if matches!(group.as_str(), "{files}" | "{files-0}" | "{results}") {
+            return true;
+        }
+    }
+    false
+}
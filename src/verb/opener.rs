@@ -0,0 +1,204 @@
+use {
+    super::*,
+    crate::{
+        app::*,
+        content_search,
+        display::W,
+        errors::ProgramError,
+        launchable::Launchable,
+    },
+    phf::{phf_map, Map},
+    std::{
+        path::Path,
+        process::{Command, Stdio},
+        thread,
+    },
+};
+
+/// mime types of common extensions (in lowercase)
+static MIME_TYPES: Map<&'static str, &'static str> = phf_map! {
+    "avi" => "video/x-msvideo",
+    "bmp" => "image/bmp",
+    "css" => "text/css",
+    "csv" => "text/csv",
+    "doc" => "application/msword",
+    "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "epub" => "application/epub+zip",
+    "flac" => "audio/flac",
+    "gif" => "image/gif",
+    "gz" => "application/gzip",
+    "htm" => "text/html",
+    "html" => "text/html",
+    "ico" => "image/vnd.microsoft.icon",
+    "jpeg" => "image/jpeg",
+    "jpg" => "image/jpeg",
+    "js" => "text/javascript",
+    "json" => "application/json",
+    "md" => "text/markdown",
+    "mkv" => "video/x-matroska",
+    "mov" => "video/quicktime",
+    "mp3" => "audio/mpeg",
+    "mp4" => "video/mp4",
+    "odt" => "application/vnd.oasis.opendocument.text",
+    "ods" => "application/vnd.oasis.opendocument.spreadsheet",
+    "ogg" => "audio/ogg",
+    "pdf" => "application/pdf",
+    "png" => "image/png",
+    "svg" => "image/svg+xml",
+    "tar" => "application/x-tar",
+    "tif" => "image/tiff",
+    "tiff" => "image/tiff",
+    "txt" => "text/plain",
+    "wav" => "audio/wav",
+    "webm" => "video/webm",
+    "webp" => "image/webp",
+    "xls" => "application/vnd.ms-excel",
+    "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    "xml" => "application/xml",
+    "zip" => "application/zip",
+};
+
+/// guess the mime type of a file from its extension. Files with
+/// an unknown extension but a textual content are "text/plain"
+fn mime_type(path: &Path) -> Option<&'static str> {
+    let known = path.extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(|ext| MIME_TYPES.get(ext.to_lowercase().as_str()));
+    match known {
+        Some(mime) => Some(mime),
+        None if !content_search::is_path_binary(path) => Some("text/plain"),
+        None => None,
+    }
+}
+
+/// a rule, coming from the `openers` configuration section, telling
+/// which program must open files, based on their extension or mime type
+#[derive(Debug, Clone)]
+pub struct Opener {
+
+    /// the extensions of the files this opener applies to, in lowercase
+    pub extensions: Vec<String>,
+
+    /// a glob on the mime type, eg `image/*`
+    pub mime: Option<glob::Pattern>,
+
+    /// the command to execute, where `{file}` and the other
+    /// selection groups are replaced
+    pub exec_pattern: ExecPattern,
+
+    /// whether the program runs in the terminal (broot waits for
+    /// its end) rather than in its own window
+    pub terminal: bool,
+}
+
+impl Opener {
+    /// return the first opener applying to the file, if any (when
+    /// there's none, the file is opened by the OS)
+    pub fn find<'o>(openers: &'o [Opener], path: &Path) -> Option<&'o Opener> {
+        openers.iter().find(|opener| opener.applies_to(path))
+    }
+
+    /// tell whether this opener is the one to use for the file
+    pub fn applies_to(&self, path: &Path) -> bool {
+        let extension = path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+        if let Some(extension) = extension {
+            if self.extensions.contains(&extension) {
+                return true;
+            }
+        }
+        match (&self.mime, mime_type(path)) {
+            (Some(pattern), Some(mime)) => pattern.matches(mime),
+            _ => false,
+        }
+    }
+
+    /// open the file, either by running the program in the terminal
+    /// and waiting for its end, or by launching it in the background
+    pub fn open(
+        &self,
+        w: &mut W,
+        path: &Path,
+        con: &AppContext,
+    ) -> Result<CmdResult, ProgramError> {
+        let sel = Selection {
+            path,
+            line: 0,
+            stype: SelectionType::File,
+            is_exe: false,
+        };
+        let builder = ExecutionStringBuilder::from_sel_info(SelInfo::One(sel))
+            .with_files(vec![path.to_path_buf()]);
+        let launchable = Launchable::program(builder.exec_token(&self.exec_pattern), None, con)?;
+        if self.terminal {
+            info!("opening {:?} with {:?}", path, launchable);
+            if let Err(e) = launchable.execute(Some(w)) {
+                warn!("opener failed : {:?}", e);
+                return Ok(CmdResult::error(e.to_string()));
+            }
+            return Ok(CmdResult::RefreshState { clear_cache: true });
+        }
+        if let Launchable::Program { exe, args, .. } = launchable {
+            info!("opening {:?} in background with {:?} {:?}", path, exe, args);
+            let child = Command::new(&exe)
+                .args(args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|source| ProgramError::LaunchError {
+                    program: exe,
+                    source,
+                })?;
+            // the child is waited for so that it doesn't stay a zombie
+            thread::spawn(move || child.wait_with_output());
+        }
+        Ok(CmdResult::Keep)
+    }
+}
+
+#[cfg(test)]
+mod opener_test {
+
+    use {
+        super::*,
+        std::fs,
+    };
+
+    fn opener(extensions: &[&str], mime: Option<&str>, execution: &str) -> Opener {
+        Opener {
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+            mime: mime.map(|mime| glob::Pattern::new(mime).unwrap()),
+            exec_pattern: ExecPattern::from_string(execution),
+            terminal: false,
+        }
+    }
+
+    #[test]
+    fn check_opener_matching() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let notes = temp_dir.path().join("notes");
+        fs::write(&notes, "some text without extension").unwrap();
+        let openers = vec![
+            opener(&["md", "txt"], None, "editor {file}"),
+            opener(&[], Some("image/*"), "viewer {file}"),
+            opener(&[], Some("text/*"), "pager {file}"),
+        ];
+        let exec = |path: &Path| {
+            Opener::find(&openers, path).map(|o| o.exec_pattern.clone().into_array()[0].clone())
+        };
+        // by extension, whatever its case, before the mime types
+        assert_eq!(exec(Path::new("README.md")).as_deref(), Some("editor"));
+        assert_eq!(exec(Path::new("a/b/TODO.TXT")).as_deref(), Some("editor"));
+        // by mime type, guessed from the extension
+        assert_eq!(exec(Path::new("photo.JPG")).as_deref(), Some("viewer"));
+        assert_eq!(exec(Path::new("logo.svg")).as_deref(), Some("viewer"));
+        assert_eq!(exec(Path::new("style.css")).as_deref(), Some("pager"));
+        // or from the content
+        assert_eq!(exec(&notes).as_deref(), Some("pager"));
+        // no opener: the file is opened by the OS
+        assert_eq!(exec(Path::new("archive.zip")), None);
+        assert_eq!(exec(&temp_dir.path().join("missing")), None);
+    }
+}
@@ -32,6 +32,9 @@ pub struct Verb {
     /// key shortcuts
     pub keys: Vec<KeyEvent>,
 
+    /// sequences of at least two keys (eg "g g") triggering the verb
+    pub chords: Vec<Vec<KeyEvent>>,
+
     /// description of the optional keyboard key(s) triggering that verb
     pub keys_desc: String,
 
@@ -54,6 +57,13 @@ pub struct Verb {
     /// whether we need to have a secondary panel for execution
     /// (which is the case when the execution pattern has {other-panel-file})
     pub needs_another_panel: bool,
+
+    /// whether a double-click on a line of the selection type
+    /// triggers the verb
+    pub on_double_click: bool,
+
+    /// whether the user must confirm before the verb is executed
+    pub confirmation: Confirmation,
 }
 
 impl Verb {
@@ -85,6 +95,7 @@ impl Verb {
         Ok(Self {
             names,
             keys: Vec::new(),
+            chords: Vec::new(),
             keys_desc: "".to_string(),
             invocation_parser,
             execution,
@@ -92,6 +103,8 @@ impl Verb {
             selection_condition: SelectionType::Any,
             needs_selection,
             needs_another_panel,
+            on_double_click: false,
+            confirmation: Confirmation::Never,
         })
     }
     fn update_key_desc(&mut self) {
@@ -99,6 +112,7 @@ impl Verb {
             .keys
             .iter()
             .map(|&k| keys::key_event_desc(k))
+            .chain(self.chords.iter().map(|chord| keys::key_sequence_desc(chord)))
             .collect::<Vec<String>>() // no way to join an iterator today ?
             .join(", ");
     }
@@ -113,6 +127,36 @@ impl Verb {
         }
         self.update_key_desc();
     }
+    pub fn add_chord(&mut self, chord: Vec<KeyEvent>) {
+        self.chords.push(chord);
+        self.update_key_desc();
+    }
+    /// add a key or a chord, depending on the length of the sequence
+    pub fn add_key_sequence(&mut self, mut sequence: Vec<KeyEvent>) {
+        if sequence.len() == 1 {
+            self.add_keys(vec![sequence.remove(0)]);
+        } else {
+            self.add_chord(sequence);
+        }
+    }
+    /// remove the key or chord, return whether the verb had it
+    pub fn remove_key_sequence(&mut self, sequence: &[KeyEvent]) -> bool {
+        let len = self.keys.len() + self.chords.len();
+        self.keys.retain(|&key| sequence != [key]);
+        self.chords.retain(|chord| chord != sequence);
+        if len == self.keys.len() + self.chords.len() {
+            return false;
+        }
+        self.update_key_desc();
+        true
+    }
+    /// tell whether the verb is triggered by the key or chord
+    pub fn has_key_sequence(&self, sequence: &[KeyEvent]) -> bool {
+        match sequence {
+            [key] => self.keys.contains(key),
+            _ => self.chords.iter().any(|chord| chord == sequence),
+        }
+    }
     pub fn with_alt_key(self, chr: char) -> Self {
         self.with_key(KeyEvent {
             code: KeyCode::Char(chr),
@@ -131,6 +175,10 @@ impl Verb {
             modifiers: KeyModifiers::NONE,
         })
     }
+    pub fn with_confirmation(mut self, confirmation: Confirmation) -> Self {
+        self.confirmation = confirmation;
+        self
+    }
     pub fn with_description(mut self, description: &str) -> Self {
         self.description = VerbDescription::from_text(description.to_string());
         self
@@ -278,4 +326,63 @@ impl Verb {
     pub fn is_sequence(&self) -> bool {
         matches!(self.execution, VerbExecution::Sequence(_))
     }
+
+    /// tell whether executing the verb may modify the files it's
+    /// given (or their tags), in which case it can be repeated with
+    /// `:repeat`, it's disabled in pick mode and it's only listed in
+    /// dry-run mode.
+    /// The verbs run from the shell (like `cd`) and the external
+    /// verbs not receiving any path (like a terminal) aren't concerned.
+    /// The verbs of a sequence are checked one by one on execution.
+    pub fn modifies_files(&self) -> bool {
+        match &self.execution {
+            VerbExecution::Internal(ie) => matches!(
+                ie.internal,
+                Internal::new_dir | Internal::new_file | Internal::clean
+                    | Internal::tag | Internal::untag
+            ),
+            VerbExecution::External(external) => {
+                !external.exec_mode.is_from_shell()
+                    && (external.exec_pattern.has_selection_group()
+                        || external.exec_pattern.has_multi_path_group()
+                        || external.exec_pattern.has_other_panel_group())
+            }
+            VerbExecution::Sequence(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod verb_test {
+
+    use super::*;
+
+    fn external(execution_str: &str, from_shell: bool) -> Verb {
+        let exec_mode = if from_shell {
+            ExternalExecutionMode::FromParentShell
+        } else {
+            ExternalExecutionMode::StayInBroot
+        };
+        let execution = VerbExecution::External(
+            ExternalExecution::new(ExecPattern::from_string(execution_str), exec_mode)
+        );
+        Verb::new(None, execution, VerbDescription::from_code(execution_str.to_string())).unwrap()
+    }
+
+    #[test]
+    fn check_modifies_files() {
+        assert!(external("rm -rf {file}", false).modifies_files());
+        assert!(external("tar czf a.tgz {files}", false).modifies_files());
+        assert!(external("cp -r {file} {other-panel-directory}", false).modifies_files());
+        // `cd` and the verbs not receiving paths are executed in dry-run mode
+        assert!(!external("cd {directory}", true).modifies_files());
+        assert!(!external("$SHELL", false).modifies_files());
+        // the deletion of the cleanable directories is always confirmed
+        let clean = super::super::builtin::builtin_verbs()
+            .into_iter()
+            .find(|verb| verb.is_internal(Internal::clean))
+            .unwrap();
+        assert!(clean.modifies_files());
+        assert_eq!(clean.confirmation, Confirmation::Always);
+    }
 }
@@ -41,6 +41,13 @@ impl VerbStore {
             self.verbs.push(Verb::try_from(vc)?);
         }
         self.verbs.extend(builtin_verbs());
+        for verb in &self.verbs {
+            for chord in &verb.chords {
+                if let Some(details) = self.chord_conflict(chord) {
+                    return Err(ConfError::KeyConflict { details });
+                }
+            }
+        }
         Ok(())
     }
 
@@ -103,6 +110,73 @@ impl VerbStore {
         }
     }
 
+    /// make the key (or chord) trigger the verb of the given index,
+    /// removing it from the verbs it was previously triggering
+    pub fn rebind(&mut self, verb_index: usize, sequence: Vec<KeyEvent>) {
+        for verb in &mut self.verbs {
+            verb.remove_key_sequence(&sequence);
+        }
+        self.verbs[verb_index].add_key_sequence(sequence);
+    }
+
+    /// return the keys and chords triggering several verbs, when one of
+    /// them is hidden by a previous one applying to the same selections,
+    /// with the indexes of all the verbs triggered by the key
+    pub fn key_conflicts(&self) -> Vec<(Vec<KeyEvent>, Vec<usize>)> {
+        let mut conflicts: Vec<(Vec<KeyEvent>, Vec<usize>)> = Vec::new();
+        for verb in &self.verbs {
+            let sequences = verb.keys.iter()
+                .map(|&key| vec![key])
+                .chain(verb.chords.iter().cloned());
+            for sequence in sequences {
+                if conflicts.iter().any(|(s, _)| *s == sequence) {
+                    continue;
+                }
+                let indexes: Vec<usize> = (0..self.verbs.len())
+                    .filter(|&i| self.verbs[i].has_key_sequence(&sequence))
+                    .collect();
+                let hidden = indexes.iter().enumerate().any(|(n, &i)| {
+                    indexes[..n].iter().any(|&prev| {
+                        let prev_condition = self.verbs[prev].selection_condition;
+                        prev_condition == SelectionType::Any
+                            || prev_condition == self.verbs[i].selection_condition
+                    })
+                });
+                if hidden {
+                    conflicts.push((sequence, indexes));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// when binding the key sequence would make a single key both
+    /// trigger a verb and start a chord, return a description of
+    /// the conflict: there would be no way to know whether the
+    /// user wants the single key verb or is starting the chord
+    pub fn chord_conflict(&self, sequence: &[KeyEvent]) -> Option<String> {
+        let verb_name = |index: usize| {
+            self.verbs[index].names.first().map_or("a verb", |name| name.as_str())
+        };
+        match sequence {
+            [key] => self.verbs.iter()
+                .find_map(|verb| verb.chords.iter().find(|chord| chord[0] == *key))
+                .map(|chord| format!(
+                    "{} can't trigger a verb, it starts the chord {}",
+                    keys::key_event_desc(*key),
+                    keys::key_sequence_desc(chord),
+                )),
+            [key, ..] => self.index_of_key(*key)
+                .map(|index| format!(
+                    "the chord {} starts with {}, which already triggers {}",
+                    keys::key_sequence_desc(sequence),
+                    keys::key_event_desc(*key),
+                    verb_name(index),
+                )),
+            [] => None,
+        }
+    }
+
     /// return the index of the verb which is triggered by the given keyboard key, if any
     pub fn index_of_key(&self, key: KeyEvent) -> Option<usize> {
         for i in 0..self.verbs.len() {
@@ -115,6 +189,13 @@ impl VerbStore {
         None
     }
 
+    /// return the verb which a double-click on a line of the
+    /// given selection type triggers, if any
+    pub fn double_click_verb(&self, stype: SelectionType) -> Option<&Verb> {
+        self.verbs.iter()
+            .find(|verb| verb.on_double_click && stype.respects(verb.selection_condition))
+    }
+
     pub fn key_desc_of_internal_stype(
         &self,
         internal: Internal,
@@ -141,3 +222,24 @@ impl VerbStore {
     }
 
 }
+
+#[cfg(test)]
+mod verb_store_test {
+
+    use super::*;
+
+    #[test]
+    fn check_chord_conflicts() {
+        let mut store = VerbStore { verbs: builtin_verbs() };
+        let seq = |s: &str| keys::parse_key_sequence(s).unwrap();
+        // 'j' triggers line_down
+        assert!(store.chord_conflict(&seq("j j")).is_some());
+        assert!(store.chord_conflict(&seq("g g")).is_none());
+        let index = store.verbs.iter()
+            .position(|verb| verb.get_internal() == Some(Internal::select_first))
+            .unwrap();
+        store.verbs[index].add_chord(seq("g g"));
+        assert!(store.chord_conflict(&seq("g")).is_some());
+        assert!(store.chord_conflict(&seq("g t")).is_none());
+    }
+}
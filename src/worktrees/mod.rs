@@ -0,0 +1,3 @@
+mod worktrees_state;
+
+pub use worktrees_state::WorktreesState;
@@ -0,0 +1,376 @@
+use {
+    crate::{
+        app::*,
+        browser::BrowserState,
+        command::*,
+        display::*,
+        errors::ProgramError,
+        git::{self, Worktree},
+        pattern::*,
+        task_sync::Dam,
+        tree::TreeOptions,
+        verb::*,
+    },
+    crossterm::{
+        cursor,
+        QueueableCommand,
+    },
+    std::path::Path,
+};
+
+/// an application state listing the worktrees of a git repository
+pub struct WorktreesState {
+    worktrees: Vec<Worktree>, // not empty
+    /// indexes in worktrees of the ones matching the pattern
+    displayed: Vec<usize>,
+    /// index in displayed
+    selection_idx: usize,
+    scroll: usize,
+    page_height: usize,
+    pattern: Pattern,
+    tree_options: TreeOptions,
+    mode: Mode,
+}
+
+impl WorktreesState {
+    /// create a state listing the worktrees of the repository containing
+    /// the given path, with the worktree containing this path selected
+    pub fn new(
+        path: &Path,
+        tree_options: TreeOptions,
+        con: &AppContext,
+    ) -> Result<WorktreesState, ProgramError> {
+        let worktrees = git::list_worktrees(path)
+            .map_err(|e| ProgramError::Git { details: e.message().to_string() })?;
+        if worktrees.is_empty() {
+            return Err(ProgramError::Git { details: "no worktree found".to_string() });
+        }
+        // the deepest worktree containing the path is the current one
+        let selection_idx = worktrees.iter()
+            .enumerate()
+            .filter(|(_, wt)| path.starts_with(&wt.path))
+            .max_by_key(|(_, wt)| wt.path.components().count())
+            .map_or(0, |(idx, _)| idx);
+        Ok(WorktreesState {
+            displayed: (0..worktrees.len()).collect(),
+            worktrees,
+            selection_idx,
+            scroll: 0,
+            page_height: 0,
+            pattern: Pattern::None,
+            tree_options,
+            mode: initial_mode(con),
+        })
+    }
+
+    pub fn try_scroll(
+        &mut self,
+        cmd: ScrollCommand,
+    ) -> bool {
+        let old_scroll = self.scroll;
+        self.scroll = cmd.apply(self.scroll, self.displayed.len(), self.page_height);
+        self.scroll != old_scroll
+    }
+
+    fn move_line(
+        &mut self,
+        internal_exec: &InternalExecution,
+        input_invocation: Option<&VerbInvocation>,
+        dir: i32, // -1 for up, 1 for down
+        cycle: bool,
+    ) -> CmdResult {
+        let count: i32 = get_arg(input_invocation, internal_exec, 1);
+        let dir = dir * count;
+        self.selection_idx = move_sel(self.selection_idx, self.displayed.len(), dir, cycle);
+        self.make_selection_visible();
+        CmdResult::Keep
+    }
+
+    /// change the scroll, if needed, so that the selection is visible
+    fn make_selection_visible(&mut self) {
+        if self.selection_idx < self.scroll {
+            self.scroll = self.selection_idx;
+        } else if self.page_height > 0 && self.selection_idx >= self.scroll + self.page_height {
+            self.scroll = self.selection_idx + 1 - self.page_height;
+        }
+    }
+
+    fn selected_worktree(&self) -> Option<&Worktree> {
+        self.displayed
+            .get(self.selection_idx)
+            .map(|&idx| &self.worktrees[idx])
+    }
+
+    fn matches(&self, worktree: &Worktree) -> bool {
+        self.pattern.score_of_string(&worktree.name).is_some()
+            || worktree.branch.as_ref().and_then(|b| self.pattern.score_of_string(b)).is_some()
+            || self.pattern.score_of_string(&worktree.path.to_string_lossy()).is_some()
+    }
+}
+
+impl PanelState for WorktreesState {
+
+    fn get_type(&self) -> PanelStateType {
+        PanelStateType::Worktrees
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    fn get_mode(&self) -> Mode {
+        self.mode
+    }
+
+    fn selected_path(&self) -> Option<&Path> {
+        self.selected_worktree().map(|wt| wt.path.as_path())
+    }
+
+    fn tree_options(&self) -> TreeOptions {
+        self.tree_options.clone()
+    }
+
+    fn with_new_options(
+        &mut self,
+        _screen: Screen,
+        change_options: &dyn Fn(&mut TreeOptions),
+        _in_new_panel: bool, // TODO open tree if true
+        _con: &AppContext,
+    ) -> CmdResult {
+        change_options(&mut self.tree_options);
+        CmdResult::Keep
+    }
+
+    fn selection(&self) -> Option<Selection<'_>> {
+        self.selected_path().map(|path| Selection {
+            path,
+            stype: SelectionType::Directory,
+            is_exe: false,
+            line: 0,
+        })
+    }
+
+    fn refresh(&mut self, _screen: Screen, _con: &AppContext) -> Command {
+        Command::empty()
+    }
+
+    fn on_pattern(
+        &mut self,
+        pattern: InputPattern,
+        _app_state: &AppState,
+        _con: &AppContext,
+    ) -> Result<CmdResult, ProgramError> {
+        let selected = self.displayed.get(self.selection_idx).copied();
+        self.pattern = pattern.pattern;
+        self.displayed = (0..self.worktrees.len())
+            .filter(|&idx| self.matches(&self.worktrees[idx]))
+            .collect();
+        self.selection_idx = selected
+            .and_then(|selected| self.displayed.iter().position(|&idx| idx == selected))
+            .unwrap_or(0);
+        self.scroll = 0;
+        Ok(CmdResult::Keep)
+    }
+
+    fn no_verb_status(
+        &self,
+        _has_previous_state: bool,
+        _con: &AppContext,
+    ) -> Status {
+        if self.pattern.is_some() {
+            Status::from_message(
+                "Hit *enter* to open the selected worktree, or *esc* to clear the filter"
+            )
+        } else {
+            Status::from_message(
+                "Hit *enter* to open the selected worktree, *esc* to get back, or a few letters to search"
+            )
+        }
+    }
+
+    fn display(
+        &mut self,
+        w: &mut W,
+        disc: &DisplayContext,
+    ) -> Result<(), ProgramError> {
+        let area = &disc.state_area;
+        let con = &disc.con;
+        let page_height = (area.height as usize).max(2) - 2;
+        if page_height != self.page_height {
+            // the panel was resized
+            self.page_height = page_height;
+            self.make_selection_visible();
+        }
+        let scrollbar = area.scrollbar(self.scroll as i32, self.displayed.len() as i32);
+        let styles = &disc.panel_skin.styles;
+        let border_style = &styles.help_table_border;
+        let width = area.width as usize;
+        let w_name = self.worktrees.iter()
+            .map(|wt| wt.name.chars().count())
+            .max().unwrap_or(0)
+            .max("worktree".len());
+        let mut wc_name = w_name;
+        if con.show_selection_mark {
+            wc_name += 1;
+        }
+        let w_branch = self.worktrees.iter()
+            .filter_map(|wt| wt.branch.as_ref())
+            .map(|b| b.chars().count())
+            .max().unwrap_or(0)
+            .max("branch".len());
+        //- titles
+        w.queue(cursor::MoveTo(area.left, area.top))?;
+        let mut cw = CropWriter::new(w, width);
+        cw.queue_g_string(&styles.default, format!("{:width$}", "worktree", width = wc_name))?;
+        cw.queue_char(border_style, '│')?;
+        cw.queue_g_string(&styles.default, format!("{:^width$}", "branch", width = w_branch))?;
+        cw.queue_char(border_style, '│')?;
+        cw.queue_str(&styles.default, "path")?;
+        cw.fill(border_style, &SPACE_FILLING)?;
+        //- horizontal line
+        w.queue(cursor::MoveTo(area.left, 1 + area.top))?;
+        let mut cw = CropWriter::new(w, width);
+        cw.queue_g_string(border_style, format!("{:─>width$}", '┼', width = wc_name + 1))?;
+        cw.queue_g_string(border_style, format!("{:─>width$}", '┼', width = w_branch + 1))?;
+        cw.fill(border_style, &BRANCH_FILLING)?;
+        //- content
+        let mut idx = self.scroll;
+        for y in 2..area.height {
+            w.queue(cursor::MoveTo(area.left, y + area.top))?;
+            let selected = self.selection_idx == idx;
+            let mut cw = CropWriter::new(w, width - 1); // -1 for scrollbar
+            let txt_style = if selected { &styles.selected_line } else { &styles.default };
+            if let Some(worktree) = self.displayed.get(idx).map(|&i| &self.worktrees[i]) {
+                let mut border_style = border_style.clone();
+                if let Some(bg) = txt_style.get_bg() {
+                    border_style.set_bg(bg);
+                }
+                if con.show_selection_mark {
+                    cw.queue_char(txt_style, if selected { '▶' } else { ' ' })?;
+                }
+                let name_style = if worktree.is_main { &styles.directory } else { &styles.file };
+                let mut name_style = name_style.clone();
+                if let Some(bg) = txt_style.get_bg() {
+                    name_style.set_bg(bg);
+                }
+                cw.queue_g_string(&name_style, format!("{:width$}", &worktree.name, width = w_name))?;
+                cw.queue_char(&border_style, '│')?;
+                let mut branch_style = styles.git_branch.clone();
+                if let Some(bg) = txt_style.get_bg() {
+                    branch_style.set_bg(bg);
+                }
+                cw.queue_g_string(
+                    &branch_style,
+                    format!("{:width$}", worktree.branch.as_deref().unwrap_or(""), width = w_branch),
+                )?;
+                cw.queue_char(&border_style, '│')?;
+                cw.queue_str(txt_style, &worktree.path.to_string_lossy())?;
+                idx += 1;
+            }
+            cw.fill(txt_style, &SPACE_FILLING)?;
+            let scrollbar_style = if ScrollCommand::is_thumb(y, scrollbar) {
+                &styles.scrollbar_thumb
+            } else {
+                &styles.scrollbar_track
+            };
+            scrollbar_style.queue_str(w, "▐")?;
+        }
+        Ok(())
+    }
+
+    fn on_internal(
+        &mut self,
+        w: &mut W,
+        internal_exec: &InternalExecution,
+        input_invocation: Option<&VerbInvocation>,
+        trigger_type: TriggerType,
+        app_state: &mut AppState,
+        cc: &CmdContext,
+    ) -> Result<CmdResult, ProgramError> {
+        let screen = cc.app.screen;
+        let con = &cc.app.con;
+        Ok(match internal_exec.internal {
+            Internal::back => {
+                if self.pattern.is_some() {
+                    self.on_pattern(InputPattern::none(), app_state, con)?
+                } else {
+                    CmdResult::PopState
+                }
+            }
+            Internal::line_down => {
+                self.move_line(internal_exec, input_invocation, 1, true)
+            }
+            Internal::line_up => {
+                self.move_line(internal_exec, input_invocation, -1, true)
+            }
+            Internal::line_down_no_cycle => {
+                self.move_line(internal_exec, input_invocation, 1, false)
+            }
+            Internal::line_up_no_cycle => {
+                self.move_line(internal_exec, input_invocation, -1, false)
+            }
+            Internal::open_stay | Internal::focus => {
+                match self.selected_path() {
+                    Some(path) => {
+                        let in_new_panel = input_invocation
+                            .map(|inv| inv.bang)
+                            .unwrap_or(internal_exec.bang);
+                        CmdResult::from_optional_state(
+                            BrowserState::new(
+                                path.to_path_buf(),
+                                self.tree_options(),
+                                screen,
+                                con,
+                                &Dam::unlimited(),
+                            ),
+                            in_new_panel,
+                        )
+                    }
+                    None => CmdResult::error("no selected worktree"),
+                }
+            }
+            Internal::scroll_down => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.try_scroll(ScrollCommand::Lines(count));
+                CmdResult::Keep
+            }
+            Internal::scroll_up => {
+                let count: i32 = get_arg(input_invocation, internal_exec, 1);
+                self.try_scroll(ScrollCommand::Lines(-count));
+                CmdResult::Keep
+            }
+            Internal::page_down => {
+                self.try_scroll(ScrollCommand::Pages(1));
+                CmdResult::Keep
+            }
+            Internal::page_up => {
+                self.try_scroll(ScrollCommand::Pages(-1));
+                CmdResult::Keep
+            }
+            _ => self.on_internal_generic(
+                w,
+                internal_exec,
+                input_invocation,
+                trigger_type,
+                app_state,
+                cc,
+            )?,
+        })
+    }
+
+    fn on_click(
+        &mut self,
+        _x: u16,
+        y: u16,
+        _screen: Screen,
+        _con: &AppContext,
+    ) -> Result<CmdResult, ProgramError> {
+        if y >= 2 {
+            let y = y as usize - 2 + self.scroll;
+            if y < self.displayed.len() {
+                self.selection_idx = y;
+            }
+        }
+        Ok(CmdResult::Keep)
+    }
+}